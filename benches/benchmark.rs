@@ -1,20 +1,21 @@
+use std::env;
+
 use aoc::{
-    day6::{self},
-    day8::{self},
-    utils::{get_day_input, parse_input},
+    bench::{bench_comparisons, bench_days, days_for_filter, filter_from_args},
+    registry,
 };
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::Criterion;
 
-pub fn benchmark_day6(c: &mut Criterion) {
-    let input = parse_input(get_day_input("day6"));
-    c.bench_function("day6", |b| b.iter(|| day6::part2(black_box(&input))));
-}
+///
+/// Criterion's `criterion_main!`, except only the days the filter names are set up, e.g.
+/// `cargo bench -- day16`. `--quick` also takes fewer samples of the slow days.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let days = days_for_filter(registry().into_keys().collect(), filter_from_args(&args));
+    let quick = args.iter().any(|arg| arg == "--quick");
 
-pub fn benchmark_day8(c: &mut Criterion) {
-    let input = parse_input(get_day_input("day8"));
-    c.bench_function("day8", |b| b.iter(|| day8::part2(black_box(&input))));
+    let mut criterion = Criterion::default().configure_from_args();
+    bench_days(&mut criterion, &days, quick);
+    bench_comparisons(&mut criterion, &days, quick);
+    criterion.final_summary();
 }
-
-// criterion_group!(benches, benchmark_day6);
-criterion_group!(benches, benchmark_day8);
-criterion_main!(benches);
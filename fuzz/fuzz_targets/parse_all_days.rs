@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// the first byte picks the day, the rest is the input. Only parsing is fuzzed, since solving an
+// arbitrary input may never finish
+fuzz_target!(|data: &[u8]| {
+    let Some((&day, input)) = data.split_first() else {
+        return;
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return;
+    };
+    if let Some(solution) = aoc::get_solution(u32::from(day % 26)) {
+        let _ = solution.prepare(input);
+    }
+});
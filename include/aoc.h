@@ -0,0 +1,24 @@
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define AOC_OK 0
+#define AOC_NULL_POINTER -1
+#define AOC_INVALID_INPUT -2
+#define AOC_UNKNOWN_DAY -3
+#define AOC_INVALID_PART -4
+#define AOC_SOLVE_FAILED -5
+#define AOC_BUFFER_TOO_SMALL -6
+#define AOC_PANIC -7
+
+/*
+ * Solve a part of a day. `out_len` holds the size of `out_buf` and is set to the length of the
+ * answer, which isn't nul terminated. On AOC_SOLVE_FAILED the error message is written instead,
+ * and on AOC_BUFFER_TOO_SMALL `out_len` is set to the required size.
+ */
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input_ptr, size_t input_len,
+                  uint8_t *out_buf, size_t *out_len);
+
+#endif
@@ -0,0 +1,480 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+use criterion::{black_box, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use serde::Deserialize;
+
+use crate::{
+    day1,
+    day10::{self, Day10},
+    day2::{self, GameSet},
+    day5::{self, Day5},
+    day6::{self, Day6},
+    gen::generate,
+    registry,
+    runner::read_day_input,
+    solution::{Part, Solution},
+    utils::{get_day_test_input, read_input_file},
+};
+
+pub const BENCH_DIR: &str = "target/criterion";
+
+/// Days with a part slow enough that criterion's default 100 samples take minutes
+const SLOW_DAYS: [u32; 2] = [5, 16];
+
+/// How many samples the slow days take in quick mode, the least criterion accepts
+const QUICK_SAMPLE_SIZE: usize = 10;
+
+/// How many copies of day 1's input to match the digits of, so the matchers' setup doesn't dominate
+const DAY1_INPUT_REPEATS: usize = 100;
+
+/// How many games the generated day 2 input has, enough for parsing them to spread over every core
+const DAY2_GAMES: usize = 50_000;
+
+/// Criterion's options which take a value, so the value isn't mistaken for the filter
+const VALUE_OPTIONS: [&str; 16] = [
+    "-c",
+    "--color",
+    "-s",
+    "--save-baseline",
+    "-b",
+    "--baseline",
+    "--baseline-lenient",
+    "--load-baseline",
+    "--format",
+    "--profile-time",
+    "--sample-size",
+    "--warm-up-time",
+    "--measurement-time",
+    "--nresamples",
+    "--noise-threshold",
+    "--significance-level",
+];
+
+/// Where criterion keeps the measurements of the latest run
+const LATEST_RUN: &str = "new";
+
+///
+/// The filter criterion was given on the command line, e.g. `day16` in `cargo bench -- day16`
+pub fn filter_from_args(args: &[String]) -> Option<&str> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if VALUE_OPTIONS.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with('-') {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+///
+/// Narrow the days down to the one named by a filter like `day16` or `day16/part2`, so the other
+/// days don't even read their inputs, and `day1` doesn't run days 10 to 19. Any other filter is
+/// left to criterion.
+///
+pub fn days_for_filter(days: Vec<u32>, filter: Option<&str>) -> Vec<u32> {
+    let Some(rest) = filter.and_then(|filter| filter.strip_prefix("day")) else {
+        return days;
+    };
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (day, rest) = rest.split_at(digits);
+    match day.parse::<u32>() {
+        Ok(day) if rest.is_empty() || rest.starts_with(['/', '-']) => {
+            days.into_iter().filter(|x| *x == day).collect()
+        }
+        _ => days,
+    }
+}
+
+///
+/// Fewer samples for the slow days in quick mode, the other days are fast enough as is
+fn set_sample_size<M: criterion::measurement::Measurement>(
+    group: &mut BenchmarkGroup<M>,
+    day: u32,
+    quick: bool,
+) {
+    if quick && SLOW_DAYS.contains(&day) {
+        group.sample_size(QUICK_SAMPLE_SIZE);
+    }
+}
+
+///
+/// A `dayN/partM` benchmark for every part of the given days. Days without an input and parts
+/// which fail to solve are skipped. Returns the parts which were benchmarked.
+///
+pub fn bench_days(c: &mut Criterion, days: &[u32], quick: bool) -> Vec<(u32, Part)> {
+    let mut benchmarked = Vec::new();
+    for (day, solution) in registry() {
+        if !days.contains(&day) {
+            continue;
+        }
+
+        let input = match read_day_input(day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day{day}: {e:#}");
+                continue;
+            }
+        };
+
+        let prepared = match solution.prepare(&input) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                eprintln!("skipping day{day}, failed to parse: {e:#}");
+                continue;
+            }
+        };
+
+        let mut group = c.benchmark_group(format!("day{day}"));
+        set_sample_size(&mut group, day, quick);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        for part in Part::ALL {
+            if let Err(e) = prepared(part) {
+                eprintln!("skipping day{day} part{part}: {e:#}");
+                continue;
+            }
+
+            group.bench_function(format!("part{part}"), |b| {
+                b.iter(|| prepared(black_box(part)))
+            });
+            benchmarked.push((day, part));
+        }
+        group.finish();
+    }
+
+    benchmarked
+}
+
+/// A named way of solving a part
+type Strategy<I, R> = (&'static str, fn(&I) -> R);
+
+///
+/// Benchmark the strategies in one `dayN-partM` group, so criterion's report shows how they
+/// compare on the same input
+fn bench_strategies<I, R>(
+    c: &mut Criterion,
+    day: u32,
+    part: Part,
+    input: &I,
+    strategies: &[Strategy<I, R>],
+    quick: bool,
+) {
+    let mut group = c.benchmark_group(format!("day{day}-part{part}"));
+    set_sample_size(&mut group, day, quick);
+    for (name, solve) in strategies {
+        group.bench_with_input(BenchmarkId::from_parameter(name), input, |b, input| {
+            b.iter(|| solve(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn parse_for_comparison<S: Solution>(
+    day: u32,
+    input: &anyhow::Result<String>,
+) -> Option<S::Input<'_>> {
+    let parsed = match input {
+        Ok(input) => S::parse(input),
+        Err(e) => Err(anyhow::anyhow!("{e:#}")),
+    };
+    match parsed {
+        Ok(parsed) => Some(parsed),
+        Err(e) => {
+            eprintln!("skipping the day{day} comparison: {e:#}");
+            None
+        }
+    }
+}
+
+///
+/// Benchmark the days which have more than one way of solving a part, like brute force next to
+/// the optimized solution, to show the speedup
+///
+pub fn bench_comparisons(c: &mut Criterion, days: &[u32], quick: bool) {
+    if days.contains(&1) {
+        match read_day_input(1) {
+            Ok(input) => {
+                let input = input.repeat(DAY1_INPUT_REPEATS);
+                let strategies: [Strategy<_, _>; 2] = [
+                    ("trie", |x: &&str| day1::sum_calibration_values(x)),
+                    ("scanning", |x: &&str| {
+                        day1::sum_calibration_values_scanning(x)
+                    }),
+                ];
+                bench_strategies(c, 1, Part::Two, &input.as_str(), &strategies, quick);
+            }
+            Err(e) => eprintln!("skipping the day1 comparison: {e:#}"),
+        }
+    }
+
+    // the parallel solvers only pay off with far more games than the actual input has
+    if days.contains(&2) {
+        match generate(2, DAY2_GAMES, 0) {
+            Ok(input) => {
+                let part1: [Strategy<_, _>; 2] = [
+                    ("sequential", |x: &&str| {
+                        x.parse::<GameSet>().map(|games| day2::part1(&games))
+                    }),
+                    ("parallel", |x: &&str| {
+                        GameSet::par_parse(x).map(|games| day2::par_part1(&games))
+                    }),
+                ];
+                bench_strategies(c, 2, Part::One, &input.as_str(), &part1, quick);
+                let part2: [Strategy<_, _>; 2] = [
+                    ("sequential", |x: &&str| {
+                        x.parse::<GameSet>().map(|games| day2::part2(&games))
+                    }),
+                    ("parallel", |x: &&str| {
+                        GameSet::par_parse(x).map(|games| day2::par_part2(&games))
+                    }),
+                ];
+                bench_strategies(c, 2, Part::Two, &input.as_str(), &part2, quick);
+            }
+            Err(e) => eprintln!("skipping the day2 comparison: {e:#}"),
+        }
+    }
+
+    // solving seed by seed takes far too long on the actual input
+    if days.contains(&5) {
+        let input = read_input_file(get_day_test_input("day5")).map_err(Into::into);
+        if let Some(almanac) = parse_for_comparison::<Day5>(5, &input) {
+            let strategies: [Strategy<_, _>; 3] = [
+                ("per-seed", day5::part2),
+                ("reverse-scan", day5::part2_reverse_scan),
+                ("ranges", day5::part2_ranges),
+            ];
+            bench_strategies(c, 5, Part::Two, &almanac, &strategies, quick);
+        }
+    }
+
+    if days.contains(&6) {
+        let input = read_day_input(6);
+        if let Some(races) = parse_for_comparison::<Day6>(6, &input) {
+            let part1: [Strategy<_, _>; 2] = [
+                ("quadratic", day6::part1),
+                ("brute-force", day6::part1_brute_force),
+            ];
+            bench_strategies(c, 6, Part::One, &races, &part1, quick);
+            let part2: [Strategy<_, _>; 2] = [
+                ("quadratic", day6::part2),
+                ("brute-force", day6::part2_brute_force),
+            ];
+            bench_strategies(c, 6, Part::Two, &races, &part2, quick);
+        }
+    }
+
+    if days.contains(&10) {
+        let input = read_day_input(10);
+        if let Some(grid) = parse_for_comparison::<Day10>(10, &input) {
+            let strategies: [Strategy<_, _>; 2] = [
+                ("shoelace", day10::part2),
+                ("ray-casting", day10::part2_ray_casting),
+            ];
+            bench_strategies(c, 10, Part::Two, &grid, &strategies, quick);
+        }
+    }
+
+    #[cfg(feature = "arena")]
+    bench_arena_comparisons(c, days, quick);
+}
+
+///
+/// The days with an arena variant, allocating from the heap against allocating from the arena
+#[cfg(feature = "arena")]
+fn bench_arena_comparisons(c: &mut Criterion, days: &[u32], quick: bool) {
+    use crate::{
+        day13::{self, Day13},
+        day15::{self, Day15},
+        day9::{self, Day9},
+    };
+
+    if days.contains(&9) {
+        let input = read_day_input(9);
+        if let Some(history) = parse_for_comparison::<Day9>(9, &input) {
+            let part1: [Strategy<_, _>; 2] = [
+                ("heap", |x: &Vec<_>| day9::part1(x)),
+                ("arena", |x: &Vec<_>| day9::part1_arena(x)),
+            ];
+            bench_strategies(c, 9, Part::One, &history, &part1, quick);
+            let part2: [Strategy<_, _>; 2] = [
+                ("heap", |x: &Vec<_>| day9::part2(x)),
+                ("arena", |x: &Vec<_>| day9::part2_arena(x)),
+            ];
+            bench_strategies(c, 9, Part::Two, &history, &part2, quick);
+        }
+    }
+
+    if days.contains(&13) {
+        let input = read_day_input(13);
+        if let Some(patterns) = parse_for_comparison::<Day13>(13, &input) {
+            let part1: [Strategy<_, _>; 2] =
+                [("heap", day13::part1), ("arena", day13::part1_arena)];
+            bench_strategies(c, 13, Part::One, &patterns, &part1, quick);
+            let part2: [Strategy<_, _>; 2] =
+                [("heap", day13::part2), ("arena", day13::part2_arena)];
+            bench_strategies(c, 13, Part::Two, &patterns, &part2, quick);
+        }
+    }
+
+    if days.contains(&15) {
+        let input = read_day_input(15);
+        if let Some(input) = parse_for_comparison::<Day15>(15, &input) {
+            let strategies: [Strategy<_, _>; 2] = [
+                ("heap", |x: &&str| day15::part2(x)),
+                ("arena", |x: &&str| day15::part2_arena(x)),
+            ];
+            bench_strategies(c, 15, Part::Two, &input, &strategies, quick);
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+/// The subset of criterion's `estimates.json` we care about, times are in nanoseconds
+#[derive(Debug, Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+///
+/// The mean time of a part in a baseline saved by criterion under `dir`
+pub fn load_mean(dir: &Path, day: u32, part: Part, baseline: &str) -> anyhow::Result<Duration> {
+    let path: PathBuf = [
+        dir,
+        Path::new(&format!("day{day}")),
+        Path::new(&format!("part{part}")),
+        Path::new(baseline),
+        Path::new("estimates.json"),
+    ]
+    .iter()
+    .collect();
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("no {baseline} baseline for day {day} part {part}"))?;
+    let estimates: Estimates = serde_json::from_str(&contents)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+    Ok(Duration::from_secs_f64(estimates.mean.point_estimate / 1e9))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub part: Part,
+    pub baseline: Duration,
+    pub latest: Duration,
+}
+
+impl Comparison {
+    ///
+    /// How much slower the latest run is, in percent. Negative when it got faster.
+    pub fn change_percent(&self) -> f64 {
+        (self.latest.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+
+    pub fn regressed(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {} part {}: {:.2?} -> {:.2?} ({:+.1}%)",
+            self.day,
+            self.part,
+            self.baseline,
+            self.latest,
+            self.change_percent()
+        )
+    }
+}
+
+///
+/// Compare the latest run of each part against a saved baseline
+pub fn compare_to_baseline(
+    dir: &Path,
+    parts: &[(u32, Part)],
+    baseline: &str,
+) -> anyhow::Result<Vec<Comparison>> {
+    parts
+        .iter()
+        .map(|&(day, part)| {
+            Ok(Comparison {
+                day,
+                part,
+                baseline: load_mean(dir, day, part, baseline)?,
+                latest: load_mean(dir, day, part, LATEST_RUN)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn write_estimates(dir: &Path, baseline: &str, mean_ns: f64) {
+        let path = dir.join("day8").join("part1").join(baseline);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(
+            path.join("estimates.json"),
+            format!(r#"{{"mean":{{"point_estimate":{mean_ns},"standard_error":1.0}}}}"#),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_days_for_filter() {
+        let days = vec![1, 6, 10, 16];
+        let args = |args: &[&str]| args.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+        let args_with_filter = args(&["--save-baseline", "main", "--bench", "day1"]);
+        let filter = filter_from_args(&args_with_filter);
+        assert_eq!(filter, Some("day1"));
+        assert_eq!(days_for_filter(days.clone(), filter), vec![1]);
+        assert_eq!(days_for_filter(days.clone(), Some("day16/part2")), vec![16]);
+        assert_eq!(days_for_filter(days.clone(), Some("day6-part1")), vec![6]);
+        assert_eq!(
+            days_for_filter(days.clone(), Some("day25")),
+            Vec::<u32>::new()
+        );
+        assert_eq!(days_for_filter(days.clone(), Some("part1")), days);
+        assert_eq!(days_for_filter(days.clone(), Some("day1.*")), days);
+        assert_eq!(filter_from_args(&args(&["--bench", "-s", "main"])), None);
+    }
+
+    #[test]
+    fn test_compare_to_baseline() {
+        let dir = env::temp_dir().join(format!("aoc-bench-test-{}", std::process::id()));
+        write_estimates(&dir, "main", 1_000_000.0);
+        write_estimates(&dir, LATEST_RUN, 1_250_000.0);
+
+        let comparisons = compare_to_baseline(&dir, &[(8, Part::One)], "main");
+        let missing = compare_to_baseline(&dir, &[(8, Part::Two)], "main");
+        fs::remove_dir_all(&dir).unwrap();
+
+        let comparison = &comparisons.unwrap()[0];
+        assert_eq!(comparison.baseline, Duration::from_millis(1));
+        assert!(comparison.regressed(10.0));
+        assert!(!comparison.regressed(30.0));
+        assert_eq!(
+            comparison.to_string(),
+            "day 8 part 1: 1.00ms -> 1.25ms (+25.0%)"
+        );
+        assert!(missing.is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use std::{collections::BTreeMap, env, fs, io::ErrorKind, path::Path, time::Duration};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    runner::{read_day_input, run_day_with_input},
+    solution::{Answer, DayRun, Part, PartRun},
+};
+
+pub const CACHE_FILE: &str = ".aoc-cache.json";
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+///
+/// Identifies the code that produced the answers, so answers from an older build are ignored.
+/// This is the hash of the running executable, falling back to the crate version.
+///
+pub fn code_fingerprint() -> String {
+    env::current_exe()
+        .and_then(fs::read)
+        .map(|exe| sha256_hex(&exe))
+        .unwrap_or_else(|_| format!("version-{}", env!("CARGO_PKG_VERSION")))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheEntry {
+    input_hash: String,
+    code_hash: String,
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+impl CacheEntry {
+    fn answer(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    fn answer_mut(&mut self, part: Part) -> &mut Option<Answer> {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+///
+/// Answers of previous runs, keyed by day. An answer is only valid for the same input and code.
+///
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    days: BTreeMap<u32, CacheEntry>,
+}
+
+impl Cache {
+    ///
+    /// Load the cache, a missing file is an empty cache
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+
+        serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse cache file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = serde_json::to_string_pretty(self).context("failed to serialize cache")?;
+        fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, day: u32, input_hash: &str, code_hash: &str, part: Part) -> Option<&Answer> {
+        self.days
+            .get(&day)
+            .filter(|entry| entry.input_hash == input_hash && entry.code_hash == code_hash)?
+            .answer(part)
+    }
+
+    ///
+    /// Record an answer, dropping the answers recorded for a different input or code
+    pub fn insert(
+        &mut self,
+        day: u32,
+        input_hash: &str,
+        code_hash: &str,
+        part: Part,
+        answer: Answer,
+    ) {
+        let entry = self.days.entry(day).or_default();
+        if entry.input_hash != input_hash || entry.code_hash != code_hash {
+            *entry = CacheEntry {
+                input_hash: input_hash.to_string(),
+                code_hash: code_hash.to_string(),
+                ..Default::default()
+            };
+        }
+        *entry.answer_mut(part) = Some(answer);
+    }
+
+    ///
+    /// Like `run_day_with_input`, but answers in the cache are returned instantly with zero
+    /// timings, and new answers are recorded
+    pub fn run_day_with_input(
+        &mut self,
+        day: u32,
+        input: &str,
+        parts: &[Part],
+        code_hash: &str,
+    ) -> anyhow::Result<DayRun> {
+        let input_hash = sha256_hex(input.as_bytes());
+        let cached: Option<Vec<PartRun>> = parts
+            .iter()
+            .map(|&part| {
+                self.get(day, &input_hash, code_hash, part)
+                    .map(|answer| PartRun {
+                        part,
+                        answer: Ok(answer.clone()),
+                        solve_time: Duration::ZERO,
+                        peak_memory: None,
+                    })
+            })
+            .collect();
+
+        if let Some(parts) = cached {
+            return Ok(DayRun {
+                parse_time: Duration::ZERO,
+                parts,
+            });
+        }
+
+        let day_run = run_day_with_input(day, input, parts)?;
+        for part_run in &day_run.parts {
+            if let Ok(answer) = &part_run.answer {
+                self.insert(day, &input_hash, code_hash, part_run.part, answer.clone());
+            }
+        }
+
+        Ok(day_run)
+    }
+
+    pub fn run_day(&mut self, day: u32, parts: &[Part], code_hash: &str) -> anyhow::Result<DayRun> {
+        let input = read_day_input(day)?;
+        self.run_day_with_input(day, &input, parts, code_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+    fn get_answers(day_run: &DayRun) -> Vec<String> {
+        day_run
+            .parts
+            .iter()
+            .map(|x| x.answer.as_ref().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_cache_hit() {
+        let mut cache = Cache::default();
+        let day_run = cache
+            .run_day_with_input(6, INPUT, &Part::ALL, "code")
+            .unwrap();
+        assert_eq!(get_answers(&day_run), vec!["288", "71503"]);
+
+        // a cached answer doesn't need a valid input to be solved
+        let input_hash = sha256_hex(INPUT.as_bytes());
+        cache.insert(6, &input_hash, "code", Part::One, "cached".into());
+        let day_run = cache
+            .run_day_with_input(6, INPUT, &Part::ALL, "code")
+            .unwrap();
+        assert_eq!(get_answers(&day_run), vec!["cached", "71503"]);
+        assert_eq!(day_run.total_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_cache_invalidation() {
+        let mut cache = Cache::default();
+        cache.insert(6, "input", "code", Part::One, Answer::U64(1));
+        cache.insert(6, "input", "code", Part::Two, Answer::U64(2));
+        assert_eq!(
+            cache.get(6, "input", "code", Part::Two),
+            Some(&Answer::U64(2))
+        );
+        assert_eq!(cache.get(6, "other input", "code", Part::Two), None);
+        assert_eq!(cache.get(6, "input", "other code", Part::Two), None);
+
+        cache.insert(6, "input", "new code", Part::One, Answer::U64(3));
+        assert_eq!(
+            cache.get(6, "input", "new code", Part::One),
+            Some(&Answer::U64(3))
+        );
+        assert_eq!(cache.get(6, "input", "new code", Part::Two), None);
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let path = env::temp_dir().join(format!("aoc-cache-test-{}.json", std::process::id()));
+        let mut cache = Cache::default();
+        cache.insert(6, "input", "code", Part::One, Answer::U64(288));
+        cache.save(&path).unwrap();
+
+        let loaded = Cache::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            loaded.get(6, "input", "code", Part::One),
+            Some(&Answer::U64(288))
+        );
+        assert!(Cache::load(&path).unwrap().days.is_empty());
+    }
+}
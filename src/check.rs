@@ -0,0 +1,127 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+
+use crate::get_solution;
+
+///
+/// The shape of an input, to spot truncated or mangled downloads at a glance
+///
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct InputStats {
+    pub lines: usize,
+    /// Groups of lines separated by blank lines
+    pub blocks: usize,
+    /// The widths of the shortest and longest lines, ignoring blank lines
+    pub min_width: usize,
+    pub max_width: usize,
+}
+
+impl InputStats {
+    pub fn new(input: &str) -> Self {
+        let widths: Vec<usize> = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().count())
+            .collect();
+        let blocks = input
+            .split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .count();
+
+        Self {
+            lines: input.lines().count(),
+            blocks,
+            min_width: widths.iter().copied().min().unwrap_or(0),
+            max_width: widths.iter().copied().max().unwrap_or(0),
+        }
+    }
+
+    ///
+    /// Whether every line has the same width, like a grid
+    pub fn is_rectangular(&self) -> bool {
+        self.min_width == self.max_width
+    }
+}
+
+impl Display for InputStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} lines in {} blocks, ", self.lines, self.blocks)?;
+        if self.is_rectangular() {
+            write!(f, "{} columns", self.max_width)
+        } else {
+            write!(f, "{} to {} columns", self.min_width, self.max_width)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CheckReport {
+    pub day: u32,
+    pub stats: InputStats,
+    /// How long parsing took, or why it failed
+    pub parse: anyhow::Result<Duration>,
+}
+
+impl Display for CheckReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "day {} input: {}", self.day, self.stats)?;
+        match &self.parse {
+            Ok(parse_time) => write!(f, "parsed in {parse_time:.2?}"),
+            Err(e) => write!(f, "ERROR, {e:#}"),
+        }
+    }
+}
+
+///
+/// Parse the input of a day without solving anything
+pub fn check(day: u32, input: &str) -> anyhow::Result<CheckReport> {
+    let solution = get_solution(day).with_context(|| format!("day {day} isn't implemented"))?;
+    let start = Instant::now();
+    let parse = solution.prepare(input).map(|_| start.elapsed());
+
+    Ok(CheckReport {
+        day,
+        stats: InputStats::new(input),
+        parse,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_stats() {
+        let stats = InputStats::new("#.#\n..#\n\n###\n#..\n");
+        assert_eq!(
+            stats,
+            InputStats {
+                lines: 5,
+                blocks: 2,
+                min_width: 3,
+                max_width: 3,
+            }
+        );
+        assert_eq!(stats.to_string(), "5 lines in 2 blocks, 3 columns");
+        assert_eq!(
+            InputStats::new("abc\nde").to_string(),
+            "2 lines in 1 blocks, 2 to 3 columns"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let report = check(6, "Time:      7  15   30\nDistance:  9  40  200\n").unwrap();
+        assert!(report.parse.is_ok());
+
+        let report = check(4, "Card 1: 1 2 | 3 4\nCard 2: 1 x | 3 4\n").unwrap();
+        let error = format!("{:#}", report.parse.unwrap_err());
+        assert!(error.contains("line 2"), "{error}");
+
+        assert!(check(26, "").is_err());
+    }
+}
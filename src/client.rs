@@ -0,0 +1,222 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{solution::Part, utils::input_dir};
+
+const BASE_URL: &str = "https://adventofcode.com/2023";
+const SESSION_ENV_VAR: &str = "AOC_SESSION";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SubmissionResult {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// We submitted too recently, contains how long is left if the site told us
+    Wait(Option<String>),
+    /// The part was already solved, or part 1 isn't solved yet
+    WrongLevel,
+    Unknown(String),
+}
+
+impl Display for SubmissionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionResult::Correct => write!(f, "correct!"),
+            SubmissionResult::TooHigh => write!(f, "incorrect, the answer is too high"),
+            SubmissionResult::TooLow => write!(f, "incorrect, the answer is too low"),
+            SubmissionResult::Incorrect => write!(f, "incorrect"),
+            SubmissionResult::Wait(Some(left)) => write!(f, "submitted too recently, {left}"),
+            SubmissionResult::Wait(None) => write!(f, "submitted too recently"),
+            SubmissionResult::WrongLevel => {
+                write!(f, "not solving the right level, was it already solved?")
+            }
+            SubmissionResult::Unknown(response) => write!(f, "unknown response: {response}"),
+        }
+    }
+}
+
+///
+/// Extract the message from the `<article>` element of the response, which is where the site
+/// puts the outcome of the submission
+fn get_article_text(html: &str) -> &str {
+    let Some(start) = html.find("<article>") else {
+        return html.trim();
+    };
+    let html = &html[start + "<article>".len()..];
+    let end = html.find("</article>").unwrap_or(html.len());
+    html[..end].trim()
+}
+
+fn parse_submission_response(html: &str) -> SubmissionResult {
+    let article = get_article_text(html);
+    if article.contains("That's the right answer") {
+        SubmissionResult::Correct
+    } else if article.contains("too high") {
+        SubmissionResult::TooHigh
+    } else if article.contains("too low") {
+        SubmissionResult::TooLow
+    } else if article.contains("That's not the right answer") {
+        SubmissionResult::Incorrect
+    } else if article.contains("You gave an answer too recently") {
+        // You have 4m 20s left to wait.
+        let left = article
+            .find("You have ")
+            .and_then(|start| {
+                let rest = &article[start + "You have ".len()..];
+                rest.find(" left to wait").map(|end| &rest[..end])
+            })
+            .map(|left| format!("{left} left to wait"));
+        SubmissionResult::Wait(left)
+    } else if article.contains("You don't seem to be solving the right level") {
+        SubmissionResult::WrongLevel
+    } else {
+        SubmissionResult::Unknown(article.to_string())
+    }
+}
+
+///
+/// Talks to adventofcode.com using the session cookie of a logged in user
+pub struct AocClient {
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(session: String) -> Self {
+        Self {
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent("github.com/RoyShulman/advent-of-code-2023")
+                .build(),
+        }
+    }
+
+    ///
+    /// Create a client with the session token from the `AOC_SESSION` environment variable
+    pub fn from_env() -> anyhow::Result<Self> {
+        let session = env::var(SESSION_ENV_VAR)
+            .with_context(|| format!("missing session token, set {SESSION_ENV_VAR}"))?;
+        Ok(Self::new(session))
+    }
+
+    ///
+    /// Create a client with the session token from `AOC_SESSION`, or from the session file if
+    /// the variable isn't set
+    pub fn from_env_or_file(session_file: Option<&Path>) -> anyhow::Result<Self> {
+        match (env::var(SESSION_ENV_VAR), session_file) {
+            (Err(_), Some(path)) => {
+                let session = fs::read_to_string(path).with_context(|| {
+                    format!("failed to read the session token from {}", path.display())
+                })?;
+                Ok(Self::new(session))
+            }
+            _ => Self::from_env(),
+        }
+    }
+
+    fn session_cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn fetch_input(&self, day: u32) -> anyhow::Result<String> {
+        self.agent
+            .get(&format!("{BASE_URL}/day/{day}/input"))
+            .set("Cookie", &self.session_cookie())
+            .call()
+            .with_context(|| format!("failed to download input for day {day}"))?
+            .into_string()
+            .context("failed to read input response")
+    }
+
+    ///
+    /// Download a day's input into `day<N>/actual.txt` under the input directory, returning the
+    /// path it was written to
+    pub fn download_input(&self, day: u32) -> anyhow::Result<PathBuf> {
+        let input = self.fetch_input(day)?;
+        let dir = input_dir().join(format!("day{day}"));
+        fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        let path = dir.join("actual.txt");
+        fs::write(&path, input).with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn submit_answer(
+        &self,
+        day: u32,
+        part: Part,
+        answer: &str,
+    ) -> anyhow::Result<SubmissionResult> {
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&format!("{BASE_URL}/day/{day}/answer"))
+            .set("Cookie", &self.session_cookie())
+            .send_form(&[("level", &level), ("answer", answer)])
+            .with_context(|| format!("failed to submit answer for day {day} part {part}"))?
+            .into_string()
+            .context("failed to read submission response")?;
+
+        Ok(parse_submission_response(&response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrap_in_article(message: &str) -> String {
+        format!("<html><main><article><p>{message}</p></article></main></html>")
+    }
+
+    #[test]
+    fn test_parse_submission_response() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                SubmissionResult::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                SubmissionResult::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmissionResult::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input",
+                SubmissionResult::Incorrect,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 20s left to wait.",
+                SubmissionResult::Wait(Some("4m 20s left to wait".to_string())),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                SubmissionResult::WrongLevel,
+            ),
+        ];
+
+        for (message, expected) in cases {
+            assert_eq!(
+                parse_submission_response(&wrap_in_article(message)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_unknown_submission_response() {
+        assert_eq!(
+            parse_submission_response("<article>something else</article>"),
+            SubmissionResult::Unknown("something else".to_string())
+        );
+    }
+}
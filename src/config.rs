@@ -0,0 +1,127 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::report::ReportFormat;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+///
+/// Settings from `aoc.toml`, every one of them optional, e.g.
+/// ```toml
+/// input_dir = "input"
+/// session_file = ".session"
+/// format = "html"
+/// threads = 4
+/// ```
+/// Relative paths are relative to the directory of the config file, not the working directory.
+///
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The directory with a directory of inputs per day, `AOC_INPUT_DIR` overrides it
+    pub input_dir: Option<PathBuf>,
+    /// A file holding the adventofcode.com session token
+    pub session_file: Option<PathBuf>,
+    /// The default format of reports
+    pub format: Option<ReportFormat>,
+    /// How many threads to run the days on, every core by default. `--threads` and `AOC_THREADS`
+    /// override it
+    pub threads: Option<usize>,
+}
+
+impl Config {
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        toml::from_str(s).context("failed to parse config")
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let config = Self::parse(&contents)
+            .with_context(|| format!("invalid config file {}", path.display()))?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        Ok(config.relative_to(base))
+    }
+
+    ///
+    /// Load the first `aoc.toml` in the working directory or any of its parents, the defaults
+    /// if there is none
+    pub fn discover() -> anyhow::Result<Self> {
+        let cwd = env::current_dir().context("failed to get the working directory")?;
+        match find_config_file(&cwd) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn relative_to(self, base: &Path) -> Self {
+        Self {
+            input_dir: self.input_dir.map(|x| base.join(x)),
+            session_file: self.session_file.map(|x| base.join(x)),
+            ..self
+        }
+    }
+}
+
+fn find_config_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+input_dir = "inputs"
+format = "html"
+threads = 4
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                input_dir: Some(PathBuf::from("inputs")),
+                session_file: None,
+                format: Some(ReportFormat::Html),
+                threads: Some(4),
+            }
+        );
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert!(Config::parse("inputs = \"x\"").is_err());
+    }
+
+    #[test]
+    fn test_load_relative_to_config() {
+        let dir = env::temp_dir().join(format!("aoc-config-test-{}", std::process::id()));
+        let nested = dir.join("src").join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            dir.join(CONFIG_FILE),
+            "input_dir = \"input\"\nsession_file = \"/abs/session\"\n",
+        )
+        .unwrap();
+
+        let path = find_config_file(&nested);
+        let config = path.as_deref().map(Config::load);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(path, Some(dir.join(CONFIG_FILE)));
+        let config = config.unwrap().unwrap();
+        assert_eq!(config.input_dir, Some(dir.join("input")));
+        assert_eq!(config.session_file, Some(PathBuf::from("/abs/session")));
+    }
+}
@@ -1,13 +1,166 @@
-use std::{collections::HashMap, iter::FusedIterator, path::Path, str::Chars};
+use std::{collections::HashMap, io::BufRead, iter::FusedIterator, str::FromStr};
 
-use crate::utils::read_lines;
+use anyhow::Context;
 
-// part 1 and 2 can be the same because test doesn't contain named digits
-pub fn day1<P: AsRef<Path>>(filename: P) -> u32 {
-    read_lines(filename)
-        .into_iter()
-        .map(|x| get_first_and_last_digit(&x).unwrap().as_two_digit_num() as u32)
-        .sum()
+use crate::{
+    solution::{Answer, Solution},
+    utils::{parse_from_lines, parse_reader_streaming, FromLines},
+};
+
+///
+/// The calibration values of every line, from its digits alone and from its digits and spelled
+/// out digits. The lines themselves aren't kept, so a huge document can be streamed in.
+///
+#[derive(Debug, Default)]
+pub struct CalibrationDocument {
+    /// `None` for the lines with only spelled out digits
+    digit_values: Vec<Option<u8>>,
+    values: Vec<u8>,
+}
+
+impl CalibrationDocument {
+    ///
+    /// The sum of the values from the digits alone, failing on the first line without any
+    fn digits_sum(&self) -> anyhow::Result<u32> {
+        self.digit_values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                value
+                    .map(u32::from)
+                    .with_context(|| format!("no digits in line {}", index + 1))
+            })
+            .sum()
+    }
+
+    fn sum(&self) -> u32 {
+        self.values.iter().map(|&value| value as u32).sum()
+    }
+}
+
+impl FromLines for CalibrationDocument {
+    type Builder = Self;
+
+    fn push_line(builder: &mut Self::Builder, line: &str) -> anyhow::Result<()> {
+        let digits = get_first_and_last_digit(line).context("no digits in line")?;
+        builder.values.push(digits.as_two_digit_num());
+        builder
+            .digit_values
+            .push(get_first_and_last_ascii_digit(line).map(|digits| digits.as_two_digit_num()));
+        Ok(())
+    }
+
+    fn finish(builder: Self::Builder) -> anyhow::Result<Self> {
+        Ok(builder)
+    }
+}
+
+///
+/// The calibration value of every line from its digits and spelled out digits, by line number
+/// starting from 1, to find the lines where another solution disagrees
+pub fn calibration_values(
+    document: &CalibrationDocument,
+) -> impl Iterator<Item = (usize, u8)> + '_ {
+    document
+        .values
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| (index + 1, value))
+}
+
+impl FromStr for CalibrationDocument {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_from_lines(s.lines())
+    }
+}
+
+///
+/// Both parts' sums of the calibration values of a document, added up as it's read, so nothing is
+/// kept per line no matter how long the document is
+///
+#[derive(Debug, Default)]
+pub struct CalibrationSums {
+    digits: u64,
+    /// The first line with only spelled out digits, which has no value from its digits alone
+    missing_digits: Option<usize>,
+    values: u64,
+    lines: usize,
+}
+
+impl CalibrationSums {
+    ///
+    /// The sum of the values from the digits alone, or an error for the first line without any
+    pub fn digits_sum(&self) -> anyhow::Result<u64> {
+        match self.missing_digits {
+            Some(line) => anyhow::bail!("no digits in line {line}"),
+            None => Ok(self.digits),
+        }
+    }
+
+    pub fn sum(&self) -> u64 {
+        self.values
+    }
+}
+
+impl FromLines for CalibrationSums {
+    type Builder = Self;
+
+    fn push_line(builder: &mut Self::Builder, line: &str) -> anyhow::Result<()> {
+        let digits = get_first_and_last_digit(line).context("no digits in line")?;
+        builder.values += u64::from(digits.as_two_digit_num());
+        builder.lines += 1;
+        match get_first_and_last_ascii_digit(line) {
+            Some(digits) => builder.digits += u64::from(digits.as_two_digit_num()),
+            None => {
+                builder.missing_digits.get_or_insert(builder.lines);
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(builder: Self::Builder) -> anyhow::Result<Self> {
+        Ok(builder)
+    }
+}
+
+///
+/// Sum up the calibration values of a document a line at a time, in constant memory
+pub fn read_calibration_sums<R: BufRead>(reader: R) -> anyhow::Result<CalibrationSums> {
+    parse_reader_streaming(reader)
+}
+
+///
+/// The sum of the calibration values from the digits of each line, ignoring spelled out digits
+pub fn part1(document: &CalibrationDocument) -> anyhow::Result<u32> {
+    document.digits_sum()
+}
+
+///
+/// The sum of the calibration values from the digits and the spelled out digits of each line
+pub fn part2(document: &CalibrationDocument) -> u32 {
+    document.sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = CalibrationDocument;
+
+    const TITLE: &'static str = "Trebuchet?!";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 struct FirstAndLastDigit {
@@ -22,146 +175,452 @@ impl FirstAndLastDigit {
 }
 
 ///
-/// Find the first and last digit from a given string. Also this assumes the digits are ascii.
+/// Find the first and last digit from a given string, spelled out or not
 ///
 fn get_first_and_last_digit(haystack: &str) -> Option<FirstAndLastDigit> {
-    let mut it = DigitOrNamedDigit::new(haystack).into_iter().fuse(); // fuse because we call next twice without checking if the first one returned None
-
-    let first = it.next();
-    let last = it.rev().next();
-
-    match (first, last) {
-        (Some(first), Some(last)) => Some(FirstAndLastDigit {
-            first: first as u8,
-            last: last as u8,
-        }),
-        (Some(first), None) => Some(FirstAndLastDigit {
-            first: first as u8,
-            last: first as u8,
-        }),
-        _ => None,
-    }
-}
-
-struct DigitOrNamedDigit<'a> {
-    buffer: &'a str,
-    index: usize,
-    back_index: usize,
-    named_to_digit: HashMap<&'static str, u8>,
-}
-
-impl<'a> DigitOrNamedDigit<'a> {
-    pub fn new(haystack: &'a str) -> Self {
-        let named_to_digit = HashMap::from_iter([
-            ("one", 1),
-            ("two", 2),
-            ("three", 3),
-            ("four", 4),
-            ("five", 5),
-            ("six", 6),
-            ("seven", 7),
-            ("eight", 8),
-            ("nine", 9),
-        ]);
-
-        let back_index = if haystack.len() == 0 {
-            0
-        } else {
-            haystack.len()
-        };
-        Self {
-            buffer: haystack,
-            index: 0,
-            back_index,
-            named_to_digit,
+    let mut digits = DigitMatches::new(haystack).map(|(_, digit)| digit);
+    let first = digits.next()?;
+    let last = digits.next_back().unwrap_or(first);
+    Some(FirstAndLastDigit { first, last })
+}
+
+///
+/// Find the first and last ascii digit, ignoring spelled out digits
+///
+fn get_first_and_last_ascii_digit(haystack: &str) -> Option<FirstAndLastDigit> {
+    let mut digits = haystack
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|c| c - b'0');
+    let first = digits.next()?;
+    let last = digits.next_back().unwrap_or(first);
+    Some(FirstAndLastDigit { first, last })
+}
+
+///
+/// Like [`get_first_and_last_digit`], but checking every spelled out digit at every position, to
+/// benchmark [`DigitTrie`] against
+///
+fn get_first_and_last_digit_scanning(haystack: &str) -> Option<FirstAndLastDigit> {
+    let named_to_digit: HashMap<&str, u8> = NAMED_DIGITS.into_iter().zip(1..).collect();
+    let digit_at = |index: usize| {
+        let byte = haystack.as_bytes()[index];
+        if byte.is_ascii_digit() {
+            return Some(byte - b'0');
         }
-    }
+        named_to_digit.iter().find_map(|(named, &digit)| {
+            (haystack.get(index..index + named.len()) == Some(named)).then_some(digit)
+        })
+    };
+
+    let first = (0..haystack.len()).find_map(digit_at)?;
+    let last = (0..haystack.len())
+        .rev()
+        .find_map(digit_at)
+        .unwrap_or(first);
+    Some(FirstAndLastDigit { first, last })
 }
 
-impl<'a> Iterator for DigitOrNamedDigit<'a> {
-    type Item = u8;
+fn sum_lines(input: &str, find: fn(&str) -> Option<FirstAndLastDigit>) -> anyhow::Result<u32> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let digits = find(line).with_context(|| format!("no digits in line {}", index + 1))?;
+            Ok(u32::from(digits.as_two_digit_num()))
+        })
+        .sum()
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.index > self.buffer.len() || self.index > self.back_index {
-                return None;
-            }
+///
+/// The sum of the calibration values from the digits and the spelled out digits of every line,
+/// straight from the input, to benchmark matching the digits
+pub fn sum_calibration_values(input: &str) -> anyhow::Result<u32> {
+    sum_lines(input, get_first_and_last_digit)
+}
 
-            let mut chars = self.buffer.chars();
+///
+/// [`sum_calibration_values`] by slicing every line at every position instead of the tries
+pub fn sum_calibration_values_scanning(input: &str) -> anyhow::Result<u32> {
+    sum_lines(input, get_first_and_last_digit_scanning)
+}
 
-            const RADIX: u32 = 10;
+const NAMED_DIGITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
 
-            if let Some(c) = chars.nth(self.index) {
-                if let Some(digit) = c.to_digit(RADIX) {
-                    self.index += 1;
-                    return Some(digit as u8);
-                }
-            }
+/// At most a node per letter of the spelled out digits, and the root
+const TRIE_NODES: usize = {
+    let mut nodes = 1;
+    let mut word = 0;
+    while word < NAMED_DIGITS.len() {
+        nodes += NAMED_DIGITS[word].len();
+        word += 1;
+    }
+    nodes
+};
+
+///
+/// A trie over the spelled out digits, spelled forwards or backwards. It's built at compile time,
+/// so matching a digit only walks a couple of table entries.
+///
+struct DigitTrie {
+    /// The node each node leads to on every lowercase letter, 0 for none since the root isn't
+    /// anyone's child
+    next: [[u8; 26]; TRIE_NODES],
+    /// The digit spelled out on the way to each node, 0 for none
+    digits: [u8; TRIE_NODES],
+}
 
-            for (named_digit, digit) in self.named_to_digit.iter() {
-                if let Some(in_buffer) = self.buffer.get(self.index..self.index + named_digit.len())
-                {
-                    if &in_buffer == named_digit {
-                        self.index += named_digit.len();
-                        return Some(*digit);
-                    }
+impl DigitTrie {
+    const fn new(backwards: bool) -> Self {
+        let mut trie = Self {
+            next: [[0; 26]; TRIE_NODES],
+            digits: [0; TRIE_NODES],
+        };
+        let mut nodes = 1;
+        let mut word = 0;
+        while word < NAMED_DIGITS.len() {
+            let letters = NAMED_DIGITS[word].as_bytes();
+            let mut node = 0;
+            let mut index = 0;
+            while index < letters.len() {
+                let letter = if backwards {
+                    letters[letters.len() - 1 - index]
+                } else {
+                    letters[index]
+                };
+                let letter = (letter - b'a') as usize;
+                if trie.next[node][letter] == 0 {
+                    trie.next[node][letter] = nodes as u8;
+                    nodes += 1;
                 }
+                node = trie.next[node][letter] as usize;
+                index += 1;
             }
-
-            self.index += 1;
+            trie.digits[node] = word as u8 + 1;
+            word += 1;
         }
+        trie
     }
-}
 
-impl<'a> DoubleEndedIterator for DigitOrNamedDigit<'a> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.back_index < self.index {
+    ///
+    /// The digit spelled out by the first letters, and how many letters it takes. No spelled out
+    /// digit starts another one, so the first one reached is the only one.
+    fn find(&self, letters: impl Iterator<Item = u8>) -> Option<(u8, usize)> {
+        let mut node = 0;
+        for (index, letter) in letters.enumerate() {
+            if !letter.is_ascii_lowercase() {
                 return None;
             }
+            node = self.next[node][(letter - b'a') as usize] as usize;
+            match self.digits[node] {
+                _ if node == 0 => return None,
+                0 => {}
+                digit => return Some((digit, index + 1)),
+            }
+        }
+        None
+    }
+}
 
-            const RADIX: u32 = 10;
+static FORWARDS: DigitTrie = DigitTrie::new(false);
 
-            let mut chars = self.buffer.chars();
-            if let Some(Some(digit)) = chars.nth(self.back_index).map(|c| c.to_digit(RADIX)) {
-                self.back_index = 0.min(self.back_index - 1);
-                return Some(digit as u8);
-            }
+static BACKWARDS: DigitTrie = DigitTrie::new(true);
 
-            for (named_digit, digit) in self.named_to_digit.iter() {
-                if self.back_index < named_digit.len() {
-                    continue;
-                }
-                if let Some(in_buffer) = self
-                    .buffer
-                    .get(self.back_index - named_digit.len()..self.back_index)
-                {
-                    if &in_buffer == named_digit {
-                        self.back_index -= named_digit.len();
-                        return Some(*digit);
-                    }
+///
+/// Return the byte offsets where the first and last recognized digits start.
+/// Overlapping named digits are all considered, so in "eightwo" the last digit starts at "two".
+///
+pub fn first_and_last_positions(haystack: &str) -> Option<(usize, usize)> {
+    let mut positions = DigitMatches::new(haystack).map(|(start, _)| start);
+    let first = positions.next()?;
+    let last = positions.next_back().unwrap_or(first);
+    Some((first, last))
+}
+
+///
+/// The digits and spelled out digits of a line along with where they start, from either end.
+/// Spelled out digits may overlap like in "oneight", but none contains another, so they're in the
+/// same order by where they start as by where they end.
+///
+struct DigitMatches<'a> {
+    haystack: &'a [u8],
+    /// Where the next digit from the front can start at the earliest
+    front: usize,
+    /// Where the next digit from the back can end at the latest
+    back: usize,
+}
+
+impl<'a> DigitMatches<'a> {
+    fn new(haystack: &'a str) -> Self {
+        Self {
+            haystack: haystack.as_bytes(),
+            front: 0,
+            back: haystack.len(),
+        }
+    }
+
+    ///
+    /// The digit starting at the index, and how many bytes it takes
+    fn starting_at(&self, start: usize) -> Option<(u8, usize)> {
+        match self.haystack[start] {
+            digit @ b'0'..=b'9' => Some((digit - b'0', 1)),
+            _ => FORWARDS.find(self.haystack[start..].iter().copied()),
+        }
+    }
+
+    ///
+    /// The digit ending right before the index, and how many bytes it takes
+    fn ending_at(&self, end: usize) -> Option<(u8, usize)> {
+        match self.haystack[end - 1] {
+            digit @ b'0'..=b'9' => Some((digit - b'0', 1)),
+            _ => BACKWARDS.find(self.haystack[..end].iter().rev().copied()),
+        }
+    }
+}
+
+impl Iterator for DigitMatches<'_> {
+    type Item = (usize, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            let start = self.front;
+            // only the start is used up, the next digit may start within this one like in "twone"
+            self.front += 1;
+            if let Some((digit, length)) = self.starting_at(start) {
+                // one ending past the back was already returned from there
+                if start + length <= self.back {
+                    return Some((start, digit));
                 }
             }
+        }
+        None
+    }
+}
 
-            if self.back_index == 0 {
-                return None;
+impl DoubleEndedIterator for DigitMatches<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.back > self.front {
+            let end = self.back;
+            // likewise only the end is used up
+            self.back -= 1;
+            if let Some((digit, length)) = self.ending_at(end) {
+                if end - length >= self.front {
+                    return Some((end - length, digit));
+                }
             }
-            self.back_index -= 1;
         }
+        None
     }
 }
 
 ///
-/// We continue returning None even after the first None is returned
-impl<'a> FusedIterator for DigitOrNamedDigit<'a> {}
+/// Once the front and the back meet there's nothing left from either end
+impl FusedIterator for DigitMatches<'_> {}
 
 #[cfg(test)]
 mod tests {
-    use super::day1;
+    use std::io;
+
+    use proptest::prelude::*;
+
+    use crate::{
+        testing::calibration_line,
+        utils::{get_day_test_input, try_parse_input},
+    };
+
+    use super::*;
+
+    const EXAMPLE_PART1: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    const EXAMPLE_PART2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
 
     #[test]
     fn test_day() {
-        let result = day1("input/day1/test.txt");
-        assert_eq!(result, 142);
+        let document = try_parse_input(get_day_test_input("day1")).unwrap();
+        assert_eq!(part1(&document).unwrap(), 142);
+    }
+
+    #[test]
+    fn test_part1() {
+        let document: CalibrationDocument = EXAMPLE_PART1.parse().unwrap();
+        assert_eq!(part1(&document).unwrap(), 142);
+
+        // spelled out digits don't count
+        let document: CalibrationDocument = "two1nine\nabc3three".parse().unwrap();
+        assert_eq!(part1(&document).unwrap(), 11 + 33);
+        let document: CalibrationDocument = EXAMPLE_PART2.parse().unwrap();
+        let error = part1(&document).unwrap_err();
+        assert!(format!("{error:#}").contains("line 2"), "{error:#}");
+    }
+
+    #[test]
+    fn test_part2() {
+        let document: CalibrationDocument = EXAMPLE_PART2.parse().unwrap();
+        assert_eq!(part2(&document), 281);
+        let document: CalibrationDocument = EXAMPLE_PART1.parse().unwrap();
+        assert_eq!(part2(&document), 142);
+    }
+
+    #[test]
+    fn test_calibration_values() {
+        let document: CalibrationDocument = EXAMPLE_PART2.parse().unwrap();
+        let values: Vec<(usize, u8)> = calibration_values(&document).collect();
+        assert_eq!(
+            values,
+            vec![
+                (1, 29),
+                (2, 83),
+                (3, 13),
+                (4, 24),
+                (5, 42),
+                (6, 14),
+                (7, 76)
+            ]
+        );
+        let sum: u32 = values.iter().map(|&(_, value)| u32::from(value)).sum();
+        assert_eq!(sum, part2(&document));
+    }
+
+    #[test]
+    fn test_read_calibration_sums() {
+        let sums = read_calibration_sums(EXAMPLE_PART1.as_bytes()).unwrap();
+        assert_eq!(sums.digits_sum().unwrap(), 142);
+        assert_eq!(sums.sum(), 142);
+
+        let repeats = 10_000;
+        let input = EXAMPLE_PART2.repeat(repeats);
+        let sums = read_calibration_sums(io::Cursor::new(input)).unwrap();
+        assert_eq!(sums.sum(), 281 * repeats as u64);
+        let error = sums.digits_sum().unwrap_err();
+        assert!(format!("{error:#}").contains("line 2"), "{error:#}");
+
+        let error = read_calibration_sums("1abc2\r\npqrstuvwx\r\n".as_bytes()).unwrap_err();
+        assert!(format!("{error:#}").contains("line 2"), "{error:#}");
+    }
+
+    #[test]
+    fn test_calibration_document() {
+        let error = "1abc2\npqrstuvwx"
+            .parse::<CalibrationDocument>()
+            .unwrap_err();
+        assert!(format!("{error:#}").contains("line 2"), "{error:#}");
+    }
+
+    #[test]
+    fn test_first_and_last_positions() {
+        assert_eq!(first_and_last_positions("eightwothree"), Some((0, 7)));
+        assert_eq!(first_and_last_positions("treb7uchet"), Some((4, 4)));
+        assert_eq!(first_and_last_positions("eightwo"), Some((0, 4)));
+        assert_eq!(first_and_last_positions("abc"), None);
+    }
+
+    #[test]
+    fn test_digit_matches() {
+        let digits: Vec<(usize, u8)> = DigitMatches::new("xtwone3four").collect();
+        assert_eq!(digits, vec![(1, 2), (3, 1), (6, 3), (7, 4)]);
+        let digits: Vec<(usize, u8)> = DigitMatches::new("xtwone3four").rev().collect();
+        assert_eq!(digits, vec![(7, 4), (6, 3), (3, 1), (1, 2)]);
+
+        let mut digits = DigitMatches::new("oneight");
+        assert_eq!(digits.next_back(), Some((2, 8)));
+        assert_eq!(digits.next(), Some((0, 1)));
+        assert_eq!(digits.next(), None);
+        assert_eq!(digits.next_back(), None);
+
+        assert_eq!(DigitMatches::new("fiv").next(), None);
+        assert_eq!(DigitMatches::new("fiveé").next_back(), Some((0, 5)));
+    }
+
+    #[test]
+    fn test_overlapping_digits() {
+        for (line, first, last) in [
+            ("oneight", 1, 8),
+            ("twone", 2, 1),
+            ("eightwo", 8, 2),
+            ("sevenine", 7, 9),
+            ("eighthree", 8, 3),
+            ("nineight", 9, 8),
+            ("1twone", 1, 1),
+        ] {
+            let digits = get_first_and_last_digit(line).unwrap();
+            assert_eq!((digits.first, digits.last), (first, last), "{line}");
+        }
+
+        let digits: Vec<u8> = DigitMatches::new("oneightwone")
+            .map(|(_, digit)| digit)
+            .collect();
+        assert_eq!(digits, vec![1, 8, 2, 1]);
+        let digits: Vec<u8> = DigitMatches::new("oneightwone")
+            .rev()
+            .map(|(_, digit)| digit)
+            .collect();
+        assert_eq!(digits, vec![1, 2, 8, 1]);
+
+        let document: CalibrationDocument = "oneight\ntwone".parse().unwrap();
+        assert_eq!(part2(&document), 18 + 21);
+    }
+
+    #[test]
+    fn test_sum_calibration_values() {
+        for input in [EXAMPLE_PART1, EXAMPLE_PART2, "oneight\ntwone\nsevenine"] {
+            assert_eq!(
+                sum_calibration_values(input).unwrap(),
+                sum_calibration_values_scanning(input).unwrap()
+            );
+        }
+        assert_eq!(sum_calibration_values(EXAMPLE_PART2).unwrap(), 281);
+        assert!(sum_calibration_values("1abc2\npqrstuvwx").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_digit_matches_agree_from_both_ends(
+            line in calibration_line(),
+            from_back in proptest::collection::vec(any::<bool>(), 0..20),
+        ) {
+            // every position where a digit or a spelled out digit starts
+            let expected: Vec<(usize, u8)> = (0..line.len())
+                .filter_map(|start| {
+                    let rest = &line[start..];
+                    let digit = rest.bytes().next().filter(u8::is_ascii_digit).map(|c| c - b'0');
+                    let named = NAMED_DIGITS.iter().zip(1..).find(|(named, _)| rest.starts_with(*named));
+                    Some((start, digit.or(named.map(|(_, digit)| digit))?))
+                })
+                .collect();
+
+            let forwards: Vec<(usize, u8)> = DigitMatches::new(&line).collect();
+            prop_assert_eq!(&forwards, &expected);
+            let mut backwards: Vec<(usize, u8)> = DigitMatches::new(&line).rev().collect();
+            backwards.reverse();
+            prop_assert_eq!(&backwards, &expected);
+
+            // taking from both ends in any order still gives every digit exactly once
+            let mut digits = DigitMatches::new(&line);
+            let (mut front, mut back) = (Vec::new(), Vec::new());
+            for from_back in from_back.into_iter().chain(std::iter::repeat(false)) {
+                let next = if from_back { digits.next_back() } else { digits.next() };
+                match next {
+                    Some(digit) if from_back => back.push(digit),
+                    Some(digit) => front.push(digit),
+                    None => break,
+                }
+            }
+            prop_assert_eq!(digits.next_back(), None);
+            front.extend(back.into_iter().rev());
+            prop_assert_eq!(front, expected);
+        }
     }
 }
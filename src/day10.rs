@@ -2,8 +2,18 @@ use std::{collections::HashSet, str::FromStr};
 
 use anyhow::Context;
 
+use crate::{
+    geometry::shoelace_area,
+    solution::{Answer, Solution},
+    utils::{
+        geom::{Direction, Point},
+        grid,
+    },
+    viz::{draw_points, Render},
+};
+
 #[derive(Debug, PartialEq, Eq)]
-pub enum Tile {
+pub(crate) enum Tile {
     Vertical,
     Horizontal,
     NorthEast,
@@ -14,69 +24,29 @@ pub enum Tile {
     Start,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-pub struct GrindIndex {
-    x: usize,
-    y: usize,
-}
-
-impl GrindIndex {
-    pub fn north(&self) -> Option<Self> {
-        if self.y == 0 {
-            return None;
-        }
-
-        Some(Self {
-            x: self.x,
-            y: self.y - 1,
-        })
-    }
-
-    pub fn south(&self) -> Option<Self> {
-        Some(Self {
-            x: self.x,
-            y: self.y + 1,
-        })
-    }
-
-    pub fn east(&self) -> Option<Self> {
-        Some(Self {
-            x: self.x + 1,
-            y: self.y,
-        })
+impl Tile {
+    fn is_corner(&self) -> bool {
+        !matches!(self, Tile::Vertical | Tile::Horizontal)
     }
 
-    pub fn west(&self) -> Option<Self> {
-        if self.x == 0 {
-            return None;
+    fn get_directions(&self) -> &'static [Direction] {
+        match self {
+            Tile::Vertical => &[Direction::North, Direction::South],
+            Tile::Horizontal => &[Direction::East, Direction::West],
+            Tile::NorthEast => &[Direction::North, Direction::East],
+            Tile::NorthWest => &[Direction::North, Direction::West],
+            Tile::SouthWest => &[Direction::South, Direction::West],
+            Tile::SouthEast => &[Direction::South, Direction::East],
+            Tile::Ground => &[],
+            Tile::Start => &Direction::ALL,
         }
-
-        Some(Self {
-            x: self.x - 1,
-            y: self.y,
-        })
     }
-}
 
-impl Tile {
-    pub fn get_possible_next(&self, current: &GrindIndex) -> Vec<GrindIndex> {
-        let options = match self {
-            Tile::Vertical => vec![current.north(), current.south()],
-            Tile::Horizontal => vec![current.east(), current.west()],
-            Tile::NorthEast => vec![current.north(), current.east()],
-            Tile::NorthWest => vec![current.north(), current.west()],
-            Tile::SouthWest => vec![current.south(), current.west()],
-            Tile::SouthEast => vec![current.south(), current.east()],
-            Tile::Ground => vec![],
-            Tile::Start => vec![
-                current.north(),
-                current.south(),
-                current.east(),
-                current.west(),
-            ],
-        };
-
-        options.into_iter().filter_map(|x| x).collect()
+    pub fn get_possible_next(&self, current: &Point) -> Vec<Point> {
+        self.get_directions()
+            .iter()
+            .filter_map(|&direction| current.step(direction))
+            .collect()
     }
 }
 
@@ -101,24 +71,19 @@ impl TryFrom<char> for Tile {
 
 #[derive(Debug)]
 pub struct Grid {
-    tiles: Vec<Vec<Tile>>,
+    tiles: grid::Grid<Tile>,
 }
 
 impl Grid {
-    fn get_start(&self) -> Option<GrindIndex> {
-        for (y, line) in self.tiles.iter().enumerate() {
-            for (x, tile) in line.iter().enumerate() {
-                match tile {
-                    Tile::Start => return Some(GrindIndex { x, y }),
-                    _ => continue,
-                }
-            }
-        }
-
-        None
+    fn get_start(&self) -> Option<Point> {
+        self.tiles
+            .position(|tile| *tile == Tile::Start)
+            .map(|(y, x)| Point { x, y })
     }
 
-    fn get_loop_length(&self, start: GrindIndex) -> Option<u32> {
+    ///
+    /// The number of tiles in the loop going through `start`, which is also its perimeter
+    pub fn loop_perimeter(&self, start: Point) -> Option<u32> {
         let mut stack = Vec::new();
         let mut discovered = HashSet::new();
         let mut first = true;
@@ -133,7 +98,7 @@ impl Grid {
                 first = false
             } else {
                 if tile == &Tile::Start {
-                    return Some(depth / 2);
+                    return Some(depth);
                 }
             }
 
@@ -156,13 +121,125 @@ impl Grid {
         None
     }
 
-    fn get_tile(&self, index: &GrindIndex) -> Option<&Tile> {
-        self.tiles.get(index.y).map(|line| line.get(index.x))?
+    /// Neighbours of a tile which also lead back into it
+    fn get_connected_neighbours(&self, index: &Point) -> Vec<Point> {
+        let Some(tile) = self.get_tile(index) else {
+            return Vec::new();
+        };
+
+        tile.get_possible_next(index)
+            .into_iter()
+            .filter(|next| {
+                self.get_tile(next)
+                    .is_some_and(|next_tile| next_tile.get_possible_next(next).contains(index))
+            })
+            .collect()
+    }
+
+    fn walk_loop(&self, start: Point, first: Point) -> Option<Vec<Point>> {
+        let mut loop_tiles = vec![start];
+        let mut previous = start;
+        let mut current = first;
+        while current != start {
+            loop_tiles.push(current);
+            let next = self
+                .get_connected_neighbours(&current)
+                .into_iter()
+                .find(|next| *next != previous)?;
+            previous = current;
+            current = next;
+        }
+
+        Some(loop_tiles)
+    }
+
+    ///
+    /// The tiles of the loop going through `start`, in the order they are connected
+    ///
+    fn get_loop(&self, start: Point) -> Option<Vec<Point>> {
+        // the start may have neighbours leading into it which aren't part of the loop
+        self.get_connected_neighbours(&start)
+            .into_iter()
+            .find_map(|first| self.walk_loop(start, first))
+    }
+
+    ///
+    /// Use the shoelace formula for the area of the loop and Pick's theorem to get
+    /// the number of tiles strictly inside it: `interior = area - perimeter / 2 + 1`
+    ///
+    fn get_num_enclosed_by_shoelace(&self, loop_tiles: &[Point]) -> u32 {
+        let corners: Vec<(i64, i64)> = loop_tiles
+            .iter()
+            .filter(|index| self.get_tile(index).is_some_and(Tile::is_corner))
+            .map(|index| (index.x as i64, index.y as i64))
+            .collect();
+
+        let area = shoelace_area(&corners);
+        let perimeter = loop_tiles.len() as i64;
+        (area - perimeter / 2 + 1) as u32
+    }
+
+    ///
+    /// Scan each row and count the loop tiles connected northwards to the left of every tile.
+    /// A tile not on the loop is enclosed if that count is odd.
+    ///
+    fn get_num_enclosed_by_ray_casting(&self, loop_tiles: &[Point]) -> u32 {
+        let on_loop: HashSet<Point> = loop_tiles.iter().copied().collect();
+        let mut connected_north = HashSet::new();
+        for (index, current) in loop_tiles.iter().enumerate() {
+            let previous = loop_tiles[(index + loop_tiles.len() - 1) % loop_tiles.len()];
+            let next = loop_tiles[(index + 1) % loop_tiles.len()];
+            let north = current.step(Direction::North);
+            if north == Some(previous) || north == Some(next) {
+                connected_north.insert(*current);
+            }
+        }
+
+        let mut enclosed = 0;
+        for y in 0..self.tiles.num_rows() {
+            let mut crossings = 0;
+            for x in 0..self.tiles.num_columns() {
+                let index = Point { x, y };
+                if on_loop.contains(&index) {
+                    if connected_north.contains(&index) {
+                        crossings += 1;
+                    }
+                } else if crossings % 2 == 1 {
+                    enclosed += 1;
+                }
+            }
+        }
+
+        enclosed
+    }
+
+    pub fn get_num_enclosed(&self) -> Option<u32> {
+        let start = self.get_start()?;
+        let loop_tiles = self.get_loop(start)?;
+        Some(self.get_num_enclosed_by_shoelace(&loop_tiles))
+    }
+
+    ///
+    /// Draw the grid with the loop tiles as `#` and everything else as `.`
+    ///
+    pub fn draw_loop(&self) -> Option<String> {
+        let start = self.get_start()?;
+        let on_loop: HashSet<Point> = self.get_loop(start)?.into_iter().collect();
+        Some(draw_points(
+            &on_loop,
+            self.tiles.num_columns(),
+            self.tiles.num_rows(),
+        ))
+    }
+
+    fn get_tile(&self, index: &Point) -> Option<&Tile> {
+        self.tiles.get(index.y, index.x)
     }
 
     pub fn get_num_furthest_from_start(&self) -> Option<u32> {
         let start = self.get_start()?;
-        self.get_loop_length(start)
+        // the furthest tile is halfway around the loop
+        self.loop_perimeter(start).map(|perimeter| perimeter / 2)
     }
 }
 
@@ -170,35 +247,139 @@ impl FromStr for Grid {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tiles: anyhow::Result<Vec<Vec<Tile>>> = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .into_iter()
-                    .map(|c| c.try_into().context("failed to parse char as tile"))
-                    .collect()
-            })
-            .collect();
+        let tiles =
+            grid::Grid::parse_chars(s, |c| c.try_into().context("failed to parse char as tile"))
+                .context("failed to parse grid")?;
 
-        Ok(Self {
-            tiles: tiles.context("failed to parse grid")?,
-        })
+        Ok(Self { tiles })
     }
 }
 
-pub fn part1(grid: &Grid) -> u32 {
-    grid.get_num_furthest_from_start().unwrap()
+impl Render for Grid {
+    fn render_ascii(&self) -> anyhow::Result<String> {
+        self.draw_loop().context("no loop found")
+    }
+}
+
+pub fn part1(grid: &Grid) -> anyhow::Result<u32> {
+    grid.get_num_furthest_from_start()
+        .context("no loop going through the start")
+}
+
+pub fn part2(grid: &Grid) -> anyhow::Result<u32> {
+    grid.get_num_enclosed()
+        .context("no loop going through the start")
+}
+
+///
+/// Part 2 by scanning every row, the baseline the shoelace formula is measured against
+pub fn part2_ray_casting(grid: &Grid) -> anyhow::Result<u32> {
+    let start = grid.get_start().context("no start tile")?;
+    let loop_tiles = grid
+        .get_loop(start)
+        .context("no loop going through the start")?;
+    Ok(grid.get_num_enclosed_by_ray_casting(&loop_tiles))
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Grid;
+
+    const TITLE: &'static str = "Pipe Maze";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::{get_day_test_input, parse_input};
+    use crate::utils::{get_day_test_input, try_parse_input};
 
     use super::*;
 
     #[test]
     fn test_part1() {
-        let grid = parse_input(get_day_test_input("day10"));
-        assert_eq!(part1(&grid), 8);
+        let grid = try_parse_input(get_day_test_input("day10")).unwrap();
+        assert_eq!(part1(&grid).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_loop_perimeter() {
+        let grid: Grid = "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ..."
+            .parse()
+            .unwrap();
+        let start = grid.get_start().unwrap();
+        assert_eq!(grid.loop_perimeter(start), Some(16));
+        assert_eq!(grid.loop_perimeter(start), Some(2 * part1(&grid).unwrap()));
+    }
+
+    fn assert_enclosed_methods_agree(grid: &Grid, expected: u32) {
+        let start = grid.get_start().unwrap();
+        let loop_tiles = grid.get_loop(start).unwrap();
+        assert_eq!(grid.get_num_enclosed_by_shoelace(&loop_tiles), expected);
+        assert_eq!(grid.get_num_enclosed_by_ray_casting(&loop_tiles), expected);
+        assert_eq!(part2(grid).unwrap(), expected);
+        assert_eq!(part2_ray_casting(grid).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_part2_rectangle() {
+        let grid: Grid = ".....
+.S-7.
+.|.|.
+.L-J.
+....."
+            .parse()
+            .unwrap();
+        assert_enclosed_methods_agree(&grid, 1);
+        assert_eq!(
+            grid.draw_loop().unwrap(),
+            ".....\n.###.\n.#.#.\n.###.\n.....\n"
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let grid: Grid = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+..........."
+            .parse()
+            .unwrap();
+        assert_enclosed_methods_agree(&grid, 4);
+        insta::assert_snapshot!("loop_map", grid.draw_loop().unwrap());
+
+        let grid: Grid = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ..."
+            .parse()
+            .unwrap();
+        assert_enclosed_methods_agree(&grid, 8);
     }
 }
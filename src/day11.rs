@@ -1,25 +1,15 @@
-use std::{iter::FusedIterator, str::FromStr};
+use std::{collections::HashSet, str::FromStr};
 
 use anyhow::Context;
 
-#[derive(Debug)]
-pub struct DriftedGridIndex {
-    x: usize,
-    y: usize,
-}
-
-impl DriftedGridIndex {
-    fn distance(&self, other: &DriftedGridIndex) -> u64 {
-        let self_x = self.x as i64;
-        let self_y = self.y as i64;
-        let other_x = other.x as i64;
-        let other_y = other.y as i64;
-        (self_x - other_x).unsigned_abs() + (self_y - other_y).unsigned_abs()
-    }
-}
+use crate::{
+    solution::{Answer, Solution},
+    utils::{geom::Point, grid::Grid},
+    viz::{draw_points, Render},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ImageData {
+pub(crate) enum ImageData {
     Galaxy,
     Empty,
 }
@@ -38,209 +28,218 @@ impl TryFrom<char> for ImageData {
 
 #[derive(Debug)]
 pub struct Image {
-    // The location after taking into account the empty rows and columns
-    drifted_galaxies: Vec<DriftedGridIndex>,
-    // empty_rows: Vec<usize>,
-}
-
-struct Grid {
-    inner: Vec<ImageData>,
+    // The (row, column) of each galaxy before taking into account the empty rows and columns
+    galaxies: Vec<(usize, usize)>,
+    empty_rows: Vec<usize>,
+    empty_columns: Vec<usize>,
     num_rows: usize,
     num_columns: usize,
 }
 
-impl FromStr for Grid {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid: anyhow::Result<Vec<ImageData>> = s
-            .lines()
-            .into_iter()
-            .flat_map(|line| {
-                line.chars()
-                    .into_iter()
-                    .map(|c| c.try_into().context("failed to parse image data"))
-            })
-            .collect();
-
-        let num_rows = s.lines().count();
-        let first_row = s.lines().next();
-        let num_columns = match first_row {
-            Some(row) => row.len(),
-            None => 0,
-        };
-
-        Ok(Self {
-            inner: grid.context("failed to parse grid")?,
-            num_rows,
-            num_columns,
-        })
-    }
-}
-
-impl Grid {
-    fn get(&self, row: usize, column: usize) -> Option<&ImageData> {
-        let index = row * self.num_rows + column;
-        self.inner.get(index)
-    }
-
-    fn iter_columns(&self) -> ColumnIterator<'_> {
-        ColumnIterator {
-            grid: self,
-            current_column: 0,
-        }
-    }
-
-    fn iter_rows(&self) -> RowIterator<'_> {
-        RowIterator {
-            grid: self,
-            current_row: 0,
-        }
-    }
-}
-
-struct ColumnIterator<'a> {
-    grid: &'a Grid,
-    current_column: usize,
-}
-
-impl<'a> Iterator for ColumnIterator<'a> {
-    type Item = Vec<ImageData>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_column > self.grid.num_columns {
-            return None;
-        }
-
-        let mut column = Vec::with_capacity(self.grid.num_rows);
-
-        for row in 0..self.grid.num_rows {
-            column.push(*self.grid.get(row, self.current_column)?);
-        }
-
-        self.current_column += 1;
-
-        Some(column)
-    }
-}
-
-struct RowIterator<'a> {
-    grid: &'a Grid,
-    current_row: usize,
-}
-
-impl<'a> Iterator for RowIterator<'a> {
-    type Item = Vec<ImageData>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_row > self.grid.num_rows {
-            return None;
-        }
-
-        let mut row = Vec::with_capacity(self.grid.num_rows);
-
-        for column in 0..self.grid.num_columns {
-            row.push(*self.grid.get(self.current_row, column)?);
-        }
-
-        self.current_row += 1;
-
-        Some(row)
-    }
-}
-
-const EMPTY_SIZE: usize = 1_000_000;
-
-impl<'a> FusedIterator for ColumnIterator<'a> {}
+const EMPTY_SIZE_PART1: usize = 2;
+const EMPTY_SIZE_PART2: usize = 1_000_000;
 
 impl FromStr for Image {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut num_empty_rows = 0;
+        let mut empty_rows = Vec::new();
         let mut empty_columns = Vec::new();
-        let mut drifted_galaxies = Vec::new();
+        let mut galaxies = Vec::new();
 
-        let grid: Grid = s.parse().context("failed to parse grid")?;
+        let grid: Grid<ImageData> =
+            Grid::parse_chars(s, |c| c.try_into().context("failed to parse image data"))
+                .context("failed to parse grid")?;
 
-        for (column_index, column) in grid.iter_columns().enumerate() {
-            if column.into_iter().all(|x| x == ImageData::Empty) {
+        for (column_index, mut column) in grid.columns().enumerate() {
+            if column.all(|&x| x == ImageData::Empty) {
                 empty_columns.push(column_index);
             }
         }
 
-        for (row_index, row) in grid.iter_rows().enumerate() {
+        for (row_index, row) in grid.rows().enumerate() {
             if row.iter().all(|&x| x == ImageData::Empty) {
-                num_empty_rows += 1;
+                empty_rows.push(row_index);
                 continue;
             }
 
-            for (column_index, data) in row.into_iter().enumerate() {
-                let column_after_drift = match data {
-                    ImageData::Galaxy => {
-                        let column = column_index
-                            + (empty_columns
-                                .iter()
-                                .filter(|&&empty_column_index| column_index > empty_column_index)
-                                .count()
-                                * EMPTY_SIZE);
-                        column
-                            - empty_columns
-                                .iter()
-                                .filter(|&&empty_column_index| column_index > empty_column_index)
-                                .count()
-                    }
-                    ImageData::Empty => continue,
-                };
-
-                drifted_galaxies.push(DriftedGridIndex {
-                    x: column_after_drift,
-                    y: row_index + (num_empty_rows * EMPTY_SIZE) - num_empty_rows,
-                })
+            for (column_index, &data) in row.iter().enumerate() {
+                if data == ImageData::Galaxy {
+                    galaxies.push((row_index, column_index));
+                }
             }
         }
 
-        Ok(Self { drifted_galaxies })
+        Ok(Self {
+            galaxies,
+            empty_rows,
+            empty_columns,
+            num_rows: grid.num_rows(),
+            num_columns: grid.num_columns(),
+        })
     }
 }
 
 impl Image {
-    fn get_shortest_path_between_all_pairs(&self) -> Vec<u64> {
-        let num_pairs = (self.drifted_galaxies.len() * (self.drifted_galaxies.len() + 1)) / 2;
+    ///
+    /// The location of each galaxy after every empty row and column grows to `empty_size`
+    ///
+    fn get_drifted_galaxies(&self, empty_size: usize) -> Vec<Point> {
+        self.galaxies
+            .iter()
+            .map(|&(row, column)| {
+                let empty_rows_before = self.empty_rows.iter().filter(|&&x| x < row).count();
+                let empty_columns_before =
+                    self.empty_columns.iter().filter(|&&x| x < column).count();
+
+                Point {
+                    x: column + empty_columns_before * (empty_size - 1),
+                    y: row + empty_rows_before * (empty_size - 1),
+                }
+            })
+            .collect()
+    }
+
+    fn get_shortest_path_between_all_pairs(&self, empty_size: usize) -> Vec<u64> {
+        let drifted_galaxies = self.get_drifted_galaxies(empty_size);
+        let num_pairs = (drifted_galaxies.len() * (drifted_galaxies.len() + 1)) / 2;
         let mut distances = Vec::with_capacity(num_pairs);
-        for (index, side_a) in self.drifted_galaxies.iter().enumerate() {
-            for side_b in self.drifted_galaxies.iter().skip(index + 1) {
-                distances.push(side_a.distance(side_b));
+        for (index, side_a) in drifted_galaxies.iter().enumerate() {
+            for side_b in drifted_galaxies.iter().skip(index + 1) {
+                distances.push(side_a.manhattan_distance(*side_b) as u64);
             }
         }
 
         distances
     }
+
+    /// How many galaxy pairs are farther apart than `threshold` when empty space grows by `factor`.
+    /// Empty space can't shrink to nothing, so `factor` has to be at least 1.
+    pub fn count_pairs_farther_than(&self, factor: u64, threshold: u64) -> anyhow::Result<usize> {
+        anyhow::ensure!(factor > 0, "empty space can't grow by a factor of 0");
+        let factor = usize::try_from(factor).context("expansion factor is too large")?;
+        Ok(self
+            .get_shortest_path_between_all_pairs(factor)
+            .into_iter()
+            .filter(|&distance| distance > threshold)
+            .count())
+    }
 }
 
-// TODO: find a way to split part1 and part2, because they are kind of the same just different EMPTY_SIZE
-pub fn part1_and_part2(image: &Image) -> u64 {
+impl Render for Image {
+    ///
+    /// The galaxies after the expansion of part 1
+    fn render_ascii(&self) -> anyhow::Result<String> {
+        let galaxies: HashSet<Point> = self
+            .get_drifted_galaxies(EMPTY_SIZE_PART1)
+            .into_iter()
+            .collect();
+        let growth = EMPTY_SIZE_PART1 - 1;
+        Ok(draw_points(
+            &galaxies,
+            self.num_columns + self.empty_columns.len() * growth,
+            self.num_rows + self.empty_rows.len() * growth,
+        ))
+    }
+}
+
+pub fn part1(image: &Image) -> u64 {
     image
-        .get_shortest_path_between_all_pairs()
+        .get_shortest_path_between_all_pairs(EMPTY_SIZE_PART1)
         .into_iter()
         .sum()
 }
 
+pub fn part2(image: &Image) -> u64 {
+    image
+        .get_shortest_path_between_all_pairs(EMPTY_SIZE_PART2)
+        .into_iter()
+        .sum()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Image;
+
+    const TITLE: &'static str = "Cosmic Expansion";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::{get_day_test_input, parse_input};
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    use crate::{
+        testing::char_grid,
+        utils::{get_day_test_input, try_parse_input},
+    };
 
     use super::*;
 
-    // #[test]
-    // fn test_part1() {
-    //     let image = parse_input(get_day_test_input("day11"));
-    //     assert_eq!(part1(&image), 374);
-    // }
-
-    // #[test]
-    // fn test_part2() {
-    //     let image = parse_input(get_day_test_input("day11"));
-    //     assert_eq!(part1_and_part2(&image), 8410);
-    // }
+    #[test]
+    fn test_part1() {
+        let image = try_parse_input(get_day_test_input("day11")).unwrap();
+        assert_eq!(part1(&image), 374);
+    }
+
+    #[test]
+    fn test_part2() {
+        let image: Image = try_parse_input(get_day_test_input("day11")).unwrap();
+        assert_eq!(part2(&image), 82000210);
+    }
+
+    #[test]
+    fn test_count_pairs_farther_than() {
+        let image: Image = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#....."
+            .parse()
+            .unwrap();
+
+        assert_eq!(image.count_pairs_farther_than(2, 0).unwrap(), 36);
+        assert_eq!(image.count_pairs_farther_than(2, 10).unwrap(), 16);
+        assert_eq!(image.count_pairs_farther_than(2, 15).unwrap(), 4);
+        assert_eq!(image.count_pairs_farther_than(10, 15).unwrap(), 27);
+        assert_eq!(image.count_pairs_farther_than(100, 100).unwrap(), 36);
+        assert!(image.count_pairs_farther_than(0, 0).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1_matches_expanded_drawing(input in char_grid(&['.', '#'], 12)) {
+            let image: Image = input.parse().unwrap();
+            let expanded: Grid<char> = Grid::parse_chars(&image.render_ascii().unwrap(), Ok).unwrap();
+            let galaxies: Vec<Point> = expanded
+                .iter()
+                .filter(|(_, &c)| c == '#')
+                .map(|((row, column), _)| Point::new(column, row))
+                .collect();
+            let brute_force: u64 = galaxies
+                .iter()
+                .tuple_combinations()
+                .map(|(a, b)| a.manhattan_distance(*b) as u64)
+                .sum();
+            prop_assert_eq!(part1(&image), brute_force);
+        }
+    }
 }
@@ -0,0 +1,203 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::parse_from_lines,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl TryFrom<char> for Spring {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Operational),
+            '#' => Ok(Self::Damaged),
+            '?' => Ok(Self::Unknown),
+            _ => anyhow::bail!("invalid spring condition: {value}"),
+        }
+    }
+}
+
+///
+/// A row of springs and the sizes of its contiguous groups of damaged springs, in order
+///
+#[derive(Debug)]
+pub struct ConditionRecord {
+    springs: Vec<Spring>,
+    groups: Vec<usize>,
+}
+
+impl FromStr for ConditionRecord {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (springs, groups) = s
+            .split_once(' ')
+            .with_context(|| format!("missing groups in record: {s}"))?;
+        let springs = springs
+            .chars()
+            .map(Spring::try_from)
+            .collect::<Result<_, _>>()?;
+        let groups = groups
+            .split(',')
+            .map(|x| {
+                x.parse()
+                    .with_context(|| format!("failed to parse group size: {x}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self { springs, groups })
+    }
+}
+
+///
+/// The springs and groups left to match are always suffixes of the record's, so their lengths
+/// are enough to identify them
+type Memo = HashMap<(usize, usize), u64>;
+
+///
+/// Count the ways the damaged groups can be laid out over the springs, either skipping the first
+/// spring as operational or starting the first group at it
+fn count_arrangements(springs: &[Spring], groups: &[usize], memo: &mut Memo) -> u64 {
+    let Some((&group, rest_groups)) = groups.split_first() else {
+        // no groups are left, so every remaining spring has to be operational
+        return u64::from(!springs.contains(&Spring::Damaged));
+    };
+    let Some(first) = springs.first() else {
+        return 0;
+    };
+    if let Some(&count) = memo.get(&(springs.len(), groups.len())) {
+        return count;
+    }
+
+    let mut count = 0;
+    if *first != Spring::Damaged {
+        count += count_arrangements(&springs[1..], groups, memo);
+    }
+
+    let fits = springs.len() >= group
+        && !springs[..group].contains(&Spring::Operational)
+        && springs.get(group) != Some(&Spring::Damaged);
+    if *first != Spring::Operational && fits {
+        // the spring after the group has to be operational to separate it from the next group
+        let rest_springs = springs.get(group + 1..).unwrap_or_default();
+        count += count_arrangements(rest_springs, rest_groups, memo);
+    }
+
+    memo.insert((springs.len(), groups.len()), count);
+    count
+}
+
+const UNFOLD_COPIES: usize = 5;
+
+impl ConditionRecord {
+    pub fn arrangements(&self) -> u64 {
+        count_arrangements(&self.springs, &self.groups, &mut Memo::new())
+    }
+
+    ///
+    /// The record repeated `copies` times, with an unknown spring between every copy of the
+    /// springs
+    pub fn unfold(&self, copies: usize) -> Self {
+        let mut springs = Vec::with_capacity((self.springs.len() + 1) * copies);
+        for copy in 0..copies {
+            if copy > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Self {
+            springs,
+            groups: self.groups.repeat(copies),
+        }
+    }
+}
+
+pub fn part1(records: &[ConditionRecord]) -> u64 {
+    records.iter().map(ConditionRecord::arrangements).sum()
+}
+
+pub fn part2(records: &[ConditionRecord]) -> u64 {
+    records
+        .iter()
+        .map(|record| record.unfold(UNFOLD_COPIES).arrangements())
+        .sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Vec<ConditionRecord>;
+
+    const TITLE: &'static str = "Hot Springs";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        parse_from_lines(input.lines())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    #[test]
+    fn test_part1() {
+        let records = Day12::parse(EXAMPLE).unwrap();
+        let arrangements: Vec<u64> = records.iter().map(ConditionRecord::arrangements).collect();
+        assert_eq!(arrangements, vec![1, 4, 1, 1, 4, 10]);
+        assert_eq!(part1(&records), 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        let records = Day12::parse(EXAMPLE).unwrap();
+        let arrangements: Vec<u64> = records
+            .iter()
+            .map(|record| record.unfold(UNFOLD_COPIES).arrangements())
+            .collect();
+        assert_eq!(arrangements, vec![1, 16384, 1, 16, 2500, 506250]);
+        assert_eq!(part2(&records), 525152);
+    }
+
+    #[test]
+    fn test_unfold() {
+        let record: ConditionRecord = ".# 1".parse().unwrap();
+        let unfolded = record.unfold(UNFOLD_COPIES);
+        let expected: ConditionRecord = ".#?.#?.#?.#?.# 1,1,1,1,1".parse().unwrap();
+        assert_eq!(unfolded.springs, expected.springs);
+        assert_eq!(unfolded.groups, expected.groups);
+    }
+
+    #[test]
+    fn test_invalid_records() {
+        assert!("???.###".parse::<ConditionRecord>().is_err());
+        assert!("??x 1".parse::<ConditionRecord>().is_err());
+        assert!("??? 1,a".parse::<ConditionRecord>().is_err());
+    }
+}
@@ -1,48 +1,20 @@
-use std::{iter::FusedIterator, str::FromStr};
+use std::str::FromStr;
 
-#[derive(Debug)]
-pub struct GridPattern {
-    inner: Vec<char>,
-    rows: usize,
-    columns: usize,
-}
+use anyhow::Context;
 
-trait EqualExceptOne: PartialEq {
-    fn equal_except_one(&self, other: &Self) -> bool;
-}
-
-impl<'a> EqualExceptOne for &'a [char] {
-    fn equal_except_one(&self, other: &Self) -> bool {
-        let mut found_unequal = false;
-        for (a, b) in self.iter().zip(other.iter()) {
-            if a != b {
-                if found_unequal {
-                    return false;
-                } else {
-                    found_unequal = true;
-                }
-            }
-        }
+#[cfg(feature = "arena")]
+use crate::utils::arena::ParseContext;
+use crate::{
+    solution::{Answer, Solution},
+    utils::{
+        grid::Grid,
+        simd::{compare_rows, RowComparison},
+    },
+};
 
-        found_unequal
-    }
-}
-
-impl<'a> EqualExceptOne for Vec<char> {
-    fn equal_except_one(&self, other: &Self) -> bool {
-        let mut found_unequal = false;
-        for (a, b) in self.iter().zip(other.iter()) {
-            if a != b {
-                if found_unequal {
-                    return false;
-                } else {
-                    found_unequal = true;
-                }
-            }
-        }
-
-        found_unequal
-    }
+#[derive(Debug)]
+pub(crate) struct GridPattern {
+    grid: Grid<u8>,
 }
 
 ///
@@ -72,232 +44,120 @@ where
     equal
 }
 
-fn is_reflected_with_smudge<T>(it: T, reflection_line: usize, total_lines: usize) -> bool
+fn is_reflected_with_smudge<'a, T>(it: T, reflection_line: usize, total_lines: usize) -> bool
 where
-    T: Iterator + DoubleEndedIterator + Clone,
-    T::Item: EqualExceptOne,
+    T: Iterator<Item = &'a [u8]> + DoubleEndedIterator + Clone,
 {
     let mut forward_it = it.clone().skip(reflection_line);
     let mut back_it = it.rev().skip(total_lines - reflection_line);
     let mut found_single_smudge = false;
     while let (Some(forward), Some(back)) = (forward_it.next(), back_it.next()) {
-        if forward.equal_except_one(&back) {
-            if found_single_smudge {
-                // second like that is almost equal
-                return false;
-            } else {
-                found_single_smudge = true;
+        match compare_rows(forward, back) {
+            RowComparison::Equal => {}
+            RowComparison::OneMismatch => {
+                if found_single_smudge {
+                    // second like that is almost equal
+                    return false;
+                } else {
+                    found_single_smudge = true;
+                }
             }
-        } else if forward != back {
-            return false;
+            RowComparison::Different => return false,
         }
     }
 
     found_single_smudge
 }
 
+///
+/// The first line between two of the rows which the rows are reflected around
+fn find_reflection_line<'a, T>(rows: T, with_smudge: bool) -> Option<usize>
+where
+    T: Iterator<Item = &'a [u8]> + DoubleEndedIterator + ExactSizeIterator + Clone,
+{
+    let num_rows = rows.len();
+    (1..num_rows).find(|&reflection_row| match with_smudge {
+        false => is_reflected(rows.clone(), reflection_row, num_rows),
+        true => is_reflected_with_smudge(rows.clone(), reflection_row, num_rows),
+    })
+}
+
 impl GridPattern {
-    pub fn from_str_lines(lines: &[&str]) -> Self {
-        let rows = lines.len();
-        let columns = match lines.get(0) {
-            Some(c) => c.len(),
-            None => {
-                return Self {
-                    inner: Vec::new(),
-                    rows: 0,
-                    columns: 0,
-                }
-            }
-        };
+    pub fn from_str_lines(lines: &[&str]) -> anyhow::Result<Self> {
+        let rows = lines.iter().map(|line| line.bytes().collect()).collect();
+        Ok(Self {
+            grid: Grid::from_rows(rows)?,
+        })
+    }
 
-        let inner = lines.iter().flat_map(|line| line.chars()).collect();
+    /// Swap the rows and columns of the pattern
+    pub fn transpose(&self) -> GridPattern {
         Self {
-            inner,
-            rows,
-            columns,
+            grid: self.grid.transpose(),
         }
     }
 
-    fn row_iter(&self) -> RowIterator<'_> {
-        RowIterator::new(self)
-    }
-
-    fn column_iter(&self) -> ColumnIterator<'_> {
-        ColumnIterator::new(self)
-    }
-
     fn find_horizontal_reflection_line(&self, with_smudge: bool) -> Option<usize> {
-        for reflection_row in 1..self.rows {
-            let is_reflected = match with_smudge {
-                false => is_reflected(self.row_iter(), reflection_row, self.rows),
-                true => is_reflected_with_smudge(self.row_iter(), reflection_row, self.rows),
-            };
-
-            if is_reflected {
-                return Some(reflection_row);
-            }
-        }
-
-        None
+        find_reflection_line(self.grid.rows(), with_smudge)
     }
 
     fn find_vertical_reflection_line(&self, with_smudge: bool) -> Option<usize> {
-        for reflection_column in 1..self.columns {
-            let is_reflected = match with_smudge {
-                false => is_reflected(self.column_iter(), reflection_column, self.columns),
-                true => {
-                    is_reflected_with_smudge(self.column_iter(), reflection_column, self.columns)
-                }
-            };
-
-            if is_reflected {
-                return Some(reflection_column);
-            }
-        }
+        // a vertical reflection is a horizontal reflection of the transposed pattern
+        self.transpose()
+            .find_horizontal_reflection_line(with_smudge)
+    }
 
-        None
+    ///
+    /// Like `find_vertical_reflection_line`, but the pattern is transposed into the arena instead
+    /// of a new grid
+    #[cfg(feature = "arena")]
+    fn find_vertical_reflection_line_in(
+        &self,
+        arena: &ParseContext,
+        with_smudge: bool,
+    ) -> Option<usize> {
+        let num_rows = self.grid.num_rows();
+        let columns = arena.alloc_slice_with(num_rows * self.grid.num_columns(), |index| {
+            self.grid.row(index % num_rows).expect("row is in the grid")[index / num_rows]
+        });
+        find_reflection_line(columns.chunks(num_rows.max(1)), with_smudge)
     }
 
-    fn find_reflection(&self, with_smudge: bool) -> Option<usize> {
+    fn find_reflection(&self, with_smudge: bool) -> Option<Reflection> {
         let horizontal = self.find_horizontal_reflection_line(with_smudge);
         if let Some(horizontal) = horizontal {
-            return Some((horizontal) * 100);
+            return Some(Reflection::Horizontal {
+                rows_above: horizontal,
+            });
         }
 
         let vertical = self.find_vertical_reflection_line(with_smudge);
         if let Some(vertical) = vertical {
-            return Some(vertical);
+            return Some(Reflection::Vertical {
+                columns_left: vertical,
+            });
         }
 
         None
     }
 }
 
-// I think iterators shouldn't be copy
-#[derive(Debug, Clone)]
-struct RowIterator<'a> {
-    grid: &'a GridPattern,
-    current_row_from_start: usize,
-    current_row_from_end: usize,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reflection {
+    Horizontal { rows_above: usize },
+    Vertical { columns_left: usize },
 }
 
-impl<'a> RowIterator<'a> {
-    fn new(grid_pattern: &'a GridPattern) -> Self {
-        Self {
-            grid: grid_pattern,
-            current_row_from_start: 0,
-            current_row_from_end: grid_pattern.rows,
-        }
-    }
-}
-
-impl<'a> Iterator for RowIterator<'a> {
-    type Item = &'a [char];
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // row end is initialized at max rows so no need to check if current is above max
-        if self.current_row_from_start >= self.current_row_from_end {
-            return None;
-        }
-
-        let start_index = self.current_row_from_start * self.grid.columns;
-        let end_index = start_index + self.grid.columns;
-        let to_yield = self
-            .grid
-            .inner
-            .get(start_index..end_index)
-            .expect("We checked the slice is valid");
-        self.current_row_from_start += 1;
-        Some(to_yield)
-    }
-}
-
-impl<'a> DoubleEndedIterator for RowIterator<'a> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.current_row_from_end <= self.current_row_from_start {
-            return None;
+impl Reflection {
+    /// What a reflection adds to the puzzle answer
+    pub fn summary(&self) -> usize {
+        match self {
+            Reflection::Horizontal { rows_above } => rows_above * 100,
+            Reflection::Vertical { columns_left } => *columns_left,
         }
-        self.current_row_from_end -= 1;
-        let start_index = self.current_row_from_end * self.grid.columns;
-        let end_index = start_index + self.grid.columns;
-        let to_yield = self
-            .grid
-            .inner
-            .get(start_index..end_index)
-            .expect("We checked the slice is valid");
-        Some(to_yield)
     }
 }
 
-#[derive(Debug, Clone)]
-struct ColumnIterator<'a> {
-    grid: &'a GridPattern,
-    current_column_from_start: usize,
-    current_column_from_end: usize,
-}
-
-impl<'a> ColumnIterator<'a> {
-    fn new(grid_pattern: &'a GridPattern) -> Self {
-        Self {
-            grid: grid_pattern,
-            current_column_from_start: 0,
-            current_column_from_end: grid_pattern.columns,
-        }
-    }
-}
-
-impl<'a> FusedIterator for RowIterator<'a> {}
-
-impl<'a> Iterator for ColumnIterator<'a> {
-    // columns aren't continous in memory so can't return a slice
-    type Item = Vec<char>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_column_from_start >= self.current_column_from_end {
-            return None;
-        }
-
-        let mut column_data = Vec::with_capacity(self.grid.rows);
-        for row in 0..self.grid.rows {
-            let index = self.current_column_from_start + row * self.grid.columns;
-            column_data.push(
-                *self
-                    .grid
-                    .inner
-                    .get(index)
-                    .expect("the index is always in the bounds"),
-            )
-        }
-
-        self.current_column_from_start += 1;
-        Some(column_data)
-    }
-}
-
-impl<'a> DoubleEndedIterator for ColumnIterator<'a> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.current_column_from_end <= self.current_column_from_start {
-            return None;
-        }
-        self.current_column_from_end -= 1;
-
-        let mut column_data = Vec::with_capacity(self.grid.rows);
-        for row in 0..self.grid.rows {
-            let index = self.current_column_from_end + row * self.grid.columns;
-            column_data.push(
-                *self
-                    .grid
-                    .inner
-                    .get(index)
-                    .expect("the index is always in the bounds"),
-            )
-        }
-        Some(column_data)
-    }
-}
-
-impl<'a> FusedIterator for ColumnIterator<'a> {}
-
 #[derive(Debug)]
 pub struct GridPatterns {
     patterns: Vec<GridPattern>,
@@ -313,52 +173,249 @@ impl FromStr for GridPatterns {
         while let Some(line) = lines.next() {
             if line.is_empty() {
                 // reached the end of a pattern
-                patterns.push(GridPattern::from_str_lines(&current_pattern_lines));
+                patterns.push(GridPattern::from_str_lines(&current_pattern_lines)?);
                 current_pattern_lines = vec![];
             } else {
                 current_pattern_lines.push(line);
             }
         }
 
-        patterns.push(GridPattern::from_str_lines(&current_pattern_lines));
+        patterns.push(GridPattern::from_str_lines(&current_pattern_lines)?);
         Ok(Self { patterns })
     }
 }
 
-fn find_reflection(grid_patterns: &GridPatterns, with_smudge: bool) -> usize {
+///
+/// The reflection of every pattern, in order
+pub fn find_reflections(
+    grid_patterns: &GridPatterns,
+    with_smudge: bool,
+) -> anyhow::Result<Vec<Reflection>> {
     grid_patterns
         .patterns
         .iter()
-        .map(|x| {
+        .enumerate()
+        .map(|(index, x)| {
             x.find_reflection(with_smudge)
-                .expect("question must be solvable")
+                .with_context(|| format!("no reflection line in pattern {index}"))
         })
-        .sum()
+        .collect()
+}
+
+///
+/// Like `find_reflections`, with the transposed patterns in a single arena which is reset after
+/// every pattern
+#[cfg(feature = "arena")]
+fn find_reflections_in_arena(
+    grid_patterns: &GridPatterns,
+    with_smudge: bool,
+) -> anyhow::Result<usize> {
+    let mut arena = ParseContext::new();
+    let mut sum = 0;
+    for (index, x) in grid_patterns.patterns.iter().enumerate() {
+        let reflection = match x.find_horizontal_reflection_line(with_smudge) {
+            Some(rows_above) => Reflection::Horizontal { rows_above },
+            None => x
+                .find_vertical_reflection_line_in(&arena, with_smudge)
+                .map(|columns_left| Reflection::Vertical { columns_left })
+                .with_context(|| format!("no reflection line in pattern {index}"))?,
+        };
+        sum += reflection.summary();
+        arena.reset();
+    }
+    Ok(sum)
+}
+
+fn find_reflection(grid_patterns: &GridPatterns, with_smudge: bool) -> anyhow::Result<usize> {
+    Ok(find_reflections(grid_patterns, with_smudge)?
+        .iter()
+        .map(Reflection::summary)
+        .sum())
 }
 
-pub fn part1(grid_patterns: &GridPatterns) -> usize {
+pub fn part1(grid_patterns: &GridPatterns) -> anyhow::Result<usize> {
     find_reflection(grid_patterns, false)
 }
 
-pub fn part2(grid_patterns: &GridPatterns) -> usize {
+pub fn part2(grid_patterns: &GridPatterns) -> anyhow::Result<usize> {
     find_reflection(grid_patterns, true)
 }
 
+#[cfg(feature = "arena")]
+pub fn part1_arena(grid_patterns: &GridPatterns) -> anyhow::Result<usize> {
+    find_reflections_in_arena(grid_patterns, false)
+}
+
+#[cfg(feature = "arena")]
+pub fn part2_arena(grid_patterns: &GridPatterns) -> anyhow::Result<usize> {
+    find_reflections_in_arena(grid_patterns, true)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = GridPatterns;
+
+    const TITLE: &'static str = "Point of Incidence";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::{get_day_test_input, parse_input};
+    use crate::{
+        testing::{seeded_patterns, DIFFERENTIAL_SEEDS},
+        utils::{get_day_test_input, try_parse_input},
+    };
 
     use super::*;
 
     #[test]
     fn test_part1() {
-        let grid_patterns = parse_input(get_day_test_input("day13"));
-        assert_eq!(part1(&grid_patterns), 405);
+        let grid_patterns = try_parse_input(get_day_test_input("day13")).unwrap();
+        assert_eq!(part1(&grid_patterns).unwrap(), 405);
     }
 
     #[test]
     fn test_part2() {
-        let grid_patterns = parse_input(get_day_test_input("day13"));
-        assert_eq!(part2(&grid_patterns), 400);
+        let grid_patterns = try_parse_input(get_day_test_input("day13")).unwrap();
+        assert_eq!(part2(&grid_patterns).unwrap(), 400);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let pattern = GridPattern::from_str_lines(&["#..", "##."]).unwrap();
+        let transposed = pattern.transpose();
+        assert_eq!(
+            transposed.grid,
+            GridPattern::from_str_lines(&["##", ".#", ".."])
+                .unwrap()
+                .grid
+        );
+        assert_eq!(transposed.transpose().grid, pattern.grid);
+    }
+
+    #[test]
+    fn test_transpose_preserves_reflections() {
+        let pattern = GridPattern::from_str_lines(&[
+            "#.##..##.",
+            "..#.##.#.",
+            "##......#",
+            "##......#",
+            "..#.##.#.",
+            "..##..##.",
+            "#.#.##.#.",
+        ])
+        .unwrap();
+        let transposed = pattern.transpose();
+
+        assert_eq!(pattern.find_vertical_reflection_line(false), Some(5));
+        assert_eq!(transposed.find_horizontal_reflection_line(false), Some(5));
+        assert_eq!(pattern.find_horizontal_reflection_line(true), Some(3));
+        assert_eq!(transposed.find_vertical_reflection_line(true), Some(3));
+    }
+
+    ///
+    /// The first line where the mirrored rows differ in exactly `smudges` cells, comparing every
+    /// cell by index
+    fn horizontal_brute_force(grid: &Grid<u8>, smudges: usize) -> Option<usize> {
+        let num_rows = grid.num_rows();
+        (1..num_rows).find(|&line| {
+            let differences: usize = (0..line.min(num_rows - line))
+                .map(|offset| {
+                    (0..grid.num_columns())
+                        .filter(|&column| {
+                            grid.get(line - 1 - offset, column) != grid.get(line + offset, column)
+                        })
+                        .count()
+                })
+                .sum();
+            differences == smudges
+        })
+    }
+
+    fn reflection_brute_force(pattern: &GridPattern, with_smudge: bool) -> Option<Reflection> {
+        let smudges = usize::from(with_smudge);
+        horizontal_brute_force(&pattern.grid, smudges)
+            .map(|rows_above| Reflection::Horizontal { rows_above })
+            .or_else(|| {
+                horizontal_brute_force(&pattern.grid.transpose(), smudges)
+                    .map(|columns_left| Reflection::Vertical { columns_left })
+            })
+    }
+
+    #[test]
+    fn test_seeded_inputs_match_brute_force() {
+        for seed in DIFFERENTIAL_SEEDS {
+            let input = seeded_patterns(seed);
+            let grid_patterns: GridPatterns = input.parse().unwrap();
+            for pattern in &grid_patterns.patterns {
+                for with_smudge in [false, true] {
+                    assert_eq!(
+                        pattern.find_reflection(with_smudge),
+                        reflection_brute_force(pattern, with_smudge),
+                        "seed {seed}, with_smudge {with_smudge}:\n{input}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn test_arena_matches_heap() {
+        for seed in DIFFERENTIAL_SEEDS.take(50) {
+            let grid_patterns: GridPatterns = seeded_patterns(seed).parse().unwrap();
+            assert_eq!(
+                part1_arena(&grid_patterns).ok(),
+                part1(&grid_patterns).ok(),
+                "seed {seed}"
+            );
+            assert_eq!(
+                part2_arena(&grid_patterns).ok(),
+                part2(&grid_patterns).ok(),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_reflections_snapshot() {
+        let grid_patterns: GridPatterns = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#"
+            .parse()
+            .unwrap();
+
+        insta::assert_debug_snapshot!(
+            "reflections",
+            find_reflections(&grid_patterns, false).unwrap()
+        );
+        insta::assert_debug_snapshot!(
+            "reflections_with_smudge",
+            find_reflections(&grid_patterns, true).unwrap()
+        );
     }
 }
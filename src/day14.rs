@@ -0,0 +1,277 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::{geom::Direction, grid::Grid},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Rock {
+    Round,
+    Cube,
+    Empty,
+}
+
+impl TryFrom<char> for Rock {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'O' => Ok(Self::Round),
+            '#' => Ok(Self::Cube),
+            '.' => Ok(Self::Empty),
+            _ => anyhow::bail!("invalid value for rock: {value}"),
+        }
+    }
+}
+
+impl From<Rock> for char {
+    fn from(value: Rock) -> Self {
+        match value {
+            Rock::Round => 'O',
+            Rock::Cube => '#',
+            Rock::Empty => '.',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    grid: Grid<Rock>,
+}
+
+impl FromStr for Platform {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_chars(s, Rock::try_from).context("failed to parse platform")?;
+        Ok(Self { grid })
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.grid.rows() {
+            let row: String = row.iter().map(|&rock| char::from(rock)).collect();
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Platform {
+    ///
+    /// Roll the round rocks along a line of cells, towards its first cell, until they hit the
+    /// end, a cube rock or a round rock which already stopped. `cell` maps an index along the line
+    /// to its (row, column), so every direction is rolled in place the same way.
+    fn roll_line(&mut self, len: usize, cell: impl Fn(usize) -> (usize, usize)) {
+        let mut free = 0;
+        for index in 0..len {
+            let (row, column) = cell(index);
+            match self.grid.get(row, column) {
+                Some(Rock::Round) => {
+                    self.grid.swap(cell(free), (row, column));
+                    free += 1;
+                }
+                Some(Rock::Cube) => free = index + 1,
+                _ => {}
+            }
+        }
+    }
+
+    ///
+    /// Roll every round rock as far as it goes in the direction
+    pub fn tilt(&mut self, direction: Direction) {
+        let num_rows = self.grid.num_rows();
+        let num_columns = self.grid.num_columns();
+        match direction {
+            Direction::North => {
+                for column in 0..num_columns {
+                    self.roll_line(num_rows, |index| (index, column));
+                }
+            }
+            Direction::South => {
+                for column in 0..num_columns {
+                    self.roll_line(num_rows, |index| (num_rows - 1 - index, column));
+                }
+            }
+            Direction::West => {
+                for row in 0..num_rows {
+                    self.roll_line(num_columns, |index| (row, index));
+                }
+            }
+            Direction::East => {
+                for row in 0..num_rows {
+                    self.roll_line(num_columns, |index| (row, num_columns - 1 - index));
+                }
+            }
+        }
+    }
+
+    ///
+    /// Tilt north, west, south and then east
+    pub fn spin_cycle(&mut self) {
+        for direction in SPIN_CYCLE {
+            self.tilt(direction);
+        }
+    }
+
+    ///
+    /// Every round rock weighs on the north support beam as much as the number of rows from it to
+    /// the south edge, including its own
+    pub fn north_load(&self) -> usize {
+        let num_rows = self.grid.num_rows();
+        self.grid
+            .rows()
+            .enumerate()
+            .map(|(row, rocks)| {
+                (num_rows - row) * rocks.iter().filter(|&&rock| rock == Rock::Round).count()
+            })
+            .sum()
+    }
+}
+
+const SPIN_CYCLE: [Direction; 4] = [
+    Direction::North,
+    Direction::West,
+    Direction::South,
+    Direction::East,
+];
+
+const SPIN_CYCLES_PART2: usize = 1_000_000_000;
+
+///
+/// The load after spinning the platform `cycles` times. The platform soon settles into a loop of
+/// states, so once a state repeats the load is looked up by where `cycles` falls in the loop
+/// instead of spinning any further.
+///
+pub fn load_after_spin_cycles(platform: &Platform, cycles: usize) -> usize {
+    let mut platform = platform.clone();
+    // the cycle at which each state was first seen, and the load after each cycle
+    let mut seen: HashMap<Platform, usize> = HashMap::new();
+    let mut loads = Vec::new();
+    for cycle in 0..cycles {
+        if let Some(&loop_start) = seen.get(&platform) {
+            let loop_length = cycle - loop_start;
+            return loads[loop_start + (cycles - loop_start) % loop_length];
+        }
+
+        loads.push(platform.north_load());
+        seen.insert(platform.clone(), cycle);
+        platform.spin_cycle();
+    }
+
+    platform.north_load()
+}
+
+pub fn part1(platform: &Platform) -> usize {
+    let mut platform = platform.clone();
+    platform.tilt(Direction::North);
+    platform.north_load()
+}
+
+pub fn part2(platform: &Platform) -> usize {
+    load_after_spin_cycles(platform, SPIN_CYCLES_PART2)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = Platform;
+
+    const TITLE: &'static str = "Parabolic Reflector Dish";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    #[test]
+    fn test_tilt_north() {
+        let mut platform: Platform = EXAMPLE.parse().unwrap();
+        platform.tilt(Direction::North);
+        assert_eq!(
+            platform.to_string(),
+            "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....
+"
+        );
+        assert_eq!(platform.north_load(), 136);
+    }
+
+    #[test]
+    fn test_spin_cycle() {
+        let mut platform: Platform = EXAMPLE.parse().unwrap();
+        platform.spin_cycle();
+        assert_eq!(
+            platform.to_string(),
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+"
+        );
+    }
+
+    #[test]
+    fn test_load_after_spin_cycles() {
+        let platform: Platform = EXAMPLE.parse().unwrap();
+        let mut spun = platform.clone();
+        for cycles in 0..30 {
+            assert_eq!(
+                load_after_spin_cycles(&platform, cycles),
+                spun.north_load(),
+                "{cycles} cycles"
+            );
+            spun.spin_cycle();
+        }
+        assert_eq!(part2(&platform), 64);
+    }
+
+    #[test]
+    fn test_part1() {
+        let platform: Platform = EXAMPLE.parse().unwrap();
+        assert_eq!(part1(&platform), 136);
+        assert!("O.x".parse::<Platform>().is_err());
+    }
+}
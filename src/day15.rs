@@ -1,8 +1,10 @@
-use std::str::FromStr;
-
 use anyhow::Context;
 use itertools::Itertools;
 
+use crate::solution::{Answer, Solution};
+#[cfg(feature = "arena")]
+use crate::utils::arena::ParseContext;
+
 // assume all characters are ascii
 fn hash_char(c: char, current_value: u32) -> u32 {
     (((c as u8) as u32 + current_value) * 17) % 256
@@ -18,27 +20,27 @@ pub fn part1(input: &str) -> u32 {
     input.trim().split(",").map(hash_str).sum()
 }
 
+///
+/// A lens in a box, with its label borrowed from the input
 #[derive(Debug)]
-struct BoxContent {
-    label: String,
+struct BoxContent<'a> {
+    label: &'a str,
     focal_length: u32,
 }
 
-enum SequenceOperation {
+enum SequenceOperation<'a> {
     AddLens {
-        box_content: BoxContent,
+        box_content: BoxContent<'a>,
         box_index: usize,
     },
     RemoveLens {
-        label: String,
+        label: &'a str,
         box_index: usize,
     },
 }
 
-impl FromStr for SequenceOperation {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl<'a> SequenceOperation<'a> {
+    fn parse(s: &'a str) -> anyhow::Result<Self> {
         let (label, rest) = match s.chars().last().context("got empty string")? {
             '-' => {
                 anyhow::ensure!(s.len() > 1);
@@ -46,12 +48,13 @@ impl FromStr for SequenceOperation {
             }
             d if d.is_ascii_digit() => {
                 anyhow::ensure!(s.len() > 2);
-                s.split_at(s.len() - 2)
+                // the operation before the digit may not be a single byte
+                s.split_at_checked(s.len() - 2)
+                    .with_context(|| format!("invalid operation: {s}"))?
             }
             _ => anyhow::bail!("invalid sequence: {s}"),
         };
         let box_index = hash_str(label) as usize;
-        let label = label.to_string();
 
         let mut rest = rest.chars();
         match rest.next().context("invalid char sequence")? {
@@ -72,14 +75,10 @@ impl FromStr for SequenceOperation {
     }
 }
 
-fn build_lens_hashmap(
-    operations: Vec<SequenceOperation>,
-) -> anyhow::Result<[Vec<BoxContent>; 256]> {
-    let mut boxes = Vec::with_capacity(256);
-    for _ in 0..256 {
-        boxes.push(Vec::new());
-    }
-    let mut boxes: [Vec<BoxContent>; 256] = boxes.try_into().expect("number of boxes matches");
+fn build_lens_hashmap<'a>(
+    operations: impl IntoIterator<Item = SequenceOperation<'a>>,
+) -> anyhow::Result<[Vec<BoxContent<'a>>; 256]> {
+    let mut boxes: [Vec<BoxContent<'a>>; 256] = std::array::from_fn(|_| Vec::new());
 
     for operation in operations {
         match operation {
@@ -114,7 +113,7 @@ fn build_lens_hashmap(
     Ok(boxes)
 }
 
-fn get_focusing_power(indexed_box: (usize, Vec<BoxContent>)) -> usize {
+fn get_focusing_power(indexed_box: (usize, Vec<BoxContent<'_>>)) -> usize {
     let (box_index, box_content_vec) = indexed_box;
     box_content_vec
         .into_iter()
@@ -125,22 +124,63 @@ fn get_focusing_power(indexed_box: (usize, Vec<BoxContent>)) -> usize {
         .sum()
 }
 
-pub fn part2(input: &str) -> usize {
-    let operations = input
+pub fn part2(input: &str) -> anyhow::Result<usize> {
+    let operations: Vec<SequenceOperation> = input
         .trim()
         .split(",")
         .map(|x| {
-            x.parse::<SequenceOperation>()
-                .expect("sequence can always be parsed")
+            SequenceOperation::parse(x)
+                .with_context(|| format!("failed to parse sequence step: {x}"))
         })
-        .collect_vec();
+        .try_collect()?;
 
-    let hashmap = build_lens_hashmap(operations).unwrap();
-    hashmap
+    let hashmap = build_lens_hashmap(operations)?;
+    Ok(hashmap
         .into_iter()
         .enumerate()
         .map(get_focusing_power)
-        .sum()
+        .sum())
+}
+
+///
+/// Like `part2`, but the steps are collected in an arena instead of a `Vec`
+#[cfg(feature = "arena")]
+pub fn part2_arena(input: &str) -> anyhow::Result<usize> {
+    let arena = ParseContext::new();
+    let mut operations = arena.vec();
+    for x in input.trim().split(",") {
+        operations.push(
+            SequenceOperation::parse(x)
+                .with_context(|| format!("failed to parse sequence step: {x}"))?,
+        );
+    }
+
+    let hashmap = build_lens_hashmap(operations)?;
+    Ok(hashmap
+        .into_iter()
+        .enumerate()
+        .map(get_focusing_power)
+        .sum())
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = &'a str;
+
+    const TITLE: &'static str = "Lens Library";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -162,6 +202,33 @@ mod tests {
     fn test_part2() {
         let input = get_day_test_input("day15");
         let input = read_to_string(&input).unwrap();
-        assert_eq!(part2(&input), 145);
+        assert_eq!(part2(&input).unwrap(), 145);
+    }
+
+    #[test]
+    fn test_invalid_steps() {
+        assert!(part2("é5").is_err());
+        assert!(part2("rn=").is_err());
+        assert!(part2("-").is_err());
+    }
+
+    #[test]
+    fn test_labels_borrow_input() {
+        let input = String::from("rn=1,cm-");
+        let Ok(SequenceOperation::AddLens { box_content, .. }) =
+            SequenceOperation::parse(&input[..4])
+        else {
+            panic!("expected a lens to add");
+        };
+        assert_eq!(box_content.label, "rn");
+        assert_eq!(box_content.label.as_ptr(), input.as_ptr());
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn test_arena_matches_heap() {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+        assert_eq!(part2_arena(input).unwrap(), 145);
+        assert!(part2_arena("rn=").is_err());
     }
 }
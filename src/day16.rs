@@ -1,9 +1,22 @@
-use std::{collections::HashSet, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
 use anyhow::Context;
 
+use crate::{
+    progress::Progress,
+    solution::{Answer, Solution},
+    utils::{
+        geom::{Direction, Point},
+        grid::Grid,
+    },
+    viz::{draw_points, Render},
+};
+
 #[derive(Debug, PartialEq, Eq)]
-pub enum GridElement {
+pub(crate) enum GridElement {
     EmptySpace,
     LeftToRightMirror,
     RightToLeftMirror,
@@ -78,89 +91,63 @@ impl GridElement {
 }
 
 pub struct Contraption {
-    grid: Vec<Vec<GridElement>>,
+    grid: Grid<GridElement>,
 }
 
 impl FromStr for Contraption {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut grid = Vec::new();
-        for line in s.lines() {
-            let grid_line: anyhow::Result<Vec<GridElement>> = line
-                .chars()
-                .into_iter()
-                .map(|x| x.try_into().context("failed to parse grid element"))
-                .collect();
-            grid.push(grid_line.context("failed to parse grid line")?)
-        }
+        let grid = Grid::parse_chars(s, |x| x.try_into().context("failed to parse grid element"))?;
 
         Ok(Self { grid })
     }
 }
 
+impl std::fmt::Display for Contraption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.grid.rows() {
+            for element in row {
+                write!(f, "{}", element)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 impl Contraption {
-    fn get(&self, index: (usize, usize)) -> Option<&GridElement> {
-        self.grid.get(index.1).map(|line| line.get(index.0))?
+    fn get(&self, index: Point) -> Option<&GridElement> {
+        self.grid.get(index.y, index.x)
     }
 
-    #[allow(dead_code)]
-    fn draw_energized(&self, energized: &HashSet<(usize, usize)>) {
-        for (y, line) in self.grid.iter().enumerate() {
-            for (x, c) in line.iter().enumerate() {
-                if energized.contains(&(x, y)) {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-            println!("")
-        }
+    fn draw_energized(&self, energized: &HashSet<Point>) -> String {
+        draw_points(energized, self.num_columns(), self.num_rows())
     }
 
     fn num_rows(&self) -> usize {
-        self.grid.len()
+        self.grid.num_rows()
     }
 
     fn num_columns(&self) -> usize {
-        self.grid.get(0).map(|x| x.len()).unwrap_or(0)
+        self.grid.num_columns()
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct MovingBeam {
-    current: (usize, usize),
-    direction: Direction,
+pub struct MovingBeam {
+    pub current: Point,
+    pub direction: Direction,
 }
 
 impl MovingBeam {
     fn get_next_location<'a>(
         &mut self,
         contraption: &'a Contraption,
-    ) -> (Option<(usize, usize)>, Option<Self>) {
-        self.current = match self.direction {
-            Direction::North => {
-                if self.current.1 == 0 {
-                    return (None, None);
-                }
-                (self.current.0, self.current.1 - 1)
-            }
-            Direction::South => (self.current.0, self.current.1 + 1),
-            Direction::East => (self.current.0 + 1, self.current.1),
-            Direction::West => {
-                if self.current.0 == 0 {
-                    return (None, None);
-                }
-                (self.current.0 - 1, self.current.1)
-            }
+    ) -> (Option<Point>, Option<Self>) {
+        self.current = match self.current.step(self.direction) {
+            Some(next) => next,
+            None => return (None, None),
         };
 
         let Some(element) = contraption.get(self.current) else {
@@ -182,16 +169,20 @@ impl MovingBeam {
     }
 }
 
-struct Beams<'a> {
+///
+/// The beams moving through a contraption, along with every cell they've energized so far
+///
+pub struct Beams<'a> {
     contraption: &'a Contraption,
     beams: Vec<MovingBeam>,
-    energized: HashSet<(usize, usize)>,
+    energized: HashSet<Point>,
+    passes: HashMap<Point, usize>,
     previous_steps: HashSet<MovingBeam>,
 }
 
 impl<'a> Beams<'a> {
     fn new(contraption: &'a Contraption) -> Self {
-        let current = (0, 0);
+        let current = Point { x: 0, y: 0 };
         let element = contraption.get(current).expect("must start at (0,0)");
         let (direction, next_beam) = element.get_next_direction(Direction::East);
         assert!(next_beam.is_none()); // pls no
@@ -199,7 +190,8 @@ impl<'a> Beams<'a> {
         Self {
             contraption,
             beams: vec![MovingBeam { current, direction }],
-            energized: HashSet::from_iter([(0, 0)]),
+            energized: HashSet::from_iter([current]),
+            passes: HashMap::from_iter([(current, 1)]),
             previous_steps: HashSet::from_iter([MovingBeam { current, direction }]),
         }
     }
@@ -220,6 +212,7 @@ impl<'a> Beams<'a> {
         };
 
         let energized = HashSet::from_iter([start_index]);
+        let passes = HashMap::from_iter([(start_index, 1)]);
         let mut previous_steps = HashSet::from_iter([start_beam]);
         let mut beams = vec![start_beam];
 
@@ -237,6 +230,7 @@ impl<'a> Beams<'a> {
             contraption,
             beams,
             energized,
+            passes,
             previous_steps,
         })
     }
@@ -257,6 +251,7 @@ impl<'a> Beams<'a> {
                     }
 
                     locations_to_add.insert(location);
+                    *self.passes.entry(location).or_default() += 1;
                     self.previous_steps.insert(*beam);
                     true
                 }
@@ -264,22 +259,55 @@ impl<'a> Beams<'a> {
             }
         });
 
-        // eprintln!("{:?}", self.beams);
-        // std::thread::sleep(std::time::Duration::from_millis(500));
+        tracing::trace!(beams = ?self.beams, "bounced");
         self.energized.extend(locations_to_add);
         self.beams.extend(beams_to_add);
 
         !self.beams.is_empty()
     }
+
+    /// How many times any beam traversed each energized cell, useful for spotting hotspots.
+    pub fn pass_counts(&self) -> HashMap<Point, usize> {
+        self.passes.clone()
+    }
 }
 
-pub fn part1(contraption: &Contraption) -> usize {
+///
+/// The beams after the beam entering from the top left bounced around until they stopped
+/// energizing anything new
+///
+pub fn part1_beams(contraption: &Contraption) -> Beams<'_> {
     let mut beams = Beams::new(contraption);
+    while beams.next_bounce() {}
+    beams
+}
+
+///
+/// Draw which cells are energized by the beam entering from the top left, as `#`
+///
+pub fn draw_part1_energized(contraption: &Contraption) -> String {
+    contraption.draw_energized(&part1_beams(contraption).energized)
+}
+
+impl Render for Contraption {
+    fn render_ascii(&self) -> anyhow::Result<String> {
+        Ok(draw_part1_energized(self))
+    }
+}
+
+pub fn part1(contraption: &Contraption) -> anyhow::Result<usize> {
+    let start_beam = MovingBeam {
+        current: Point { x: 0, y: 0 },
+        direction: Direction::East,
+    };
+    let mut beams = Beams::with_start_beam(contraption, start_beam)?;
     while beams.next_bounce() {
-        // contraption.draw_energized(&beams.energized);
-        // println!("");
+        tracing::trace!(
+            "energized:\n{}",
+            contraption.draw_energized(&beams.energized)
+        );
     }
-    beams.energized.len()
+    Ok(beams.energized.len())
 }
 
 fn get_num_energized(beams: &mut Beams<'_>) -> usize {
@@ -287,58 +315,181 @@ fn get_num_energized(beams: &mut Beams<'_>) -> usize {
     beams.energized.len()
 }
 
-pub fn part2(contraption: &Contraption) -> usize {
-    let mut energized = 0;
-
+/// Every beam entering the contraption from one of its edges
+fn get_edge_start_beams(contraption: &Contraption) -> Vec<MovingBeam> {
     let mut start_beams = Vec::new();
     for y in 0..contraption.num_rows() {
         start_beams.push(MovingBeam {
-            current: (0, y),
+            current: Point { x: 0, y },
             direction: Direction::East,
         });
 
         start_beams.push(MovingBeam {
-            current: (contraption.num_columns() - 1, y),
+            current: Point {
+                x: contraption.num_columns() - 1,
+                y,
+            },
             direction: Direction::West,
         });
     }
 
     for x in 0..contraption.num_columns() {
         start_beams.push(MovingBeam {
-            current: (x, 0),
+            current: Point { x, y: 0 },
             direction: Direction::South,
         });
 
         start_beams.push(MovingBeam {
-            current: (x, contraption.num_rows() - 1),
+            current: Point {
+                x,
+                y: contraption.num_rows() - 1,
+            },
             direction: Direction::North,
         });
     }
 
+    start_beams
+}
+
+///
+/// All the edge entries whose beams energize the `target` cell, given as (x, y)
+pub fn entries_energizing(contraption: &Contraption, target: Point) -> Vec<MovingBeam> {
+    get_edge_start_beams(contraption)
+        .into_iter()
+        .filter(|start_beam| {
+            let mut beams = Beams::with_start_beam(contraption, *start_beam).unwrap();
+            while beams.next_bounce() {}
+            beams.energized.contains(&target)
+        })
+        .collect()
+}
+
+pub fn part2(contraption: &Contraption) -> anyhow::Result<usize> {
+    let mut energized = 0;
+    let start_beams = get_edge_start_beams(contraption);
+    let progress = Progress::start("day16 start beams", start_beams.len() as u64);
+
     for start_beam in start_beams {
-        let mut beams = Beams::with_start_beam(contraption, start_beam).unwrap();
+        let mut beams = Beams::with_start_beam(contraption, start_beam)?;
 
         energized = energized.max(get_num_energized(&mut beams));
+        progress.inc(1);
     }
 
-    energized
+    Ok(energized)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Contraption;
+
+    const TITLE: &'static str = "The Floor Will Be Lava";
+
+    const CONCURRENT_PARTS: bool = true;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::{get_day_test_input, parse_input};
+    use proptest::prelude::*;
+
+    use crate::{
+        testing::char_grid,
+        utils::{get_day_test_input, try_parse_input},
+    };
 
     use super::*;
 
     #[test]
     fn test_part1() {
-        let input = parse_input(get_day_test_input("day16"));
-        assert_eq!(part1(&input), 46);
+        let input = try_parse_input(get_day_test_input("day16")).unwrap();
+        assert_eq!(part1(&input).unwrap(), 46);
     }
 
     #[test]
     fn test_part2() {
-        let input = parse_input(get_day_test_input("day16"));
-        assert_eq!(part2(&input), 51);
+        let input = try_parse_input(get_day_test_input("day16")).unwrap();
+        assert_eq!(part2(&input).unwrap(), 51);
+    }
+
+    #[test]
+    fn test_pass_counts() {
+        let contraption: Contraption = "..\\.\n....\n.\\/.\n....".parse().unwrap();
+        let beams = part1_beams(&contraption);
+        let pass_counts = beams.pass_counts();
+        assert_eq!(pass_counts.get(&Point { x: 1, y: 0 }), Some(&2));
+        assert_eq!(pass_counts.get(&Point { x: 0, y: 0 }), Some(&1));
+        assert_eq!(pass_counts.len(), beams.energized.len());
+        assert_eq!(
+            draw_part1_energized(&contraption),
+            "###.\n.##.\n.##.\n....\n"
+        );
+    }
+
+    #[test]
+    fn test_entries_energizing() {
+        let contraption: Contraption = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|...."
+            .parse()
+            .unwrap();
+
+        insta::assert_snapshot!("energized", draw_part1_energized(&contraption));
+
+        let entries = entries_energizing(&contraption, Point { x: 9, y: 0 });
+        assert_eq!(
+            entries,
+            vec![
+                MovingBeam {
+                    current: Point { x: 9, y: 0 },
+                    direction: Direction::West
+                },
+                MovingBeam {
+                    current: Point { x: 9, y: 5 },
+                    direction: Direction::West
+                },
+                MovingBeam {
+                    current: Point { x: 5, y: 0 },
+                    direction: Direction::South
+                },
+                MovingBeam {
+                    current: Point { x: 9, y: 0 },
+                    direction: Direction::South
+                },
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_display_roundtrips(input in char_grid(&['.', '/', '\\', '|', '-'], 10)) {
+            let contraption: Contraption = input.parse().unwrap();
+            prop_assert_eq!(contraption.to_string(), input);
+        }
+
+        #[test]
+        fn prop_best_entry_beats_top_left(input in char_grid(&['.', '/', '\\', '|', '-'], 10)) {
+            let contraption: Contraption = input.parse().unwrap();
+            prop_assert!(part2(&contraption).unwrap() >= part1(&contraption).unwrap());
+        }
     }
 }
@@ -0,0 +1,202 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::{
+        geom::{Direction, Point},
+        grid::Grid,
+        search::dijkstra,
+    },
+};
+
+///
+/// How many blocks in a row a crucible has to move in a direction before it can turn or stop, and
+/// how many it can move before it has to turn
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovementRules {
+    pub min_straight: u8,
+    pub max_straight: u8,
+}
+
+pub const CRUCIBLE: MovementRules = MovementRules {
+    min_straight: 0,
+    max_straight: 3,
+};
+
+pub const ULTRA_CRUCIBLE: MovementRules = MovementRules {
+    min_straight: 4,
+    max_straight: 10,
+};
+
+#[derive(Debug)]
+pub struct HeatLossMap {
+    grid: Grid<u8>,
+}
+
+impl FromStr for HeatLossMap {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_chars(s, |c| {
+            let heat_loss = c.to_digit(10).context("heat loss isn't a digit")?;
+            Ok(heat_loss as u8)
+        })
+        .context("failed to parse heat loss map")?;
+        Ok(Self { grid })
+    }
+}
+
+///
+/// Where a crucible is, which way it's going and how many blocks in a row it went that way. The
+/// same block is a different state depending on how the crucible got there.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Crucible {
+    position: Point,
+    direction: Direction,
+    steps: u8,
+}
+
+impl HeatLossMap {
+    fn heat_loss(&self, position: Point) -> u64 {
+        self.grid
+            .get(position.y, position.x)
+            .map_or(0, |&heat_loss| heat_loss.into())
+    }
+
+    ///
+    /// The crucibles reachable in one block, turning left or right or going straight as far as the
+    /// rules allow, along with the heat lost entering the block
+    fn successors(
+        &self,
+        crucible: &Crucible,
+        rules: MovementRules,
+    ) -> impl Iterator<Item = (Crucible, u64)> + '_ {
+        let Crucible {
+            position,
+            direction,
+            steps,
+        } = *crucible;
+        let can_turn = steps >= rules.min_straight;
+        let turns = [direction.turn_left(), direction.turn_right()]
+            .map(|x| (x, 1))
+            .into_iter()
+            .filter(move |_| can_turn);
+        let straight = (steps < rules.max_straight).then_some((direction, steps + 1));
+
+        let (width, height) = (self.grid.num_columns(), self.grid.num_rows());
+        turns.chain(straight).filter_map(move |(direction, steps)| {
+            let position = position.step_within(direction, width, height)?;
+            let crucible = Crucible {
+                position,
+                direction,
+                steps,
+            };
+            Some((crucible, self.heat_loss(position)))
+        })
+    }
+
+    ///
+    /// The least heat lost moving a crucible from the top left block to the bottom right one. The
+    /// heat loss of the starting block doesn't count, since the crucible is already there. The
+    /// crucible can only stop at the end once it moved enough blocks in a row to be able to turn.
+    pub fn least_heat_loss(&self, rules: MovementRules) -> Option<u64> {
+        if self.grid.is_empty() {
+            return None;
+        }
+        let target = Point::new(self.grid.num_columns() - 1, self.grid.num_rows() - 1);
+        let starts = [Direction::East, Direction::South].map(|direction| Crucible {
+            position: Point::new(0, 0),
+            direction,
+            steps: 0,
+        });
+
+        dijkstra(
+            starts,
+            |crucible| self.successors(crucible, rules),
+            |crucible| crucible.position == target && crucible.steps >= rules.min_straight,
+        )
+    }
+}
+
+pub fn part1(map: &HeatLossMap) -> anyhow::Result<u64> {
+    map.least_heat_loss(CRUCIBLE)
+        .context("the bottom right block is unreachable")
+}
+
+pub fn part2(map: &HeatLossMap) -> anyhow::Result<u64> {
+    map.least_heat_loss(ULTRA_CRUCIBLE)
+        .context("the bottom right block is unreachable by an ultra crucible")
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = HeatLossMap;
+
+    const TITLE: &'static str = "Clumsy Crucible";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    #[test]
+    fn test_part1() {
+        let map: HeatLossMap = EXAMPLE.parse().unwrap();
+        assert_eq!(part1(&map).unwrap(), 102);
+    }
+
+    #[test]
+    fn test_part2() {
+        let map: HeatLossMap = EXAMPLE.parse().unwrap();
+        assert_eq!(part2(&map).unwrap(), 94);
+
+        let map: HeatLossMap = "111111111111
+999999999991
+999999999991
+999999999991
+999999999991"
+            .parse()
+            .unwrap();
+        assert_eq!(part2(&map).unwrap(), 71);
+    }
+
+    #[test]
+    fn test_max_straight() {
+        // going straight along the top row would be cheapest, but the crucible has to turn
+        let map: HeatLossMap = "11111\n99991".parse().unwrap();
+        assert_eq!(part1(&map).unwrap(), 1 + 1 + 1 + 9 + 1);
+        assert!("12\n3x".parse::<HeatLossMap>().is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+
+use crate::{
+    geometry::shoelace_area,
+    solution::{Answer, Solution},
+    utils::{geom::Direction, parse_from_lines},
+};
+
+#[derive(Debug)]
+pub struct DigInstruction {
+    direction: Direction,
+    meters: i64,
+    /// The hex color of the trench, e.g. `0x70c710`
+    color: u32,
+}
+
+impl DigInstruction {
+    pub fn color(&self) -> u32 {
+        self.color
+    }
+
+    ///
+    /// The real instruction hidden in the color: the first five hex digits are the meters, and
+    /// the last one the direction
+    pub fn decode_color(&self) -> anyhow::Result<(Direction, i64)> {
+        let direction = match self.color & 0xf {
+            0 => Direction::East,
+            1 => Direction::South,
+            2 => Direction::West,
+            3 => Direction::North,
+            digit => anyhow::bail!(
+                "invalid direction digit in color {:06x}: {digit}",
+                self.color
+            ),
+        };
+        Ok((direction, i64::from(self.color >> 4)))
+    }
+}
+
+fn parse_direction(s: &str) -> anyhow::Result<Direction> {
+    match s {
+        "U" => Ok(Direction::North),
+        "D" => Ok(Direction::South),
+        "L" => Ok(Direction::West),
+        "R" => Ok(Direction::East),
+        _ => anyhow::bail!("invalid direction: {s}"),
+    }
+}
+
+impl FromStr for DigInstruction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let direction = parse_direction(parts.next().context("missing direction")?)?;
+        let meters = parts.next().context("missing meters")?;
+        let meters = meters
+            .parse()
+            .with_context(|| format!("failed to parse meters: {meters}"))?;
+        let color = parts.next().context("missing color")?;
+        let hex = color
+            .strip_prefix("(#")
+            .and_then(|x| x.strip_suffix(')'))
+            .with_context(|| format!("invalid color: {color}"))?;
+        let color = u32::from_str_radix(hex, 16)
+            .with_context(|| format!("failed to parse color: {color}"))?;
+
+        Ok(Self {
+            direction,
+            meters,
+            color,
+        })
+    }
+}
+
+///
+/// The number of cubic meters dug out by a closed trench and its interior. The shoelace formula
+/// gives the area of the polygon through the centers of the trench's cubes, and by Pick's theorem
+/// that's `interior + boundary / 2 - 1`, so adding the other half of the boundary and one more
+/// gives the whole lagoon. Only the corners are visited, however long the trench.
+///
+pub fn lagoon_volume(steps: impl IntoIterator<Item = (Direction, i64)>) -> i64 {
+    let (mut x, mut y) = (0, 0);
+    let mut corners = Vec::new();
+    let mut boundary = 0;
+    for (direction, meters) in steps {
+        match direction {
+            Direction::North => y -= meters,
+            Direction::South => y += meters,
+            Direction::West => x -= meters,
+            Direction::East => x += meters,
+        }
+        corners.push((x, y));
+        boundary += meters;
+    }
+
+    shoelace_area(&corners) + boundary / 2 + 1
+}
+
+pub fn part1(instructions: &[DigInstruction]) -> i64 {
+    lagoon_volume(instructions.iter().map(|x| (x.direction, x.meters)))
+}
+
+pub fn part2(instructions: &[DigInstruction]) -> anyhow::Result<i64> {
+    let steps = instructions
+        .iter()
+        .map(DigInstruction::decode_color)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(lagoon_volume(steps))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = Vec<DigInstruction>;
+
+    const TITLE: &'static str = "Lavaduct Lagoon";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        parse_from_lines(input.lines())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
+
+    #[test]
+    fn test_part1() {
+        let instructions = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(instructions[0].color(), 0x70c710);
+        assert_eq!(part1(&instructions), 62);
+    }
+
+    #[test]
+    fn test_part2() {
+        let instructions = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            instructions[0].decode_color().unwrap(),
+            (Direction::East, 461937)
+        );
+        assert_eq!(part2(&instructions).unwrap(), 952408144115);
+
+        let invalid: DigInstruction = "R 6 (#70c714)".parse().unwrap();
+        assert!(invalid.decode_color().is_err());
+    }
+
+    #[test]
+    fn test_lagoon_volume() {
+        // a 3x3 square trench around a single interior cube
+        let square = [
+            (Direction::East, 2),
+            (Direction::South, 2),
+            (Direction::West, 2),
+            (Direction::North, 2),
+        ];
+        assert_eq!(lagoon_volume(square), 9);
+    }
+
+    #[test]
+    fn test_invalid_instructions() {
+        assert!("X 6 (#70c710)".parse::<DigInstruction>().is_err());
+        assert!("R six (#70c710)".parse::<DigInstruction>().is_err());
+        assert!("R 6 70c710".parse::<DigInstruction>().is_err());
+        assert!("R 6".parse::<DigInstruction>().is_err());
+    }
+}
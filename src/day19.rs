@@ -0,0 +1,486 @@
+use std::{collections::HashMap, ops::Range, str::FromStr};
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::interval::split_range,
+};
+
+const START_WORKFLOW: &str = "in";
+
+const RATINGS: Range<u64> = 1..4001;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Category {
+    ExtremelyCoolLooking,
+    Musical,
+    Aerodynamic,
+    Shiny,
+}
+
+impl TryFrom<char> for Category {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'x' => Ok(Self::ExtremelyCoolLooking),
+            'm' => Ok(Self::Musical),
+            'a' => Ok(Self::Aerodynamic),
+            's' => Ok(Self::Shiny),
+            _ => anyhow::bail!("invalid category: {value}"),
+        }
+    }
+}
+
+impl Category {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+///
+/// A part's rating in each category, in `xmas` order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Part {
+    ratings: [u32; 4],
+}
+
+impl Part {
+    fn rating(&self, category: Category) -> u32 {
+        self.ratings[category.index()]
+    }
+
+    pub fn total_rating(&self) -> u32 {
+        self.ratings.iter().sum()
+    }
+}
+
+///
+/// Every part with a rating in each of the ranges, in `xmas` order
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PartRanges {
+    ranges: [Range<u64>; 4],
+}
+
+impl PartRanges {
+    fn all() -> Self {
+        Self {
+            ranges: std::array::from_fn(|_| RATINGS),
+        }
+    }
+
+    fn combinations(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| range.end.saturating_sub(range.start))
+            .product()
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // {x=787,m=2655,a=1222,s=2876}
+        let ratings = s
+            .strip_prefix('{')
+            .and_then(|x| x.strip_suffix('}'))
+            .with_context(|| format!("invalid part: {s}"))?;
+
+        let mut parsed = [None; 4];
+        for rating in ratings.split(',') {
+            let (category, value) = rating
+                .split_once('=')
+                .with_context(|| format!("invalid rating: {rating}"))?;
+            let mut chars = category.chars();
+            let category = match (chars.next(), chars.next()) {
+                (Some(c), None) => Category::try_from(c)?,
+                _ => anyhow::bail!("invalid category: {category}"),
+            };
+            let value = value
+                .parse()
+                .with_context(|| format!("failed to parse rating: {value}"))?;
+            anyhow::ensure!(
+                parsed[category.index()].replace(value).is_none(),
+                "duplicate rating in part: {s}"
+            );
+        }
+
+        let mut ratings = [0; 4];
+        for (rating, parsed) in ratings.iter_mut().zip(parsed) {
+            *rating = parsed.with_context(|| format!("missing rating in part: {s}"))?;
+        }
+        Ok(Self { ratings })
+    }
+}
+
+///
+/// Where a part goes once a rule matches it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target<'a> {
+    Accept,
+    Reject,
+    Workflow(&'a str),
+}
+
+impl<'a> Target<'a> {
+    fn parse(s: &'a str) -> anyhow::Result<Self> {
+        match s {
+            "A" => Ok(Self::Accept),
+            "R" => Ok(Self::Reject),
+            "" => anyhow::bail!("missing target"),
+            label => Ok(Self::Workflow(label)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    LessThan,
+    GreaterThan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Condition {
+    category: Category,
+    comparison: Comparison,
+    value: u32,
+}
+
+impl Condition {
+    fn matches(&self, part: &Part) -> bool {
+        let rating = part.rating(self.category);
+        match self.comparison {
+            Comparison::LessThan => rating < self.value,
+            Comparison::GreaterThan => rating > self.value,
+        }
+    }
+
+    ///
+    /// Split the ranges into the parts matching the condition and the parts which don't
+    fn split(&self, ranges: PartRanges) -> (PartRanges, PartRanges) {
+        let index = self.category.index();
+        let value = u64::from(self.value);
+        let (matching, rest) = match self.comparison {
+            Comparison::LessThan => split_range(ranges.ranges[index].clone(), value),
+            Comparison::GreaterThan => {
+                let (rest, matching) = split_range(ranges.ranges[index].clone(), value + 1);
+                (matching, rest)
+            }
+        };
+
+        let mut matching_ranges = ranges.clone();
+        matching_ranges.ranges[index] = matching;
+        let mut rest_ranges = ranges;
+        rest_ranges.ranges[index] = rest;
+        (matching_ranges, rest_ranges)
+    }
+}
+
+impl FromStr for Condition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // a<2006
+        let mut chars = s.chars();
+        let category = Category::try_from(chars.next().context("missing category")?)?;
+        let comparison = match chars.next().context("missing comparison")? {
+            '<' => Comparison::LessThan,
+            '>' => Comparison::GreaterThan,
+            c => anyhow::bail!("invalid comparison: {c}"),
+        };
+        let value = chars.as_str();
+        let value = value
+            .parse()
+            .with_context(|| format!("failed to parse condition value: {value}"))?;
+
+        Ok(Self {
+            category,
+            comparison,
+            value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rule<'a> {
+    condition: Condition,
+    target: Target<'a>,
+}
+
+///
+/// Rules tried in order, sending a part to the target of the first one it matches, or to the
+/// fallback if it matches none
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Workflow<'a> {
+    rules: Vec<Rule<'a>>,
+    fallback: Target<'a>,
+}
+
+impl<'a> Workflow<'a> {
+    fn target(&self, part: &Part) -> Target<'a> {
+        self.rules
+            .iter()
+            .find(|rule| rule.condition.matches(part))
+            .map_or(self.fallback, |rule| rule.target)
+    }
+
+    ///
+    /// Parse a workflow along with its label, e.g. `px{a<2006:qkq,m>2090:A,rfg}`
+    fn parse(s: &'a str) -> anyhow::Result<(&'a str, Self)> {
+        let (label, rules) = s
+            .strip_suffix('}')
+            .and_then(|x| x.split_once('{'))
+            .with_context(|| format!("invalid workflow: {s}"))?;
+        anyhow::ensure!(!label.is_empty(), "missing workflow label: {s}");
+
+        let (rules, fallback) = match rules.rsplit_once(',') {
+            Some((rules, fallback)) => (Some(rules), fallback),
+            None => (None, rules),
+        };
+        let rules = rules
+            .into_iter()
+            .flat_map(|x| x.split(','))
+            .map(|rule| {
+                let (condition, target) = rule
+                    .split_once(':')
+                    .with_context(|| format!("invalid rule: {rule}"))?;
+                Ok(Rule {
+                    condition: condition.parse()?,
+                    target: Target::parse(target)?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let fallback = Target::parse(fallback)?;
+
+        Ok((label, Self { rules, fallback }))
+    }
+}
+
+///
+/// The workflows by label, with their labels borrowed from the input, and the parts to sort
+///
+#[derive(Debug)]
+pub struct System<'a> {
+    workflows: HashMap<&'a str, Workflow<'a>>,
+    parts: Vec<Part>,
+}
+
+impl<'a> System<'a> {
+    pub fn parse(s: &'a str) -> anyhow::Result<Self> {
+        let mut lines = s.lines();
+        let mut workflows = HashMap::new();
+        for line in lines.by_ref().take_while(|line| !line.is_empty()) {
+            let (label, workflow) = Workflow::parse(line)?;
+            anyhow::ensure!(
+                workflows.insert(label, workflow).is_none(),
+                "duplicate workflow: {label}"
+            );
+        }
+        let parts = lines
+            .filter(|line| !line.is_empty())
+            .map(str::parse)
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self { workflows, parts })
+    }
+
+    ///
+    /// Run the part through the workflows, starting at `in`, until it's accepted or rejected.
+    /// A part going around in circles would never be either, so that's an error.
+    pub fn accepts(&self, part: &Part) -> anyhow::Result<bool> {
+        let mut label = START_WORKFLOW;
+        // without a loop, a part can't visit more workflows than there are
+        for _ in 0..=self.workflows.len() {
+            let workflow = self
+                .workflows
+                .get(label)
+                .with_context(|| format!("unknown workflow: {label}"))?;
+            match workflow.target(part) {
+                Target::Accept => return Ok(true),
+                Target::Reject => return Ok(false),
+                Target::Workflow(next) => label = next,
+            }
+        }
+
+        anyhow::bail!("the workflows loop for part {part:?}")
+    }
+
+    ///
+    /// How many of the parts in the ranges end up accepted once sent to the target. Each rule of a
+    /// workflow splits off the parts it matches, and what none of them match goes to the fallback.
+    fn accepted_combinations(
+        &self,
+        target: Target<'a>,
+        ranges: PartRanges,
+        depth: usize,
+    ) -> anyhow::Result<u64> {
+        let label = match target {
+            Target::Accept => return Ok(ranges.combinations()),
+            Target::Reject => return Ok(0),
+            Target::Workflow(label) => label,
+        };
+        if ranges.combinations() == 0 {
+            return Ok(0);
+        }
+        anyhow::ensure!(
+            depth <= self.workflows.len(),
+            "the workflows loop through {label}"
+        );
+        let workflow = self
+            .workflows
+            .get(label)
+            .with_context(|| format!("unknown workflow: {label}"))?;
+
+        let mut total = 0;
+        let mut rest = ranges;
+        for rule in &workflow.rules {
+            let (matching, remaining) = rule.condition.split(rest);
+            total += self.accepted_combinations(rule.target, matching, depth + 1)?;
+            rest = remaining;
+        }
+        total += self.accepted_combinations(workflow.fallback, rest, depth + 1)?;
+        Ok(total)
+    }
+}
+
+pub fn part1(system: &System) -> anyhow::Result<u32> {
+    let mut total = 0;
+    for part in &system.parts {
+        if system.accepts(part)? {
+            total += part.total_rating();
+        }
+    }
+    Ok(total)
+}
+
+///
+/// How many of the combinations of ratings from 1 to 4000 the workflows accept
+pub fn part2(system: &System) -> anyhow::Result<u64> {
+    system.accepted_combinations(Target::Workflow(START_WORKFLOW), PartRanges::all(), 0)
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = System<'a>;
+
+    const TITLE: &'static str = "Aplenty";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        System::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+    #[test]
+    fn test_part1() {
+        let system = System::parse(EXAMPLE).unwrap();
+        let accepted: Vec<bool> = system
+            .parts
+            .iter()
+            .map(|part| system.accepts(part).unwrap())
+            .collect();
+        assert_eq!(accepted, vec![true, false, true, false, true]);
+        assert_eq!(part1(&system).unwrap(), 19114);
+    }
+
+    #[test]
+    fn test_part2() {
+        let system = System::parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&system).unwrap(), 167409079868000);
+
+        let accept_all = System::parse("in{A}").unwrap();
+        assert_eq!(part2(&accept_all).unwrap(), 4000u64.pow(4));
+        let split = System::parse("in{x<2001:A,s>3000:A,R}").unwrap();
+        assert_eq!(
+            part2(&split).unwrap(),
+            2000 * 4000u64.pow(3) + 2000 * 4000 * 4000 * 1000
+        );
+    }
+
+    #[test]
+    fn test_parse_workflow() {
+        let (label, workflow) = Workflow::parse("px{a<2006:qkq,m>2090:A,rfg}").unwrap();
+        assert_eq!(label, "px");
+        assert_eq!(
+            workflow.rules,
+            vec![
+                Rule {
+                    condition: Condition {
+                        category: Category::Aerodynamic,
+                        comparison: Comparison::LessThan,
+                        value: 2006,
+                    },
+                    target: Target::Workflow("qkq"),
+                },
+                Rule {
+                    condition: Condition {
+                        category: Category::Musical,
+                        comparison: Comparison::GreaterThan,
+                        value: 2090,
+                    },
+                    target: Target::Accept,
+                },
+            ]
+        );
+        assert_eq!(workflow.fallback, Target::Workflow("rfg"));
+
+        let (_, workflow) = Workflow::parse("in{R}").unwrap();
+        assert!(workflow.rules.is_empty());
+        assert_eq!(workflow.fallback, Target::Reject);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(Workflow::parse("px{a<2006:qkq,m>2090:A,rfg").is_err());
+        assert!(Workflow::parse("{a<2006:qkq,A}").is_err());
+        assert!(Workflow::parse("px{a=2006:qkq,A}").is_err());
+        assert!(Workflow::parse("px{y<2006:qkq,A}").is_err());
+        assert!(Workflow::parse("px{a<2006,A}").is_err());
+        assert!(Workflow::parse("px{a<2006:qkq,}").is_err());
+        assert!("{x=787,m=2655,a=1222}".parse::<Part>().is_err());
+        assert!("{x=787,m=2655,a=1222,x=1}".parse::<Part>().is_err());
+        assert!("x=787,m=2655,a=1222,s=2876".parse::<Part>().is_err());
+
+        // the workflows have to end up accepting or rejecting every part
+        let looping = System::parse("in{a}\na{in}\n\n{x=1,m=1,a=1,s=1}").unwrap();
+        assert!(part1(&looping).is_err());
+        assert!(part2(&looping).is_err());
+        let unknown = System::parse("in{a}\n\n{x=1,m=1,a=1,s=1}").unwrap();
+        assert!(part1(&unknown).is_err());
+        assert!(part2(&unknown).is_err());
+    }
+}
@@ -1,49 +1,203 @@
-use std::{collections::HashMap, path::Path, str::FromStr};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    str::FromStr,
+    sync::Arc,
+};
 
 use anyhow::Context;
-
-use crate::utils::read_lines;
-
-struct Game {
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::try_parse_input;
+use crate::{
+    solution::{Answer, Solution},
+    utils::{par_parse_lines, parse_lines},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Game {
     id: u32,
     revealed_cubes_list: RevealedCubesList,
+    /// The names of the colors, shared by every game of a game set
+    colors: Arc<ColorNames>,
 }
 
 impl Game {
-    pub fn is_game_posssible(&self, constraints: &HashMap<Color, u32>) -> bool {
-        // A game is possibe if no revealed cubes are above the constraints
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    ///
+    /// The handfuls of cubes revealed during the game, in order
+    pub fn draws(&self) -> &[RevealedCubes] {
+        &self.revealed_cubes_list.revealed_cubes
+    }
+
+    pub fn colors(&self) -> &ColorNames {
+        &self.colors
+    }
+
+    pub fn is_game_posssible(&self, constraints: &CubeConstraints) -> bool {
+        self.is_possible_within(&constraints.limits_by_color(&self.colors))
+    }
+
+    ///
+    /// A game is possible if no revealed cubes are above the limits, indexed by color
+    fn is_possible_within(&self, limits: &[u32]) -> bool {
+        self.revealed_cubes_list.iter().all(|subset| {
+            subset
+                .colors_count
+                .iter()
+                .all(|(color, count)| *count <= limits.get(color.index()).copied().unwrap_or(0))
+        })
+    }
+
+    ///
+    /// The fewest cubes of each color the bag could have held for the game to be possible
+    pub fn minimum_set(&self) -> MinimumCubeSet {
+        let mut counts: HashMap<Color, u32> = HashMap::new();
         for subset in self.revealed_cubes_list.iter() {
-            for (color, constraint_count) in constraints.iter() {
-                match subset.colors_count.get(&color) {
-                    Some(count) => {
-                        if *constraint_count < *count {
-                            return false;
-                        }
-                    }
-                    None => continue,
-                }
+            for (&color, &count) in subset.colors_count.iter() {
+                let fewest = counts.entry(color).or_default();
+                *fewest = count.max(*fewest);
             }
         }
 
-        true
+        MinimumCubeSet {
+            counts,
+            colors: Arc::clone(&self.colors),
+        }
     }
+}
 
-    pub fn get_fewest_for_all_color(&self) -> HashMap<Color, u32> {
-        let mut fewest_for_color: HashMap<Color, u32> = HashMap::new();
+///
+/// How many cubes of each color are in the bag, by color name. Colors missing from the bag have
+/// no cubes.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeConstraints {
+    limits: BTreeMap<String, u32>,
+}
 
-        for subset in self.revealed_cubes_list.iter() {
-            for (color, count) in subset.colors_count.iter() {
-                fewest_for_color
-                    .entry(*color)
-                    .and_modify(|x| *x = *count.max(x))
-                    .or_insert(*count);
-            }
+impl CubeConstraints {
+    pub fn new<'a>(limits: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Self {
+            limits: limits
+                .into_iter()
+                .map(|(name, count)| (name.to_string(), count))
+                .collect(),
+        }
+    }
+
+    pub fn limit(&self, name: &str) -> u32 {
+        self.limits.get(name).copied().unwrap_or(0)
+    }
+
+    ///
+    /// The limit of every color of the game set, indexed by color, so checking a game doesn't
+    /// look up names
+    fn limits_by_color(&self, colors: &ColorNames) -> Vec<u32> {
+        colors.iter().map(|(_, name)| self.limit(name)).collect()
+    }
+}
+
+impl FromStr for CubeConstraints {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 12 red, 13 green, 14 blue
+        let limits: anyhow::Result<Vec<(&str, u32)>> = s
+            .split(",")
+            .map(|single_cube_str| {
+                parse_single_cube_str(single_cube_str)
+                    .with_context(|| format!("invalid cube constraints: {s}"))
+            })
+            .collect();
+        Ok(Self::new(limits?))
+    }
+}
+
+impl Display for CubeConstraints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let limits = self
+            .limits
+            .iter()
+            .map(|(name, count)| format!("{count} {name}"))
+            .join(", ");
+        write!(f, "{limits}")
+    }
+}
+
+///
+/// The fewest cubes of each color which make a game possible, e.g. `{"blue": 6, "red": 4}`
+///
+#[derive(Debug, Clone)]
+pub struct MinimumCubeSet {
+    counts: HashMap<Color, u32>,
+    colors: Arc<ColorNames>,
+}
+
+impl MinimumCubeSet {
+    ///
+    /// How many cubes of the color are needed, 0 if the game had none
+    pub fn count(&self, color: Color) -> u32 {
+        self.counts.get(&color).copied().unwrap_or(0)
+    }
+
+    ///
+    /// The counts of the colors the game had, multiplied together
+    pub fn power(&self) -> u64 {
+        self.counts.values().copied().map(u64::from).product()
+    }
+
+    ///
+    /// The counts by color name, in order of the names
+    fn named(&self) -> BTreeMap<&str, u32> {
+        self.counts
+            .iter()
+            .filter_map(|(&color, &count)| Some((self.colors.name(color)?, count)))
+            .collect()
+    }
+}
+
+// sets from different game sets may number their colors differently, the names are what matter
+impl PartialEq for MinimumCubeSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.named() == other.named()
+    }
+}
+
+impl Eq for MinimumCubeSet {}
+
+impl Serialize for MinimumCubeSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.named().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MinimumCubeSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let named = BTreeMap::<String, u32>::deserialize(deserializer)?;
+        let mut colors = ColorNames::default();
+        let mut counts = HashMap::new();
+        for (name, count) in named {
+            let name = parse_color_name(&name).map_err(D::Error::custom)?;
+            counts.insert(colors.intern(name), count);
         }
 
-        fewest_for_color
+        Ok(Self {
+            counts,
+            colors: Arc::new(colors),
+        })
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
 struct RevealedCubesList {
     revealed_cubes: Vec<RevealedCubes>,
 }
@@ -54,43 +208,131 @@ impl RevealedCubesList {
     }
 }
 
-struct RevealedCubes {
-    pub colors_count: HashMap<Color, u32>,
+///
+/// A handful of cubes revealed from the bag
+///
+#[derive(Debug, PartialEq, Eq)]
+pub struct RevealedCubes {
+    colors_count: HashMap<Color, u32>,
 }
 
+///
+/// A color of cubes, an index into the [`ColorNames`] of its game set so it's as cheap to compare
+/// and hash as an integer. The puzzle's colors have the same index in every game set.
+///
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-enum Color {
-    Red,
-    Green,
-    Blue,
+pub struct Color(u32);
+
+impl Color {
+    pub const RED: Self = Self(0);
+    pub const GREEN: Self = Self(1);
+    pub const BLUE: Self = Self(2);
+
+    const PUZZLE_COLORS: [&'static str; 3] = ["red", "green", "blue"];
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
 }
 
-impl FromStr for Color {
-    type Err = anyhow::Error;
+///
+/// The names of the colors of a game set. The puzzle's colors always exist, any other color is
+/// added the first time it's seen, so the names only live as long as the games do.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ColorNames {
+    /// The names of the colors which aren't the puzzle's, by their index after the puzzle's colors
+    others: Vec<Box<str>>,
+    indices: HashMap<Box<str>, Color>,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let color = match s {
-            "red" => Color::Red,
-            "green" => Color::Green,
-            "blue" => Color::Blue,
-            _ => anyhow::bail!("unknown color: {}", s),
-        };
+impl ColorNames {
+    pub fn get(&self, name: &str) -> Option<Color> {
+        match Color::PUZZLE_COLORS.iter().position(|&other| other == name) {
+            Some(index) => Some(Color(index as u32)),
+            None => self.indices.get(name).copied(),
+        }
+    }
 
-        Ok(color)
+    pub fn name(&self, color: Color) -> Option<&str> {
+        match Color::PUZZLE_COLORS.get(color.index()) {
+            Some(name) => Some(name),
+            None => self
+                .others
+                .get(color.index() - Color::PUZZLE_COLORS.len())
+                .map(|name| &**name),
+        }
+    }
+
+    ///
+    /// Every color with its name, the puzzle's colors first
+    pub fn iter(&self) -> impl Iterator<Item = (Color, &str)> + '_ {
+        Color::PUZZLE_COLORS
+            .iter()
+            .copied()
+            .chain(self.others.iter().map(|name| &**name))
+            .enumerate()
+            .map(|(index, name)| (Color(index as u32), name))
+    }
+
+    ///
+    /// The color with the name, adding the name if it wasn't seen before
+    fn intern(&mut self, name: &str) -> Color {
+        if let Some(color) = self.get(name) {
+            return color;
+        }
+
+        let color = Color((Color::PUZZLE_COLORS.len() + self.others.len()) as u32);
+        self.others.push(name.into());
+        self.indices.insert(name.into(), color);
+        color
     }
 }
 
-impl RevealedCubes {
-    pub fn new() -> Self {
-        Self {
-            colors_count: HashMap::new(),
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut revealed = Vec::new();
+        for subset in self.revealed_cubes_list.iter() {
+            let mut cubes = Vec::new();
+            for (&color, count) in &subset.colors_count {
+                let name = self.colors.name(color).ok_or(std::fmt::Error)?;
+                cubes.push(format!("{count} {name}"));
+            }
+            revealed.push(cubes.join(", "));
         }
+        write!(f, "Game {}: {}", self.id, revealed.join("; "))
+    }
+}
+
+impl RevealedCubes {
+    ///
+    /// How many cubes of the color were revealed, 0 if there were none
+    pub fn count(&self, color: Color) -> u32 {
+        self.colors_count.get(&color).copied().unwrap_or(0)
+    }
+
+    ///
+    /// The colors revealed and how many cubes of each, in no particular order
+    pub fn colors(&self) -> impl Iterator<Item = (Color, u32)> + '_ {
+        self.colors_count
+            .iter()
+            .map(|(&color, &count)| (color, count))
     }
 }
 
 // We don't care about matching on the specific error, and only really care about displaying a trace so using anyhow is good
 
-impl FromStr for Game {
+///
+/// A game parsed on its own, along with the names of the colors it had which aren't the puzzle's
+///
+#[derive(Debug)]
+struct GameLine {
+    id: u32,
+    revealed_cubes_list: RevealedCubesList,
+    colors: ColorNames,
+}
+
+impl FromStr for GameLine {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -100,58 +342,65 @@ impl FromStr for Game {
             .next()
             .with_context(|| format!("missing first part before : for {}", s))?;
         let id = parse_game_id(game).context("failed to parse game id")?;
-        let revealed_cubes_list = it
-            .next()
-            .with_context(|| format!("missing revealed part in: {}", s))?
-            .parse()
-            .context("failed to parse revealed cubes list")?;
-
-        Ok(Game {
+        let mut colors = ColorNames::default();
+        let revealed_cubes_list = parse_revealed_cubes_list(
+            it.next()
+                .with_context(|| format!("missing revealed part in: {}", s))?,
+            &mut colors,
+        )
+        .context("failed to parse revealed cubes list")?;
+
+        Ok(GameLine {
             id,
             revealed_cubes_list,
+            colors,
         })
     }
 }
 
-impl FromStr for RevealedCubesList {
+impl FromStr for Game {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-        let revealed_cubes: anyhow::Result<Vec<RevealedCubes>> = s
-            .split(";")
-            .into_iter()
-            .map(|x| {
-                x.parse()
-                    .with_context(|| format!("failed to parse to revealed cubes: {}", x))
-            })
-            .collect();
-
-        Ok(Self {
-            revealed_cubes: revealed_cubes?,
+        let line: GameLine = s.parse()?;
+        Ok(Game {
+            id: line.id,
+            revealed_cubes_list: line.revealed_cubes_list,
+            colors: Arc::new(line.colors),
         })
     }
 }
 
-impl FromStr for RevealedCubes {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // 3 blue, 4 red
-        let it = s.split(",");
-        let mut revelead_cubes = Self::new();
-
-        for single_cube_str in it {
-            let (color, count) = parse_single_cube_str(single_cube_str)?;
+fn parse_revealed_cubes_list(
+    s: &str,
+    colors: &mut ColorNames,
+) -> anyhow::Result<RevealedCubesList> {
+    // 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    let revealed_cubes: anyhow::Result<Vec<RevealedCubes>> = s
+        .split(";")
+        .map(|x| {
+            parse_revealed_cubes(x, colors)
+                .with_context(|| format!("failed to parse to revealed cubes: {}", x))
+        })
+        .collect();
 
-            revelead_cubes.colors_count.insert(color, count);
-        }
+    Ok(RevealedCubesList {
+        revealed_cubes: revealed_cubes?,
+    })
+}
 
-        Ok(revelead_cubes)
+fn parse_revealed_cubes(s: &str, colors: &mut ColorNames) -> anyhow::Result<RevealedCubes> {
+    // 3 blue, 4 red
+    let mut colors_count = HashMap::new();
+    for single_cube_str in s.split(",") {
+        let (name, count) = parse_single_cube_str(single_cube_str)?;
+        colors_count.insert(colors.intern(name), count);
     }
+
+    Ok(RevealedCubes { colors_count })
 }
 
-fn parse_single_cube_str(single_cube_str: &str) -> anyhow::Result<(Color, u32)> {
+fn parse_single_cube_str(single_cube_str: &str) -> anyhow::Result<(&str, u32)> {
     let mut single_cube_it = single_cube_str.split_whitespace();
     let count = single_cube_it
         .next()
@@ -163,8 +412,15 @@ fn parse_single_cube_str(single_cube_str: &str) -> anyhow::Result<(Color, u32)>
     let count = u32::from_str(count)
         .with_context(|| format!("failed to parse for cube count: {}", count))?;
 
-    let color = color.parse()?;
-    Ok((color, count))
+    Ok((parse_color_name(color)?, count))
+}
+
+fn parse_color_name(s: &str) -> anyhow::Result<&str> {
+    anyhow::ensure!(
+        !s.is_empty() && s.chars().all(char::is_alphabetic),
+        "invalid color: {s}"
+    );
+    Ok(s)
 }
 
 fn parse_game_id(s: &str) -> anyhow::Result<u32> {
@@ -176,50 +432,441 @@ fn parse_game_id(s: &str) -> anyhow::Result<u32> {
     u32::from_str(str_id).with_context(|| format!("failed to parse to u32: {}", str_id))
 }
 
-fn get_games(path: &Path) -> Vec<Game> {
-    let games: anyhow::Result<Vec<Game>> = read_lines(path)
-        .into_iter()
-        .map(|line| line.parse())
-        .collect();
+///
+/// Every game of the record, a line each, along with the names of their colors
+///
+#[derive(Debug, PartialEq, Eq)]
+pub struct GameSet {
+    games: Vec<Game>,
+    colors: Arc<ColorNames>,
+}
+
+impl GameSet {
+    pub fn games(&self) -> &[Game] {
+        &self.games
+    }
 
-    games.unwrap()
+    pub fn colors(&self) -> &ColorNames {
+        &self.colors
+    }
+
+    ///
+    /// Parse the games on every core, for inputs with millions of them
+    pub fn par_parse(s: &str) -> anyhow::Result<Self> {
+        Ok(Self::from_lines(par_parse_lines(s)?))
+    }
+
+    ///
+    /// Gather the colors of the games parsed on their own into the game set's names, renumbering
+    /// the games with colors which aren't the puzzle's
+    fn from_lines(lines: Vec<GameLine>) -> Self {
+        let mut colors = ColorNames::default();
+        let lines: Vec<(u32, RevealedCubesList)> = lines
+            .into_iter()
+            .map(|mut line| {
+                if !line.colors.others.is_empty() {
+                    let renumbered: HashMap<Color, Color> = line
+                        .colors
+                        .iter()
+                        .map(|(color, name)| (color, colors.intern(name)))
+                        .collect();
+                    for subset in &mut line.revealed_cubes_list.revealed_cubes {
+                        subset.colors_count = subset
+                            .colors_count
+                            .drain()
+                            .map(|(color, count)| (renumbered[&color], count))
+                            .collect();
+                    }
+                }
+                (line.id, line.revealed_cubes_list)
+            })
+            .collect();
+
+        let colors = Arc::new(colors);
+        let games = lines
+            .into_iter()
+            .map(|(id, revealed_cubes_list)| Game {
+                id,
+                revealed_cubes_list,
+                colors: Arc::clone(&colors),
+            })
+            .collect();
+
+        Self { games, colors }
+    }
 }
 
-pub fn day2_part1<P: AsRef<Path>>(path: P) -> u32 {
-    let games = get_games(path.as_ref());
-    let constraints: HashMap<Color, u32> =
-        HashMap::from_iter([(Color::Red, 12), (Color::Green, 13), (Color::Blue, 14)]);
+impl FromStr for GameSet {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_lines(parse_lines(s)?))
+    }
+}
 
+fn sum_possible_ids(games: &GameSet, constraints: &CubeConstraints) -> u64 {
+    let limits = constraints.limits_by_color(&games.colors);
     games
+        .games
         .iter()
-        .filter_map(|game| match game.is_game_posssible(&constraints) {
-            true => Some(game.id),
+        .filter_map(|game| match game.is_possible_within(&limits) {
+            true => Some(u64::from(game.id)),
             false => None,
         })
         .sum()
 }
 
-pub fn day2_part2<P: AsRef<Path>>(path: P) -> u32 {
-    let games = get_games(path.as_ref());
+///
+/// The sum of the ids of the games impossible with the cubes in the bag, the complement of
+/// `part1_with_constraints` which is useful for sanity checking part 1
+pub fn sum_impossible_ids(games: &GameSet, constraints: &CubeConstraints) -> u64 {
+    let limits = constraints.limits_by_color(&games.colors);
     games
-        .into_iter()
-        .map(|x| x.get_fewest_for_all_color().into_values().product::<u32>())
+        .games
+        .iter()
+        .filter(|game| !game.is_possible_within(&limits))
+        .map(|game| u64::from(game.id))
+        .sum()
+}
+
+fn get_part1_constraints() -> CubeConstraints {
+    CubeConstraints::new([("red", 12), ("green", 13), ("blue", 14)])
+}
+
+///
+/// The sum of the ids of the games possible with only 12 red, 13 green and 14 blue cubes
+pub fn part1(games: &GameSet) -> u64 {
+    part1_with_constraints(games, &get_part1_constraints())
+}
+
+///
+/// The sum of the ids of the games possible with the cubes in the bag
+pub fn part1_with_constraints(games: &GameSet, constraints: &CubeConstraints) -> u64 {
+    sum_possible_ids(games, constraints)
+}
+
+///
+/// The sum of the powers of the fewest cubes of each color every game is possible with
+pub fn part2(games: &GameSet) -> u64 {
+    games
+        .games
+        .iter()
+        .map(|game| game.minimum_set().power())
         .sum()
 }
 
+///
+/// [`part1_with_constraints`] checking the games on every core
+pub fn par_part1_with_constraints(games: &GameSet, constraints: &CubeConstraints) -> u64 {
+    let limits = constraints.limits_by_color(&games.colors);
+    games
+        .games
+        .par_iter()
+        .filter(|game| game.is_possible_within(&limits))
+        .map(|game| u64::from(game.id))
+        .sum()
+}
+
+///
+/// [`part1`] checking the games on every core
+pub fn par_part1(games: &GameSet) -> u64 {
+    par_part1_with_constraints(games, &get_part1_constraints())
+}
+
+///
+/// [`part2`] finding the minimum sets on every core
+pub fn par_part2(games: &GameSet) -> u64 {
+    games
+        .games
+        .par_iter()
+        .map(|game| game.minimum_set().power())
+        .sum()
+}
+
+///
+/// [`part1`] straight from an input file. A malformed game fails with its line in the error.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn day2_part1<P: AsRef<Path>>(path: P) -> anyhow::Result<u64> {
+    Ok(part1(&try_parse_input(path)?))
+}
+
+///
+/// [`part2`] straight from an input file. A malformed game fails with its line in the error.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn day2_part2<P: AsRef<Path>>(path: P) -> anyhow::Result<u64> {
+    Ok(part2(&try_parse_input(path)?))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = GameSet;
+
+    const TITLE: &'static str = "Cube Conundrum";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{env, fs};
+
+    use proptest::prelude::*;
+
+    use crate::{error::ParseLocation, gen::generate, testing::game_line};
+
     use super::*;
 
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
     #[test]
     fn test_day2_part1() {
         let path = "input/day2/test.txt";
-        assert_eq!(day2_part1(path), 8);
+        assert_eq!(day2_part1(path).unwrap(), 8);
     }
 
     #[test]
     fn test_day2_part2() {
         let path = "input/day2/test.txt";
-        assert_eq!(day2_part2(path), 2286);
+        assert_eq!(day2_part2(path).unwrap(), 2286);
+    }
+
+    #[test]
+    fn test_day2_invalid_file() {
+        let path = env::temp_dir().join(format!("aoc-day2-test-{}.txt", std::process::id()));
+        fs::write(
+            &path,
+            "Game 1: 3 blue, 4 red\nGame 2 3 blue\nGame 3: 1 red\n",
+        )
+        .unwrap();
+        let error = day2_part1(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        let error = format!("{error:#}");
+        assert!(error.contains(&path.display().to_string()), "{error}");
+        assert!(error.contains("line 2: Game 2 3 blue"), "{error}");
+        assert!(day2_part2("input/missing/actual.txt").is_err());
+    }
+
+    #[test]
+    fn test_part1() {
+        let games: GameSet = EXAMPLE.parse().unwrap();
+        assert_eq!(part1(&games), 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        let games: GameSet = EXAMPLE.parse().unwrap();
+        assert_eq!(part2(&games), 2286);
+    }
+
+    #[test]
+    fn test_part1_with_constraints() {
+        let games: GameSet = EXAMPLE.parse().unwrap();
+        let constraints: CubeConstraints = "12 red, 13 green, 14 blue".parse().unwrap();
+        assert_eq!(constraints, get_part1_constraints());
+        assert_eq!(part1_with_constraints(&games, &constraints), 8);
+
+        let constraints: CubeConstraints = " 20 red,13 green, 15 blue".parse().unwrap();
+        assert_eq!(part1_with_constraints(&games, &constraints), 15);
+        // game 2 is the only one with fewer than 4 red cubes
+        let constraints: CubeConstraints = "1 red, 3 green, 4 blue".parse().unwrap();
+        assert_eq!(part1_with_constraints(&games, &constraints), 2);
+        // every game has some green cubes
+        let constraints: CubeConstraints = "100 red, 100 blue".parse().unwrap();
+        assert_eq!(part1_with_constraints(&games, &constraints), 0);
+        assert_eq!(constraints.to_string(), "100 blue, 100 red");
+
+        assert!("12 red, 13 purple!".parse::<CubeConstraints>().is_err());
+        assert!("red".parse::<CubeConstraints>().is_err());
+    }
+
+    #[test]
+    fn test_game_accessors() {
+        let games: GameSet = EXAMPLE.parse().unwrap();
+        let ids: Vec<u32> = games.games().iter().map(Game::id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+
+        let game = &games.games()[2];
+        assert_eq!(game.draws().len(), 3);
+        let red: Vec<u32> = game.draws().iter().map(|x| x.count(Color::RED)).collect();
+        assert_eq!(red, vec![20, 4, 1]);
+        assert_eq!(game.draws()[0].count(Color::BLUE), 6);
+        let mut colors: Vec<(Color, u32)> = game.draws()[2].colors().collect();
+        colors.sort_by_key(|&(_, count)| count);
+        assert_eq!(colors, vec![(Color::RED, 1), (Color::GREEN, 5)]);
+
+        // how many games had each number of draws
+        let draws = games.games().iter().counts_by(|game| game.draws().len());
+        assert_eq!(draws, HashMap::from([(2, 1), (3, 4)]));
+    }
+
+    #[test]
+    fn test_sums_past_u32() {
+        // the ids alone add up to more than a u32 holds
+        let input = generate(2, 100_000, 3).unwrap();
+        let games = GameSet::par_parse(&input).unwrap();
+        let constraints = get_part1_constraints();
+        let ids: u64 = games.games().iter().map(|game| u64::from(game.id())).sum();
+        assert!(ids > u64::from(u32::MAX));
+        assert_eq!(
+            part1(&games) + sum_impossible_ids(&games, &constraints),
+            ids
+        );
+        assert_eq!(par_part1(&games), part1(&games));
+        assert_eq!(par_part2(&games), part2(&games));
+    }
+
+    #[test]
+    fn test_parallel() {
+        let games = GameSet::par_parse(EXAMPLE).unwrap();
+        assert_eq!(games, EXAMPLE.parse().unwrap());
+        assert_eq!(par_part1(&games), 8);
+        assert_eq!(par_part2(&games), 2286);
+
+        let input = generate(2, 2000, 7).unwrap();
+        let games: GameSet = input.parse().unwrap();
+        let par_games = GameSet::par_parse(&input).unwrap();
+        assert_eq!(par_games, games);
+        assert_eq!(par_part1(&par_games), part1(&games));
+        assert_eq!(par_part2(&par_games), part2(&games));
+        let constraints: CubeConstraints = "5 red, 10 green, 15 blue".parse().unwrap();
+        assert_eq!(
+            par_part1_with_constraints(&par_games, &constraints),
+            part1_with_constraints(&games, &constraints)
+        );
+
+        let error = GameSet::par_parse("Game 1: 3 blue\nGame 2: 3 blue\nGame 3 4 red").unwrap_err();
+        assert!(
+            format!("{error:#}").contains("line 3: Game 3 4 red"),
+            "{error:#}"
+        );
+    }
+
+    #[test]
+    fn test_minimum_set() {
+        let games: GameSet = EXAMPLE.parse().unwrap();
+        let minimum = games.games()[0].minimum_set();
+        assert_eq!(minimum.count(Color::RED), 4);
+        assert_eq!(minimum.count(Color::GREEN), 2);
+        assert_eq!(minimum.count(Color::BLUE), 6);
+        assert_eq!(minimum.power(), 48);
+        let powers: Vec<u64> = games
+            .games()
+            .iter()
+            .map(|game| game.minimum_set().power())
+            .collect();
+        assert_eq!(powers, vec![48, 12, 1560, 630, 36]);
+
+        let json = serde_json::to_string(&minimum).unwrap();
+        assert!(json.contains(r#""red":4"#), "{json}");
+        let deserialized: MinimumCubeSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, minimum);
+        assert!(serde_json::from_str::<MinimumCubeSet>(r#"{"red!": 4}"#).is_err());
+    }
+
+    #[test]
+    fn test_other_colors() {
+        let games: GameSet = "Game 1: 3 teal, 2 red; 1 magenta\nGame 2: 7 teal; 2 green"
+            .parse()
+            .unwrap();
+        let teal = games.colors().get("teal").unwrap();
+        assert_ne!(teal, games.colors().get("magenta").unwrap());
+        assert_eq!(games.colors().name(teal), Some("teal"));
+        assert_eq!(games.colors().get("purple"), None);
+        assert_eq!(games.games()[1].draws()[0].count(teal), 7);
+        assert_eq!(games.games()[0].to_string().matches("teal").count(), 1);
+
+        let constraints: CubeConstraints = "5 teal, 1 magenta, 2 red, 2 green".parse().unwrap();
+        assert_eq!(part1_with_constraints(&games, &constraints), 1);
+        assert!(games.games()[0].is_game_posssible(&constraints));
+        assert_eq!(constraints.to_string(), "2 green, 1 magenta, 2 red, 5 teal");
+        assert_eq!(part1(&games), 0);
+        assert_eq!(part2(&games), 3 * 2 + 7 * 2);
+
+        // every game set names its own colors
+        let other: GameSet = "Game 1: 1 violet, 1 teal".parse().unwrap();
+        let violet = other.colors().get("violet").unwrap();
+        assert_eq!(violet, teal);
+        assert_eq!(other.colors().get("magenta"), None);
+        assert_eq!(
+            other.games()[0].minimum_set(),
+            "Game 9: 1 teal; 1 violet"
+                .parse::<Game>()
+                .unwrap()
+                .minimum_set()
+        );
+
+        let input = (1..=200)
+            .map(|id| {
+                format!(
+                    "Game {id}: {id} color{}, 1 red",
+                    char::from(b'a' + (id % 7) as u8)
+                )
+            })
+            .join("\n");
+        let games: GameSet = input.parse().unwrap();
+        assert_eq!(games.colors().iter().count(), 3 + 7);
+        assert_eq!(GameSet::par_parse(&input).unwrap(), games);
+        let colord = games.colors().get("colord").unwrap();
+        assert_eq!(games.games()[9].draws()[0].count(colord), 10);
+    }
+
+    #[test]
+    fn test_invalid_game_set() {
+        let error = "Game 1: 3 blue\nGame 2: 3 purple!"
+            .parse::<GameSet>()
+            .unwrap_err();
+        assert!(
+            format!("{error:#}").contains("line 2: Game 2: 3 purple!"),
+            "{error:#}"
+        );
+        assert_eq!(
+            error.downcast_ref::<ParseLocation>().map(|x| x.line),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_sum_impossible_ids() {
+        let games: GameSet = EXAMPLE.parse().unwrap();
+        let constraints = get_part1_constraints();
+
+        let possible = part1_with_constraints(&games, &constraints);
+        let impossible = sum_impossible_ids(&games, &constraints);
+        assert_eq!(possible, 8);
+        assert_eq!(impossible, 7);
+        assert_eq!(
+            possible + impossible,
+            games
+                .games()
+                .iter()
+                .map(|game| u64::from(game.id()))
+                .sum::<u64>()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_game_display_roundtrips(line in game_line()) {
+            let game: Game = line.parse().unwrap();
+            let reparsed: Game = game.to_string().parse().unwrap();
+            prop_assert_eq!(reparsed, game);
+        }
     }
 }
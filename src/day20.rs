@@ -0,0 +1,369 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::math::lcm,
+};
+
+const BROADCASTER: &str = "broadcaster";
+
+const BUTTON_PRESSES: usize = 1000;
+
+const RX: &str = "rx";
+
+/// How many presses to wait for every input of the conjunction feeding `rx` to send a high pulse
+const MAX_CYCLE_PRESSES: u64 = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModuleKind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// Only ever a destination, e.g. `output`, so it ignores its pulses
+    Untyped,
+}
+
+///
+/// Where a module sends its pulses: the destination module and which of its inputs the pulse
+/// arrives at, so a conjunction knows what to remember
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Connection {
+    module: usize,
+    input: usize,
+}
+
+#[derive(Debug)]
+struct Module<'a> {
+    name: &'a str,
+    kind: ModuleKind,
+    destinations: Vec<Connection>,
+    inputs: Vec<usize>,
+}
+
+///
+/// The modules and how they're wired, with their names borrowed from the input. Modules are
+/// referred to by their index everywhere else.
+///
+#[derive(Debug)]
+pub struct Network<'a> {
+    modules: Vec<Module<'a>>,
+    broadcaster: usize,
+}
+
+///
+/// A pulse on its way from a module to one of its destinations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pulse {
+    pub from: usize,
+    pub to: usize,
+    input: usize,
+    pub high: bool,
+}
+
+///
+/// Whether every flip-flop is on, and the last pulse every conjunction got from each of its inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkState {
+    flip_flops: Vec<bool>,
+    memory: Vec<Vec<bool>>,
+}
+
+impl<'a> Network<'a> {
+    pub fn parse(s: &'a str) -> anyhow::Result<Self> {
+        let mut modules: Vec<Module> = Vec::new();
+        let mut indices: HashMap<&str, usize> = HashMap::new();
+        let mut index_of = |modules: &mut Vec<Module<'a>>, name: &'a str| {
+            *indices.entry(name).or_insert_with(|| {
+                modules.push(Module {
+                    name,
+                    kind: ModuleKind::Untyped,
+                    destinations: Vec::new(),
+                    inputs: Vec::new(),
+                });
+                modules.len() - 1
+            })
+        };
+
+        for line in s.lines().filter(|line| !line.is_empty()) {
+            // %a -> inv, con
+            let (module, destinations) = line
+                .split_once(" -> ")
+                .with_context(|| format!("missing destinations: {line}"))?;
+            let (kind, name) = match module.strip_prefix('%') {
+                Some(name) => (ModuleKind::FlipFlop, name),
+                None => match module.strip_prefix('&') {
+                    Some(name) => (ModuleKind::Conjunction, name),
+                    None if module == BROADCASTER => (ModuleKind::Broadcaster, module),
+                    None => anyhow::bail!("invalid module: {module}"),
+                },
+            };
+            anyhow::ensure!(!name.is_empty(), "missing module name: {line}");
+
+            let index = index_of(&mut modules, name);
+            anyhow::ensure!(
+                modules[index].kind == ModuleKind::Untyped,
+                "duplicate module: {name}"
+            );
+            modules[index].kind = kind;
+            for destination in destinations.split(", ") {
+                anyhow::ensure!(!destination.is_empty(), "missing destination: {line}");
+                let module = index_of(&mut modules, destination);
+                let input = modules[module].inputs.len();
+                modules[module].inputs.push(index);
+                modules[index]
+                    .destinations
+                    .push(Connection { module, input });
+            }
+        }
+
+        let broadcaster = modules
+            .iter()
+            .position(|module| module.kind == ModuleKind::Broadcaster)
+            .context("missing broadcaster")?;
+        Ok(Self {
+            modules,
+            broadcaster,
+        })
+    }
+
+    pub fn module_name(&self, index: usize) -> Option<&'a str> {
+        self.modules.get(index).map(|module| module.name)
+    }
+
+    fn module_index(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|module| module.name == name)
+    }
+
+    pub fn initial_state(&self) -> NetworkState {
+        NetworkState {
+            flip_flops: vec![false; self.modules.len()],
+            memory: self
+                .modules
+                .iter()
+                .map(|module| vec![false; module.inputs.len()])
+                .collect(),
+        }
+    }
+
+    ///
+    /// Send a low pulse to the broadcaster and handle pulses in the order they're sent until
+    /// there are none left, calling `on_pulse` for each of them, the button's included
+    pub fn press_button(&self, state: &mut NetworkState, mut on_pulse: impl FnMut(&Pulse)) {
+        let mut queue = VecDeque::from([Pulse {
+            from: self.broadcaster,
+            to: self.broadcaster,
+            input: 0,
+            high: false,
+        }]);
+
+        while let Some(pulse) = queue.pop_front() {
+            on_pulse(&pulse);
+            let module = &self.modules[pulse.to];
+            let output = match module.kind {
+                ModuleKind::Broadcaster => pulse.high,
+                // a flip-flop ignores high pulses and toggles on low ones
+                ModuleKind::FlipFlop if pulse.high => continue,
+                ModuleKind::FlipFlop => {
+                    let on = &mut state.flip_flops[pulse.to];
+                    *on = !*on;
+                    *on
+                }
+                ModuleKind::Conjunction => {
+                    let memory = &mut state.memory[pulse.to];
+                    memory[pulse.input] = pulse.high;
+                    !memory.iter().all(|&high| high)
+                }
+                ModuleKind::Untyped => continue,
+            };
+
+            queue.extend(module.destinations.iter().map(|connection| Pulse {
+                from: pulse.to,
+                to: connection.module,
+                input: connection.input,
+                high: output,
+            }));
+        }
+    }
+}
+
+///
+/// The number of low pulses times the number of high pulses sent over the presses
+pub fn pulses_product(network: &Network, presses: usize) -> u64 {
+    let mut state = network.initial_state();
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..presses {
+        network.press_button(&mut state, |pulse| {
+            if pulse.high {
+                high += 1;
+            } else {
+                low += 1;
+            }
+        });
+    }
+    low * high
+}
+
+pub fn part1(network: &Network) -> u64 {
+    pulses_product(network, BUTTON_PRESSES)
+}
+
+///
+/// The number of presses until `rx` gets a low pulse. `rx` is fed by a single conjunction, which
+/// only sends a low pulse once all of its inputs sent it a high one during the same press. Each of
+/// the inputs is the end of a separate counter sending a high pulse every so many presses, so
+/// they all line up at the least common multiple of the presses each first sends one at.
+///
+pub fn part2(network: &Network) -> anyhow::Result<u64> {
+    let rx = network.module_index(RX).context("missing rx module")?;
+    let [feeder] = network.modules[rx].inputs[..] else {
+        anyhow::bail!("rx isn't fed by exactly one module");
+    };
+    anyhow::ensure!(
+        network.modules[feeder].kind == ModuleKind::Conjunction,
+        "rx isn't fed by a conjunction"
+    );
+
+    let inputs = &network.modules[feeder].inputs;
+    let mut cycles: Vec<Option<u64>> = vec![None; inputs.len()];
+    let mut state = network.initial_state();
+    for presses in 1..=MAX_CYCLE_PRESSES {
+        network.press_button(&mut state, |pulse| {
+            if pulse.high && pulse.to == feeder {
+                cycles[pulse.input].get_or_insert(presses);
+            }
+        });
+
+        if cycles.iter().all(Option::is_some) {
+            return Ok(cycles.into_iter().flatten().fold(1, lcm));
+        }
+    }
+
+    anyhow::bail!(
+        "the inputs feeding rx didn't all send a high pulse in {MAX_CYCLE_PRESSES} presses"
+    )
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Network<'a>;
+
+    const TITLE: &'static str = "Pulse Propagation";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Network::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+
+    const EXAMPLE_WITH_OUTPUT: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+    #[test]
+    fn test_part1() {
+        let network = Network::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&network), 32000000);
+
+        let network = Network::parse(EXAMPLE_WITH_OUTPUT).unwrap();
+        assert_eq!(part1(&network), 11687500);
+    }
+
+    #[test]
+    fn test_press_button() {
+        let network = Network::parse(EXAMPLE_WITH_OUTPUT).unwrap();
+        let mut state = network.initial_state();
+        let name = |index| network.module_name(index).unwrap();
+
+        let mut pulses = Vec::new();
+        network.press_button(&mut state, |pulse| {
+            pulses.push((name(pulse.from), pulse.high, name(pulse.to)));
+        });
+        assert_eq!(
+            pulses,
+            vec![
+                ("broadcaster", false, "broadcaster"),
+                ("broadcaster", false, "a"),
+                ("a", true, "inv"),
+                ("a", true, "con"),
+                ("inv", false, "b"),
+                ("con", true, "output"),
+                ("b", true, "con"),
+                ("con", false, "output"),
+            ]
+        );
+
+        // after four presses every module is back where it started
+        for _ in 0..3 {
+            network.press_button(&mut state, |_| {});
+        }
+        assert_eq!(state, network.initial_state());
+    }
+
+    #[test]
+    fn test_part2() {
+        // two counters feeding rx through a conjunction, each sending a high pulse every few
+        // presses
+        let network = Network::parse(
+            "broadcaster -> a, c
+%a -> ka
+&ka -> feed
+%c -> d
+%d -> kd
+&kd -> feed
+&feed -> rx
+",
+        )
+        .unwrap();
+
+        let rx = network.module_index(RX).unwrap();
+        let mut state = network.initial_state();
+        let mut presses = 0;
+        let mut low_to_rx = false;
+        while !low_to_rx {
+            presses += 1;
+            network.press_button(&mut state, |pulse| {
+                low_to_rx |= pulse.to == rx && !pulse.high;
+            });
+        }
+        assert_eq!(part2(&network).unwrap(), presses);
+
+        // without rx there's nothing to wait for
+        let network = Network::parse(EXAMPLE).unwrap();
+        assert!(part2(&network).is_err());
+        let network = Network::parse("broadcaster -> a\n%a -> rx").unwrap();
+        assert!(part2(&network).is_err());
+    }
+
+    #[test]
+    fn test_invalid_network() {
+        assert!(Network::parse("%a -> b").is_err());
+        assert!(Network::parse("broadcaster -> a\n%a").is_err());
+        assert!(Network::parse("broadcaster -> a\n$a -> b").is_err());
+        assert!(Network::parse("broadcaster -> a\n%a -> b\n&a -> b").is_err());
+        assert!(Network::parse("broadcaster -> a\n% -> b").is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::{grid::Grid, search::bfs},
+};
+
+const STEPS_PART1: usize = 64;
+
+const STEPS_PART2: usize = 26_501_365;
+
+/// How many copies of the garden to walk before giving up on the count growing quadratically
+const MAX_SAMPLE_CYCLES: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Tile {
+    Plot,
+    Rock,
+}
+
+#[derive(Debug)]
+pub struct Garden {
+    grid: Grid<Tile>,
+    /// The (row, column) of the plot the elf starts on
+    start: (usize, usize),
+}
+
+impl FromStr for Garden {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_chars(s, |c| match c {
+            '.' | 'S' => Ok(Tile::Plot),
+            '#' => Ok(Tile::Rock),
+            _ => anyhow::bail!("invalid garden tile: {c}"),
+        })
+        .context("failed to parse garden")?;
+
+        // every tile is a single byte once the grid parsed
+        let start = s
+            .lines()
+            .enumerate()
+            .find_map(|(row, line)| line.find('S').map(|column| (row, column)))
+            .context("missing starting position")?;
+        anyhow::ensure!(
+            s.matches('S').count() == 1,
+            "more than one starting position"
+        );
+
+        Ok(Self { grid, start })
+    }
+}
+
+impl Garden {
+    fn plot_neighbours(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.grid
+            .neighbours(row, column)
+            .filter(|&(row, column)| self.grid.get(row, column) == Some(&Tile::Plot))
+    }
+
+    ///
+    /// The plots the elf can end up on after exactly `steps` steps. A plot reached in fewer steps
+    /// can still be ended on by stepping back and forth, as long as the number of steps left over
+    /// is even, so it's the plots at most `steps` away with the same parity.
+    pub fn reachable_plots(&self, steps: usize) -> usize {
+        bfs(
+            [self.start],
+            |&(row, column)| self.plot_neighbours(row, column),
+            steps,
+        )
+        .into_values()
+        .filter(|distance| distance % 2 == steps % 2)
+        .count()
+    }
+
+    fn is_plot_tiled(&self, row: i64, column: i64) -> bool {
+        let num_rows = self.grid.num_rows() as i64;
+        let num_columns = self.grid.num_columns() as i64;
+        let row = row.rem_euclid(num_rows) as usize;
+        let column = column.rem_euclid(num_columns) as usize;
+        self.grid.get(row, column) == Some(&Tile::Plot)
+    }
+
+    ///
+    /// Like [`Garden::reachable_plots`], but with the garden repeating infinitely in every
+    /// direction. Only the original copy has a starting plot.
+    pub fn reachable_plots_tiled(&self, steps: usize) -> usize {
+        if self.grid.is_empty() {
+            return 0;
+        }
+        let start = (self.start.0 as i64, self.start.1 as i64);
+        bfs(
+            [start],
+            |&(row, column)| {
+                [
+                    (row - 1, column),
+                    (row + 1, column),
+                    (row, column - 1),
+                    (row, column + 1),
+                ]
+                .into_iter()
+                .filter(|&(row, column)| self.is_plot_tiled(row, column))
+            },
+            steps,
+        )
+        .into_values()
+        .filter(|distance| distance % 2 == steps % 2)
+        .count()
+    }
+
+    ///
+    /// The tiled reachable plots for a huge number of steps. Once the reachable area spans a few
+    /// copies of the garden, it grows by the same pattern of copies every `size` steps, so the
+    /// counts `size` steps apart grow quadratically. They're sampled at the same remainder as
+    /// `steps` until the second difference settles, and the quadratic is carried on from there.
+    pub fn extrapolate_reachable_plots(&self, steps: usize) -> anyhow::Result<u64> {
+        let size = self.grid.num_rows();
+        anyhow::ensure!(
+            size > 0 && size == self.grid.num_columns(),
+            "the garden isn't square"
+        );
+
+        let remainder = steps % size;
+        let cycles = steps / size;
+        let mut samples: Vec<i128> = Vec::new();
+        for cycle in 0..=cycles.min(MAX_SAMPLE_CYCLES) {
+            samples.push(self.reachable_plots_tiled(remainder + cycle * size) as i128);
+            if cycle == cycles {
+                return Ok(samples[cycle] as u64);
+            }
+
+            let [.., a, b, c, d] = samples[..] else {
+                continue;
+            };
+            let second_difference = d - 2 * c + b;
+            if second_difference == c - 2 * b + a {
+                // the first difference grows by the second difference every cycle
+                let t = (cycles - cycle) as i128;
+                let plots = d + t * (d - c) + t * (t + 1) / 2 * second_difference;
+                return plots
+                    .try_into()
+                    .with_context(|| format!("invalid number of reachable plots: {plots}"));
+            }
+        }
+
+        anyhow::bail!(
+            "the reachable plots didn't settle into a quadratic in {MAX_SAMPLE_CYCLES} cycles"
+        )
+    }
+}
+
+pub fn part1(garden: &Garden) -> usize {
+    garden.reachable_plots(STEPS_PART1)
+}
+
+pub fn part2(garden: &Garden) -> anyhow::Result<u64> {
+    garden.extrapolate_reachable_plots(STEPS_PART2)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Garden;
+
+    const TITLE: &'static str = "Step Counter";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    #[test]
+    fn test_reachable_plots() {
+        let garden: Garden = EXAMPLE.parse().unwrap();
+        assert_eq!(garden.start, (5, 5));
+        assert_eq!(garden.reachable_plots(0), 1);
+        assert_eq!(garden.reachable_plots(1), 2);
+        assert_eq!(garden.reachable_plots(2), 4);
+        assert_eq!(garden.reachable_plots(3), 6);
+        assert_eq!(garden.reachable_plots(6), 16);
+    }
+
+    #[test]
+    fn test_reachable_plots_tiled() {
+        let garden: Garden = EXAMPLE.parse().unwrap();
+        for (steps, plots) in [(6, 16), (10, 50), (50, 1594), (100, 6536)] {
+            assert_eq!(garden.reachable_plots_tiled(steps), plots, "{steps} steps");
+        }
+    }
+
+    #[test]
+    fn test_extrapolate_reachable_plots() {
+        let garden: Garden = EXAMPLE.parse().unwrap();
+        for (steps, plots) in [
+            (6, 16),
+            (10, 50),
+            (50, 1594),
+            (100, 6536),
+            (500, 167004),
+            (1000, 668697),
+            (5000, 16733044),
+        ] {
+            assert_eq!(
+                garden.extrapolate_reachable_plots(steps).unwrap(),
+                plots,
+                "{steps} steps"
+            );
+        }
+
+        let narrow: Garden = "S..\n...".parse().unwrap();
+        assert!(narrow.extrapolate_reachable_plots(STEPS_PART2).is_err());
+    }
+
+    #[test]
+    fn test_invalid_garden() {
+        assert!("...\n.#.\n...".parse::<Garden>().is_err());
+        assert!("S..\n.#.\n..S".parse::<Garden>().is_err());
+        assert!("S..\n.x.\n...".parse::<Garden>().is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::parse_from_lines,
+};
+
+///
+/// A brick spanning from `start` to `end` inclusive, with every coordinate of `start` at most the
+/// one of `end`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brick {
+    start: [u32; 3],
+    end: [u32; 3],
+}
+
+fn parse_position(s: &str) -> anyhow::Result<[u32; 3]> {
+    let mut coordinates = s.split(',').map(|x| {
+        x.parse()
+            .with_context(|| format!("failed to parse coordinate: {x}"))
+    });
+    let mut position = [0; 3];
+    for coordinate in &mut position {
+        *coordinate = coordinates
+            .next()
+            .with_context(|| format!("missing coordinate: {s}"))??;
+    }
+    anyhow::ensure!(coordinates.next().is_none(), "too many coordinates: {s}");
+    Ok(position)
+}
+
+impl FromStr for Brick {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 1,0,1~1,2,1
+        let (start, end) = s
+            .split_once('~')
+            .with_context(|| format!("invalid brick: {s}"))?;
+        let (start, end) = (parse_position(start)?, parse_position(end)?);
+
+        Ok(Self {
+            start: std::array::from_fn(|axis| start[axis].min(end[axis])),
+            end: std::array::from_fn(|axis| start[axis].max(end[axis])),
+        })
+    }
+}
+
+impl Brick {
+    fn bottom(&self) -> u32 {
+        self.start[2]
+    }
+
+    fn height(&self) -> u32 {
+        self.end[2] - self.start[2] + 1
+    }
+
+    ///
+    /// The (x, y) of every cube of the brick seen from above
+    fn footprint(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        (self.start[0]..=self.end[0])
+            .flat_map(move |x| (self.start[1]..=self.end[1]).map(move |y| (x, y)))
+    }
+}
+
+///
+/// Which bricks rest on which once they all settled, by the bricks' indices in the snapshot
+///
+#[derive(Debug)]
+pub struct SettledBricks {
+    /// The bricks directly on top of each brick
+    supports: Vec<Vec<usize>>,
+    /// The bricks each brick rests directly on
+    supported_by: Vec<Vec<usize>>,
+}
+
+impl SettledBricks {
+    ///
+    /// Let the bricks fall, lowest first, until each lands on the ground or on the highest brick
+    /// under it. Every brick under it at that height supports it.
+    pub fn settle(bricks: &[Brick]) -> Self {
+        let mut order: Vec<usize> = (0..bricks.len()).collect();
+        order.sort_unstable_by_key(|&index| bricks[index].bottom());
+
+        // the top of the highest brick over every (x, y), and which brick that is
+        let mut heights: HashMap<(u32, u32), (u32, usize)> = HashMap::new();
+        let mut supports = vec![Vec::new(); bricks.len()];
+        let mut supported_by = vec![Vec::new(); bricks.len()];
+        for index in order {
+            let brick = &bricks[index];
+            let below: Vec<(u32, usize)> = brick
+                .footprint()
+                .filter_map(|cell| heights.get(&cell).copied())
+                .collect();
+            let landing = below.iter().map(|&(top, _)| top).max().unwrap_or(0);
+
+            let mut supporters: Vec<usize> = below
+                .into_iter()
+                .filter(|&(top, _)| top == landing)
+                .map(|(_, supporter)| supporter)
+                .collect();
+            supporters.sort_unstable();
+            supporters.dedup();
+            for &supporter in &supporters {
+                supports[supporter].push(index);
+            }
+            supported_by[index] = supporters;
+
+            let top = landing + brick.height();
+            for cell in brick.footprint() {
+                heights.insert(cell, (top, index));
+            }
+        }
+
+        Self {
+            supports,
+            supported_by,
+        }
+    }
+
+    ///
+    /// A brick can be disintegrated safely if every brick on it rests on some other brick too
+    pub fn can_disintegrate(&self, brick: usize) -> bool {
+        self.supports[brick]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    ///
+    /// How many other bricks fall if the brick is disintegrated. A brick falls once the last of
+    /// the bricks it rests on fell, so counting the fallen supporters of every brick is enough.
+    pub fn chain_reaction(&self, brick: usize) -> usize {
+        let mut fallen_supporters = vec![0; self.supports.len()];
+        let mut falling = VecDeque::from([brick]);
+        let mut fallen = 0;
+        while let Some(brick) = falling.pop_front() {
+            for &above in &self.supports[brick] {
+                fallen_supporters[above] += 1;
+                if fallen_supporters[above] == self.supported_by[above].len() {
+                    fallen += 1;
+                    falling.push_back(above);
+                }
+            }
+        }
+        fallen
+    }
+}
+
+pub fn part1(bricks: &[Brick]) -> usize {
+    let settled = SettledBricks::settle(bricks);
+    (0..bricks.len())
+        .filter(|&brick| settled.can_disintegrate(brick))
+        .count()
+}
+
+pub fn part2(bricks: &[Brick]) -> usize {
+    let settled = SettledBricks::settle(bricks);
+    (0..bricks.len())
+        .map(|brick| settled.chain_reaction(brick))
+        .sum()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = Vec<Brick>;
+
+    const TITLE: &'static str = "Sand Slabs";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        parse_from_lines(input.lines())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn test_part1() {
+        let bricks = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&bricks), 5);
+    }
+
+    #[test]
+    fn test_part2() {
+        let bricks = Day22::parse(EXAMPLE).unwrap();
+        let settled = SettledBricks::settle(&bricks);
+        let falls: Vec<usize> = (0..bricks.len())
+            .map(|brick| settled.chain_reaction(brick))
+            .collect();
+        assert_eq!(falls, vec![6, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(part2(&bricks), 7);
+    }
+
+    #[test]
+    fn test_settle() {
+        let bricks = Day22::parse(EXAMPLE).unwrap();
+        let settled = SettledBricks::settle(&bricks);
+        assert_eq!(settled.supported_by[0], Vec::<usize>::new());
+        assert_eq!(settled.supports[0], vec![1, 2]);
+        assert_eq!(settled.supported_by[3], vec![1, 2]);
+        assert_eq!(settled.supported_by[5], vec![3, 4]);
+        assert_eq!(settled.supported_by[6], vec![5]);
+        assert_eq!(settled.supports[6], Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_invalid_brick() {
+        let brick: Brick = "2,2,2~0,2,1".parse().unwrap();
+        assert_eq!(brick.start, [0, 2, 1]);
+        assert_eq!(brick.end, [2, 2, 2]);
+        assert!("1,0,1".parse::<Brick>().is_err());
+        assert!("1,0~1,2,1".parse::<Brick>().is_err());
+        assert!("1,0,1,1~1,2,1".parse::<Brick>().is_err());
+        assert!("1,a,1~1,2,1".parse::<Brick>().is_err());
+    }
+}
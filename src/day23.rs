@@ -0,0 +1,311 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::{
+        geom::{Direction, Point},
+        grid::Grid,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Tile {
+    Path,
+    Forest,
+    /// A steep slope which can only be walked down, in its direction
+    Slope(Direction),
+}
+
+impl TryFrom<char> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Path),
+            '#' => Ok(Self::Forest),
+            '^' => Ok(Self::Slope(Direction::North)),
+            'v' => Ok(Self::Slope(Direction::South)),
+            '<' => Ok(Self::Slope(Direction::West)),
+            '>' => Ok(Self::Slope(Direction::East)),
+            _ => anyhow::bail!("invalid trail tile: {value}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TrailMap {
+    grid: Grid<Tile>,
+    /// The only path tile in the top row
+    start: Point,
+    /// The only path tile in the bottom row
+    end: Point,
+}
+
+impl FromStr for TrailMap {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_chars(s, Tile::try_from).context("failed to parse trail map")?;
+        anyhow::ensure!(!grid.is_empty(), "empty trail map");
+
+        let path_in_row = |row: usize| {
+            grid.row(row)
+                .and_then(|tiles| tiles.iter().position(|&tile| tile == Tile::Path))
+                .map(|column| Point::new(column, row))
+        };
+        let start = path_in_row(0).context("missing path in the top row")?;
+        let end = path_in_row(grid.num_rows() - 1).context("missing path in the bottom row")?;
+
+        Ok(Self { grid, start, end })
+    }
+}
+
+///
+/// The trail map boiled down to the points where paths meet, along with the start and the end,
+/// and the lengths of the paths between them
+///
+#[derive(Debug)]
+struct JunctionGraph {
+    /// The junctions each junction leads to, and how many steps away they are
+    edges: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
+    /// The junction leading to the end, if it's the only one
+    before_end: Option<usize>,
+}
+
+impl TrailMap {
+    fn tile(&self, point: Point) -> Tile {
+        self.grid
+            .get(point.y, point.x)
+            .copied()
+            .unwrap_or(Tile::Forest)
+    }
+
+    ///
+    /// The tile one step away in the direction, unless it's forest, or a slope would have to be
+    /// walked up to get there
+    fn step(&self, point: Point, direction: Direction, slippery: bool) -> Option<Point> {
+        let next = point.step_within(direction, self.grid.num_columns(), self.grid.num_rows())?;
+        let against_slope = |tile| matches!(tile, Tile::Slope(slope) if slope != direction);
+        match self.tile(next) {
+            Tile::Forest => None,
+            _ if slippery
+                && (against_slope(self.tile(point)) || against_slope(self.tile(next))) =>
+            {
+                None
+            }
+            _ => Some(next),
+        }
+    }
+
+    fn is_junction(&self, point: Point) -> bool {
+        point == self.start
+            || point == self.end
+            || Direction::ALL
+                .into_iter()
+                .filter(|&direction| self.step(point, direction, false).is_some())
+                .count()
+                > 2
+    }
+
+    ///
+    /// Follow the path leaving the junction in the direction until it reaches another junction,
+    /// or `None` if it's a dead end or goes up a slope
+    fn follow_path(
+        &self,
+        junction: Point,
+        direction: Direction,
+        slippery: bool,
+    ) -> Option<(Point, usize)> {
+        let mut point = self.step(junction, direction, slippery)?;
+        let mut direction = direction;
+        let mut steps = 1;
+        while !self.is_junction(point) {
+            // the only way on along a path is the one which doesn't turn back
+            (point, direction) = [direction, direction.turn_left(), direction.turn_right()]
+                .into_iter()
+                .find_map(|next| Some((self.step(point, next, slippery)?, next)))?;
+            steps += 1;
+        }
+        Some((point, steps))
+    }
+
+    fn junction_graph(&self, slippery: bool) -> JunctionGraph {
+        let junctions: Vec<Point> = self
+            .grid
+            .iter()
+            .map(|((row, column), _)| Point::new(column, row))
+            .filter(|&point| self.tile(point) != Tile::Forest && self.is_junction(point))
+            .collect();
+        let indices: HashMap<Point, usize> = junctions
+            .iter()
+            .enumerate()
+            .map(|(index, &point)| (point, index))
+            .collect();
+
+        let edges: Vec<Vec<(usize, usize)>> = junctions
+            .iter()
+            .map(|&junction| {
+                Direction::ALL
+                    .into_iter()
+                    .filter_map(|direction| self.follow_path(junction, direction, slippery))
+                    .map(|(point, steps)| (indices[&point], steps))
+                    .collect()
+            })
+            .collect();
+
+        let end = indices[&self.end];
+        let mut before_end = edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edges)| edges.iter().any(|&(next, _)| next == end))
+            .map(|(junction, _)| junction);
+        let before_end = match (before_end.next(), before_end.next()) {
+            (Some(junction), None) => Some(junction),
+            _ => None,
+        };
+
+        JunctionGraph {
+            edges,
+            start: indices[&self.start],
+            end,
+            before_end,
+        }
+    }
+
+    ///
+    /// The most steps a hike from the start to the end can take without stepping on a tile twice.
+    /// `slippery` slopes can only be walked down.
+    pub fn longest_hike(&self, slippery: bool) -> anyhow::Result<usize> {
+        let graph = self.junction_graph(slippery);
+        anyhow::ensure!(
+            graph.edges.len() <= u64::BITS as usize,
+            "too many junctions to search: {}",
+            graph.edges.len()
+        );
+        graph
+            .longest_path(graph.start, 0)
+            .context("the end of the trail is unreachable")
+    }
+}
+
+impl JunctionGraph {
+    ///
+    /// The longest path from the junction to the end which avoids the `visited` junctions, a bit
+    /// per junction, by trying every one of them. That's exponential, but there are only a few
+    /// dozen junctions once the paths between them are contracted.
+    fn longest_path(&self, junction: usize, visited: u64) -> Option<usize> {
+        if junction == self.end {
+            return Some(0);
+        }
+
+        let visited = visited | 1 << junction;
+        let mut longest = None;
+        for &(next, steps) in &self.edges[junction] {
+            if next == self.end && self.before_end == Some(junction) {
+                // leaving the only junction before the end any other way would cut the end off
+                return Some(steps);
+            }
+            if visited & 1 << next != 0 {
+                continue;
+            }
+            if let Some(path) = self.longest_path(next, visited) {
+                longest = longest.max(Some(path + steps));
+            }
+        }
+        longest
+    }
+}
+
+pub fn part1(map: &TrailMap) -> anyhow::Result<usize> {
+    map.longest_hike(true)
+}
+
+pub fn part2(map: &TrailMap) -> anyhow::Result<usize> {
+    map.longest_hike(false)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = TrailMap;
+
+    const TITLE: &'static str = "A Long Walk";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
+
+    #[test]
+    fn test_part1() {
+        let map: TrailMap = EXAMPLE.parse().unwrap();
+        assert_eq!(map.start, Point::new(1, 0));
+        assert_eq!(map.end, Point::new(21, 22));
+        assert_eq!(part1(&map).unwrap(), 94);
+    }
+
+    #[test]
+    fn test_part2() {
+        let map: TrailMap = EXAMPLE.parse().unwrap();
+        assert_eq!(part2(&map).unwrap(), 154);
+    }
+
+    #[test]
+    fn test_slopes() {
+        // the only way to the end is up a slope
+        let map: TrailMap = "#.#\n#^#\n#.#".parse().unwrap();
+        assert!(part1(&map).is_err());
+        let map: TrailMap = "#.#\n#v#\n#.#".parse().unwrap();
+        assert_eq!(part1(&map).unwrap(), 2);
+        let map: TrailMap = "#.#\n#^#\n#.#".parse().unwrap();
+        assert_eq!(part2(&map).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert!("#.#\n#x#\n#.#".parse::<TrailMap>().is_err());
+        assert!("###\n#.#\n#.#".parse::<TrailMap>().is_err());
+        assert!("".parse::<TrailMap>().is_err());
+    }
+}
@@ -0,0 +1,347 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+use anyhow::Context;
+
+use crate::{
+    solution::{Answer, Solution},
+    utils::parse_from_lines,
+};
+
+const TEST_AREA: RangeInclusive<i64> = 200_000_000_000_000..=400_000_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    position: [i64; 3],
+    velocity: [i64; 3],
+}
+
+fn parse_vector(s: &str) -> anyhow::Result<[i64; 3]> {
+    let mut components = s.split(',').map(|x| {
+        let x = x.trim();
+        x.parse()
+            .with_context(|| format!("failed to parse component: {x}"))
+    });
+    let mut vector = [0; 3];
+    for component in &mut vector {
+        *component = components
+            .next()
+            .with_context(|| format!("missing component: {s}"))??;
+    }
+    anyhow::ensure!(components.next().is_none(), "too many components: {s}");
+    Ok(vector)
+}
+
+impl FromStr for Hailstone {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 19, 13, 30 @ -2,  1, -2
+        let (position, velocity) = s
+            .split_once('@')
+            .with_context(|| format!("invalid hailstone: {s}"))?;
+
+        Ok(Self {
+            position: parse_vector(position)?,
+            velocity: parse_vector(velocity)?,
+        })
+    }
+}
+
+///
+/// Whether the paths of the hailstones, ignoring the Z axis, cross inside the test area in both
+/// of their futures. Solving `a + t * va = b + s * vb` with Cramer's rule gives `t` and `s` as
+/// fractions over the same determinant, so everything is compared exactly, multiplied through by
+/// the determinant.
+///
+fn paths_cross_within(a: &Hailstone, b: &Hailstone, area: &RangeInclusive<i64>) -> bool {
+    let [ax, ay, _] = a.position.map(i128::from);
+    let [avx, avy, _] = a.velocity.map(i128::from);
+    let [bx, by, _] = b.position.map(i128::from);
+    let [bvx, bvy, _] = b.velocity.map(i128::from);
+
+    let mut determinant = bvx * avy - avx * bvy;
+    if determinant == 0 {
+        // parallel paths never cross at a single point
+        return false;
+    }
+    let (dx, dy) = (bx - ax, by - ay);
+    let mut t = bvx * dy - bvy * dx;
+    let mut s = avx * dy - avy * dx;
+    if determinant < 0 {
+        (t, s, determinant) = (-t, -s, -determinant);
+    }
+    if t < 0 || s < 0 {
+        return false;
+    }
+
+    let (min, max) = (i128::from(*area.start()), i128::from(*area.end()));
+    let x = ax * determinant + t * avx;
+    let y = ay * determinant + t * avy;
+    let within = |coordinate| (min * determinant..=max * determinant).contains(&coordinate);
+    within(x) && within(y)
+}
+
+pub fn count_crossings(hailstones: &[Hailstone], area: &RangeInclusive<i64>) -> usize {
+    hailstones
+        .iter()
+        .enumerate()
+        .map(|(index, a)| {
+            hailstones[index + 1..]
+                .iter()
+                .filter(|b| paths_cross_within(a, b, area))
+                .count()
+        })
+        .sum()
+}
+
+pub fn part1(hailstones: &[Hailstone]) -> usize {
+    count_crossings(hailstones, &TEST_AREA)
+}
+
+/// A prime larger than twice any coordinate or velocity, so they're exact modulo it
+const PRIME: i128 = (1 << 61) - 1;
+
+fn pow_mod(mut base: i128, mut exponent: i128) -> i128 {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % PRIME;
+        }
+        base = base * base % PRIME;
+        exponent >>= 1;
+    }
+    result
+}
+
+///
+/// Solve the system of `N` linear equations in `N` unknowns, each row being the coefficients
+/// followed by the constant, modulo [`PRIME`] with Gaussian elimination. `None` if the system is
+/// singular modulo the prime.
+///
+fn solve_mod_prime<const N: usize>(mut rows: Vec<Vec<i128>>) -> Option<[i128; N]> {
+    for row in &mut rows {
+        for value in row.iter_mut() {
+            *value = value.rem_euclid(PRIME);
+        }
+    }
+
+    for column in 0..N {
+        let pivot = (column..N).find(|&row| rows[row][column] != 0)?;
+        rows.swap(column, pivot);
+        let inverse = pow_mod(rows[column][column], PRIME - 2);
+        for value in rows[column].iter_mut() {
+            *value = *value * inverse % PRIME;
+        }
+
+        let pivot_row = rows[column].clone();
+        for (index, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if index == column || factor == 0 {
+                continue;
+            }
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row) {
+                *value = (*value - factor * pivot_value).rem_euclid(PRIME);
+            }
+        }
+    }
+
+    Some(std::array::from_fn(|row| {
+        // the solution is an integer much smaller than the prime, either side of zero
+        let value = rows[row][N];
+        if value > PRIME / 2 {
+            value - PRIME
+        } else {
+            value
+        }
+    }))
+}
+
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+///
+/// The rock hits a hailstone if they're at the same position at the same whole, non-negative
+/// time
+fn hits(rock: &Hailstone, hailstone: &Hailstone) -> bool {
+    let mut time = None;
+    for axis in 0..3 {
+        let distance = i128::from(hailstone.position[axis]) - i128::from(rock.position[axis]);
+        let closing_speed = i128::from(rock.velocity[axis]) - i128::from(hailstone.velocity[axis]);
+        if closing_speed == 0 {
+            if distance != 0 {
+                return false;
+            }
+            continue;
+        }
+        if distance % closing_speed != 0 {
+            return false;
+        }
+        let axis_time = distance / closing_speed;
+        if axis_time < 0 || time.is_some_and(|time| time != axis_time) {
+            return false;
+        }
+        time = Some(axis_time);
+    }
+    true
+}
+
+///
+/// The rock hitting all of the first three hailstones, if there's a single one. The rock at `P`
+/// moving at `V` hits a hailstone at `p` moving at `v` when `(P - p) x (V - v) = 0`, and
+/// subtracting the equations of two hailstones cancels out the only non-linear term, `P x V`:
+/// `P x (vj - vi) + (pj - pi) x V = pj x vj - pi x vi`. Two pairs give six linear equations.
+///
+fn rock_hitting(hailstones: &[Hailstone; 3]) -> Option<Hailstone> {
+    let position = |index: usize| hailstones[index].position.map(i128::from);
+    let velocity = |index: usize| hailstones[index].velocity.map(i128::from);
+
+    let mut rows = Vec::with_capacity(6);
+    for other in [1, 2] {
+        let dv: [i128; 3] = std::array::from_fn(|axis| velocity(other)[axis] - velocity(0)[axis]);
+        let dp: [i128; 3] = std::array::from_fn(|axis| position(other)[axis] - position(0)[axis]);
+        let rhs: [i128; 3] = std::array::from_fn(|axis| {
+            cross(position(other), velocity(other))[axis] - cross(position(0), velocity(0))[axis]
+        });
+        // the coefficients of P and then V in each axis of the cross products
+        rows.push(vec![0, dv[2], -dv[1], 0, -dp[2], dp[1], rhs[0]]);
+        rows.push(vec![-dv[2], 0, dv[0], dp[2], 0, -dp[0], rhs[1]]);
+        rows.push(vec![dv[1], -dv[0], 0, -dp[1], dp[0], 0, rhs[2]]);
+    }
+
+    let [x, y, z, vx, vy, vz] = solve_mod_prime::<6>(rows)?;
+    let component = |value: i128| i64::try_from(value).ok();
+    Some(Hailstone {
+        position: [component(x)?, component(y)?, component(z)?],
+        velocity: [component(vx)?, component(vy)?, component(vz)?],
+    })
+}
+
+///
+/// The rock which hits every hailstone. Any three hailstones whose equations aren't degenerate
+/// pin it down, so it's solved from three of them in a row and checked against all the others.
+pub fn find_rock(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    hailstones.windows(3).find_map(|window| {
+        let rock = rock_hitting(window.try_into().ok()?)?;
+        hailstones
+            .iter()
+            .all(|hailstone| hits(&rock, hailstone))
+            .then_some(rock)
+    })
+}
+
+pub fn part2(hailstones: &[Hailstone]) -> anyhow::Result<i64> {
+    let rock = find_rock(hailstones).context("no rock hits every hailstone")?;
+    Ok(rock.position.iter().sum())
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = Vec<Hailstone>;
+
+    const TITLE: &'static str = "Never Tell Me The Odds";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        parse_from_lines(input.lines())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+
+    const EXAMPLE_AREA: RangeInclusive<i64> = 7..=27;
+
+    #[test]
+    fn test_part1() {
+        let hailstones = Day24::parse(EXAMPLE).unwrap();
+        assert_eq!(hailstones[4].velocity, [1, -5, -3]);
+        assert_eq!(count_crossings(&hailstones, &EXAMPLE_AREA), 2);
+    }
+
+    #[test]
+    fn test_paths_cross_within() {
+        let hailstones = Day24::parse(EXAMPLE).unwrap();
+        let crosses =
+            |a: usize, b: usize| paths_cross_within(&hailstones[a], &hailstones[b], &EXAMPLE_AREA);
+        // inside the test area
+        assert!(crosses(0, 1));
+        assert!(crosses(0, 2));
+        // outside of it
+        assert!(!crosses(0, 3));
+        // in the past
+        assert!(!crosses(0, 4));
+        // parallel
+        assert!(!crosses(1, 2));
+        assert!(crosses(1, 0));
+    }
+
+    #[test]
+    fn test_part2() {
+        let hailstones = Day24::parse(EXAMPLE).unwrap();
+        let rock = find_rock(&hailstones).unwrap();
+        assert_eq!(rock, "24, 13, 10 @ -3, 1, 2".parse().unwrap());
+        assert_eq!(part2(&hailstones).unwrap(), 47);
+
+        // a rock thrown at real-sized coordinates
+        let rock: Hailstone = "287430900705823, 451620998712421, 260730677041648 @ -17, -40, 48"
+            .parse()
+            .unwrap();
+        let hailstones: Vec<Hailstone> = [
+            ([-31, 72, 2], 320),
+            ([19, -84, 22], 475),
+            ([-200, 13, -7], 1066),
+            ([5, 5, 5], 9001),
+        ]
+        .into_iter()
+        .map(|(velocity, time)| Hailstone {
+            position: std::array::from_fn(|axis| {
+                rock.position[axis] + (rock.velocity[axis] - velocity[axis]) * time
+            }),
+            velocity,
+        })
+        .collect();
+        assert_eq!(find_rock(&hailstones), Some(rock));
+    }
+
+    #[test]
+    fn test_hits() {
+        let rock: Hailstone = "24, 13, 10 @ -3, 1, 2".parse().unwrap();
+        let hailstone: Hailstone = "19, 13, 30 @ -2, 1, -2".parse().unwrap();
+        assert!(hits(&rock, &hailstone));
+        // it would have been hit in the past
+        let hailstone: Hailstone = "19, 13, 30 @ -4, 1, 6".parse().unwrap();
+        assert!(!hits(&rock, &hailstone));
+        // the paths cross, but not at the same time
+        let hailstone: Hailstone = "19, 13, 30 @ -2, 0, -2".parse().unwrap();
+        assert!(!hits(&rock, &hailstone));
+    }
+
+    #[test]
+    fn test_invalid_hailstone() {
+        assert!("19, 13, 30".parse::<Hailstone>().is_err());
+        assert!("19, 13 @ -2, 1, -2".parse::<Hailstone>().is_err());
+        assert!("19, 13, 30, 1 @ -2, 1, -2".parse::<Hailstone>().is_err());
+        assert!("19, x, 30 @ -2, 1, -2".parse::<Hailstone>().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Context;
+
+use crate::solution::{Answer, Solution};
+
+/// How many wires have to be disconnected to split the components into two groups
+const WIRES_TO_CUT: usize = 3;
+
+///
+/// The components and the wires between them, with the components' names borrowed from the
+/// input. Components are referred to by their index everywhere else.
+///
+#[derive(Debug)]
+pub struct WiringDiagram<'a> {
+    names: Vec<&'a str>,
+    /// Every wire, between the components at both ends
+    wires: Vec<(usize, usize)>,
+    /// The components each component is wired to, and the index of the wire to it
+    neighbours: Vec<Vec<(usize, usize)>>,
+}
+
+impl<'a> WiringDiagram<'a> {
+    pub fn parse(s: &'a str) -> anyhow::Result<Self> {
+        let mut names = Vec::new();
+        let mut indices: HashMap<&str, usize> = HashMap::new();
+        let mut index_of = |name: &'a str| {
+            *indices.entry(name).or_insert_with(|| {
+                names.push(name);
+                names.len() - 1
+            })
+        };
+
+        let mut wires = Vec::new();
+        for line in s.lines().filter(|line| !line.is_empty()) {
+            // jqt: rhn xhk nvd
+            let (component, connected) = line
+                .split_once(": ")
+                .with_context(|| format!("missing connected components: {line}"))?;
+            anyhow::ensure!(!component.is_empty(), "missing component name: {line}");
+            let component = index_of(component);
+            for other in connected.split_whitespace() {
+                wires.push((component, index_of(other)));
+            }
+        }
+
+        let mut neighbours = vec![Vec::new(); names.len()];
+        for (wire, &(a, b)) in wires.iter().enumerate() {
+            neighbours[a].push((b, wire));
+            neighbours[b].push((a, wire));
+        }
+
+        Ok(Self {
+            names,
+            wires,
+            neighbours,
+        })
+    }
+
+    ///
+    /// The components reachable from the source through wires with some capacity left, and the
+    /// wire used to first reach each of them
+    fn residual_reachable(&self, source: usize, flows: &[i8]) -> Vec<Option<usize>> {
+        let mut reached_by = vec![None; self.names.len()];
+        let mut visited = vec![false; self.names.len()];
+        visited[source] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(component) = queue.pop_front() {
+            for &(next, wire) in &self.neighbours[component] {
+                // every wire carries a unit of flow either way, and `flows` is towards its second
+                // end
+                let forward = self.wires[wire].0 == component;
+                let capacity = if forward {
+                    1 - flows[wire]
+                } else {
+                    1 + flows[wire]
+                };
+                if capacity > 0 && !visited[next] {
+                    visited[next] = true;
+                    reached_by[next] = Some(wire);
+                    queue.push_back(next);
+                }
+            }
+        }
+        reached_by
+    }
+
+    ///
+    /// The number of components on the source's side of the smallest cut between the source and
+    /// the sink, if that cut is exactly `cut` wires. Finds up to `cut + 1` paths which don't share
+    /// wires, Edmonds-Karp style; once no more are found, the components still reachable from the
+    /// source are the source's side.
+    fn min_cut_side(&self, source: usize, sink: usize, cut: usize) -> Option<usize> {
+        let mut flows = vec![0i8; self.wires.len()];
+        for paths in 0..=cut {
+            let reached_by = self.residual_reachable(source, &flows);
+            if reached_by[sink].is_none() {
+                let side = reached_by
+                    .iter()
+                    .enumerate()
+                    .filter(|&(component, wire)| component == source || wire.is_some())
+                    .count();
+                return (paths == cut).then_some(side);
+            }
+
+            let mut component = sink;
+            while let Some(wire) = reached_by[component] {
+                let (a, b) = self.wires[wire];
+                if b == component {
+                    flows[wire] += 1;
+                    component = a;
+                } else {
+                    flows[wire] -= 1;
+                    component = b;
+                }
+            }
+        }
+        None
+    }
+
+    ///
+    /// The sizes of the two groups left after cutting `wires` wires. Some component is on the
+    /// other side from the first one, and the smallest cut between the two is the one.
+    pub fn split(&self, wires: usize) -> Option<(usize, usize)> {
+        (1..self.names.len()).find_map(|sink| {
+            let side = self.min_cut_side(0, sink, wires)?;
+            Some((side, self.names.len() - side))
+        })
+    }
+}
+
+pub fn part1(diagram: &WiringDiagram) -> anyhow::Result<usize> {
+    let (a, b) = diagram
+        .split(WIRES_TO_CUT)
+        .with_context(|| format!("cutting {WIRES_TO_CUT} wires doesn't split the components"))?;
+    Ok(a * b)
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = WiringDiagram<'a>;
+
+    const TITLE: &'static str = "Snowverload";
+
+    const PART2_IMPLEMENTED: bool = false;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        WiringDiagram::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        anyhow::bail!("day 25 only has one part")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
+
+    #[test]
+    fn test_part1() {
+        let diagram = WiringDiagram::parse(EXAMPLE).unwrap();
+        assert_eq!(diagram.names.len(), 15);
+        assert_eq!(diagram.wires.len(), 33);
+        assert_eq!(part1(&diagram).unwrap(), 54);
+    }
+
+    #[test]
+    fn test_min_cut_side() {
+        // two triangles joined by a single wire
+        let diagram = WiringDiagram::parse("a: b c\nb: c\nc: d\nd: e f\ne: f").unwrap();
+        assert_eq!(diagram.split(1), Some((3, 3)));
+        assert_eq!(diagram.min_cut_side(0, 1, 1), None);
+        assert!(part1(&diagram).is_err());
+    }
+
+    #[test]
+    fn test_invalid_diagram() {
+        assert!(WiringDiagram::parse("jqt rhn xhk nvd").is_err());
+        assert!(WiringDiagram::parse(": rhn").is_err());
+    }
+}
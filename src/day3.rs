@@ -1,206 +1,322 @@
 use std::{ops::Range, str::FromStr};
 
 use anyhow::Context;
-use itertools::Itertools;
 
+use crate::{
+    solution::{Answer, Solution},
+    utils::{geom::Point, grid::Grid, simd::find_symbol},
+};
+
+///
+/// A number in the schematic, which is a part number if it's next to a symbol
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SchematicNumber {
+    number: u32,
+    row: usize,
+    columns: Range<usize>,
+}
+
+///
+/// The engine schematic as a grid, along with where its numbers are, so the cells around a symbol
+/// lead straight to the numbers next to it on any row, the first and last ones included
+///
 #[derive(Debug)]
-pub struct AdjacencyRange {
-    inner: Range<usize>,
+pub struct EngineSchematic {
+    grid: Grid<u8>,
+    numbers: Vec<SchematicNumber>,
+    /// The index in `numbers` of the number covering each cell
+    number_at: Grid<Option<usize>>,
 }
 
-impl AdjacencyRange {
-    pub fn new(range: &Range<usize>) -> Self {
-        let start = match range.start {
-            0 => range.start,
-            _ => range.start - 1,
-        };
-        let end = range.end + 1;
-        Self { inner: start..end }
+///
+/// A symbol in the schematic along with the part numbers next to it
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolAdjacency {
+    symbol: char,
+    position: Point,
+    part_numbers: Vec<u32>,
+}
+
+impl SymbolAdjacency {
+    pub fn symbol(&self) -> char {
+        self.symbol
     }
 
-    pub fn contains(&self, index: &usize) -> bool {
-        self.inner.contains(index)
+    pub fn position(&self) -> Point {
+        self.position
     }
-}
 
-#[derive(Debug)]
-pub struct PossiblePartNumber {
-    number: u32,
-    location_range: AdjacencyRange,
+    ///
+    /// The numbers next to the symbol, diagonally included, in reading order
+    pub fn part_numbers(&self) -> &[u32] {
+        &self.part_numbers
+    }
 }
 
-#[derive(Debug)]
-pub struct EngineLine {
-    possible_part_numbers: Vec<PossiblePartNumber>,
-    symbol_indexes: Vec<usize>,
-    possible_gears: Vec<usize>,
+/// A symbol is anything but a digit or a `.`
+fn find_symbols(row: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    let mut search_start = 0;
+    std::iter::from_fn(move || {
+        let index = search_start + find_symbol(&row[search_start..])?;
+        search_start = index + 1;
+        Some(index)
+    })
 }
 
-fn parse_possible_part_number(
-    s: &str,
-    possible_start: &mut Option<usize>,
-    current_index: usize,
-) -> anyhow::Result<Option<PossiblePartNumber>> {
-    let Some(number_start_index) = possible_start.take() else {
-        return Ok(None);
-    };
-    let number_substring = &s[number_start_index..current_index];
-    let number = u32::from_str(number_substring)
-        .with_context(|| format!("failed to parse string as u32: {number_substring}"))?;
-
-    Ok(Some(PossiblePartNumber {
-        number,
-        location_range: AdjacencyRange::new(&(number_start_index..current_index)),
-    }))
+fn parse_numbers(row: usize, line: &[u8]) -> anyhow::Result<Vec<SchematicNumber>> {
+    let mut numbers = Vec::new();
+    let mut column = 0;
+    while column < line.len() {
+        let digits = line[column..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            column += 1;
+            continue;
+        }
+
+        let columns = column..column + digits;
+        // the digits are ascii
+        let number = std::str::from_utf8(&line[columns.clone()])?;
+        let number = number
+            .parse()
+            .with_context(|| format!("failed to parse string as u32: {number}"))?;
+        numbers.push(SchematicNumber {
+            number,
+            row,
+            columns: columns.clone(),
+        });
+        column = columns.end;
+    }
+
+    Ok(numbers)
 }
 
-impl FromStr for EngineLine {
+impl FromStr for EngineSchematic {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut symbol_indexes = Vec::new();
-        let mut possible_part_numbers = Vec::new();
-        let mut possible_gears = Vec::new();
-        let mut parsing_number_start = None;
-        for (index, c) in s.chars().enumerate() {
-            if c.is_ascii_digit() {
-                if parsing_number_start.is_none() {
-                    parsing_number_start = Some(index);
-                }
-                continue;
-            }
-
-            if let Some(possible_part_number) =
-                parse_possible_part_number(s, &mut parsing_number_start, index)?
-            {
-                possible_part_numbers.push(possible_part_number);
-            }
-
-            if c == '.' {
-                continue;
-            }
-
-            symbol_indexes.push(index);
+        let grid = Grid::parse_chars(s, |c| {
+            anyhow::ensure!(c.is_ascii(), "engine schematic isn't ascii: {c}");
+            Ok(c as u8)
+        })
+        .context("failed to parse engine schematic")?;
 
-            if c == '*' {
-                possible_gears.push(index);
-            }
+        let mut numbers = Vec::new();
+        for (row, line) in grid.rows().enumerate() {
+            numbers.extend(
+                parse_numbers(row, line).with_context(|| format!("invalid row {}", row + 1))?,
+            );
         }
 
-        // if the line ends in a digit we mustn't forget to parse the last number
-        if let Some(possible_part_number) =
-            parse_possible_part_number(s, &mut parsing_number_start, s.len())?
-        {
-            possible_part_numbers.push(possible_part_number);
+        let mut number_at = Grid::from_rows(vec![vec![None; grid.num_columns()]; grid.num_rows()])?;
+        for (index, number) in numbers.iter().enumerate() {
+            for column in number.columns.clone() {
+                if let Some(cell) = number_at.get_mut(number.row, column) {
+                    *cell = Some(index);
+                }
+            }
         }
 
         Ok(Self {
-            possible_part_numbers,
-            symbol_indexes,
-            possible_gears,
+            grid,
+            numbers,
+            number_at,
         })
     }
 }
 
-fn get_part_numbers_sum(part_numbers: &[PossiblePartNumber], symbol_indexes: &[usize]) -> u32 {
-    let mut sum = 0;
-    for part_number in part_numbers {
-        for index in symbol_indexes {
-            if part_number.location_range.contains(index) {
-                sum += part_number.number;
-            }
-        }
+impl EngineSchematic {
+    ///
+    /// The (row, column) of every symbol, row by row
+    fn symbols(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.grid
+            .rows()
+            .enumerate()
+            .flat_map(|(row, line)| find_symbols(line).map(move |column| (row, column)))
     }
 
-    sum
-}
-
-pub fn part1(engine_lines: &[EngineLine]) -> u32 {
-    let mut sum = 0;
-    // Iterate same line adjacency
-    for line in engine_lines {
-        sum += get_part_numbers_sum(&line.possible_part_numbers, &line.symbol_indexes);
+    ///
+    /// The indices of the numbers next to the cell, diagonally included, each once
+    fn adjacent_numbers(&self, row: usize, column: usize) -> Vec<usize> {
+        let mut adjacent: Vec<usize> = self
+            .grid
+            .surrounding(row, column)
+            .filter_map(|(row, column)| *self.number_at.get(row, column)?)
+            .collect();
+        adjacent.sort_unstable();
+        adjacent.dedup();
+        adjacent
     }
 
-    // interline adjacencies
-
-    for (line_above, line_below) in engine_lines.iter().tuple_windows() {
-        sum += get_part_numbers_sum(
-            &line_above.possible_part_numbers,
-            &line_below.symbol_indexes,
-        );
-        sum += get_part_numbers_sum(
-            &line_below.possible_part_numbers,
-            &line_above.symbol_indexes,
-        );
+    ///
+    /// Every symbol in reading order, with the part numbers next to it. A number next to a few
+    /// symbols is listed for each of them.
+    pub fn symbol_adjacencies(&self) -> Vec<SymbolAdjacency> {
+        self.symbols()
+            .filter_map(|(row, column)| {
+                Some(SymbolAdjacency {
+                    symbol: char::from(*self.grid.get(row, column)?),
+                    position: Point::new(column, row),
+                    part_numbers: self
+                        .adjacent_numbers(row, column)
+                        .into_iter()
+                        .map(|index| self.numbers[index].number)
+                        .collect(),
+                })
+            })
+            .collect()
     }
-
-    sum
 }
 
-fn add_adjecent_part_number(
-    gear: &usize,
-    part_numbers: &[PossiblePartNumber],
-    adjecent: &mut Vec<u32>,
-) {
-    for part_number in part_numbers {
-        if part_number.location_range.contains(gear) {
-            adjecent.push(part_number.number);
+///
+/// The sum of the part numbers, the numbers next to a symbol. A number next to a few symbols
+/// still counts once.
+pub fn part1(schematic: &EngineSchematic) -> u32 {
+    let mut is_part = vec![false; schematic.numbers.len()];
+    for (row, column) in schematic.symbols() {
+        for index in schematic.adjacent_numbers(row, column) {
+            is_part[index] = true;
         }
     }
-}
 
-fn get_gear_product_sum(
-    part_numbers_above: &[PossiblePartNumber],
-    part_numbers_current: &[PossiblePartNumber],
-    part_numbers_below: &[PossiblePartNumber],
-    gears: &[usize],
-) -> u32 {
-    let mut sum = 0;
-    for gear in gears {
-        let mut adjecent = Vec::new();
-        add_adjecent_part_number(gear, part_numbers_above, &mut adjecent);
-        add_adjecent_part_number(gear, part_numbers_current, &mut adjecent);
-        add_adjecent_part_number(gear, part_numbers_below, &mut adjecent);
-
-        if adjecent.len() == 2 {
-            sum += adjecent.into_iter().product::<u32>();
-        }
-    }
+    schematic
+        .numbers
+        .iter()
+        .zip(is_part)
+        .filter(|(_, is_part)| *is_part)
+        .map(|(number, _)| number.number)
+        .sum()
+}
 
-    sum
+///
+/// The sum of the gear ratios, the products of the two part numbers of every `*` which is next
+/// to exactly two of them
+pub fn part2(schematic: &EngineSchematic) -> u32 {
+    schematic
+        .symbol_adjacencies()
+        .iter()
+        .filter(|adjacency| adjacency.symbol == '*' && adjacency.part_numbers.len() == 2)
+        .map(|adjacency| adjacency.part_numbers.iter().product::<u32>())
+        .sum()
 }
 
-pub fn part2(engine_lines: &[EngineLine]) -> u32 {
-    let mut sum = 0;
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = EngineSchematic;
+
+    const TITLE: &'static str = "Gear Ratios";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
 
-    for (line_above, current_line, line_below) in engine_lines.iter().tuple_windows() {
-        // this is cheating a bit, but the first and last lines don't have gears. Otherwise we would also have to sum the first and last line of gears by having the above and below lines empty accordingly
-        sum += get_gear_product_sum(
-            &line_above.possible_part_numbers,
-            &current_line.possible_part_numbers,
-            &line_below.possible_part_numbers,
-            &current_line.possible_gears,
-        );
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
     }
 
-    sum
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::{get_day_test_input, parse_input};
+    use crate::utils::{get_day_test_input, try_parse_input};
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
 
     #[test]
     fn test_day3_part1() {
-        let engine_lines = parse_input(get_day_test_input("day3"));
-        assert_eq!(part1(&engine_lines), 4361);
+        let schematic = try_parse_input(get_day_test_input("day3")).unwrap();
+        assert_eq!(part1(&schematic), 4361);
     }
 
     #[test]
     fn test_day3_part2() {
-        let engine_lines = parse_input(get_day_test_input("day3"));
-        assert_eq!(part2(&engine_lines), 467835);
+        let schematic = try_parse_input(get_day_test_input("day3")).unwrap();
+        assert_eq!(part2(&schematic), 467835);
+    }
+
+    #[test]
+    fn test_part1() {
+        let schematic: EngineSchematic = EXAMPLE.parse().unwrap();
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(part1(&schematic), 4361);
+
+        // a number next to two symbols counts once
+        let schematic: EngineSchematic = "#12#\n....".parse().unwrap();
+        assert_eq!(part1(&schematic), 12);
+        // numbers at the edges of the first and last rows
+        let schematic: EngineSchematic = "7..8\n.+..\n..$.\n9..1".parse().unwrap();
+        assert_eq!(part1(&schematic), 7 + 1);
+    }
+
+    #[test]
+    fn test_part2() {
+        let schematic: EngineSchematic = EXAMPLE.parse().unwrap();
+        assert_eq!(part2(&schematic), 467835);
+
+        // gears on the first and last rows
+        let schematic: EngineSchematic = "2*3.\n....\n..10\n.5*.".parse().unwrap();
+        assert_eq!(part2(&schematic), 2 * 3 + 5 * 10);
+        // a gear next to a single number twice is no gear
+        let schematic: EngineSchematic = "123\n.*.".parse().unwrap();
+        assert_eq!(part2(&schematic), 0);
+    }
+
+    #[test]
+    fn test_symbol_adjacencies() {
+        let schematic: EngineSchematic = EXAMPLE.parse().unwrap();
+        let adjacencies = schematic.symbol_adjacencies();
+        assert_eq!(adjacencies.len(), 6);
+        assert_eq!(adjacencies[0].symbol(), '*');
+        assert_eq!(adjacencies[0].position(), Point::new(3, 1));
+        assert_eq!(adjacencies[0].part_numbers(), &[467, 35]);
+        assert_eq!(adjacencies[2].symbol(), '*');
+        assert_eq!(adjacencies[2].part_numbers(), &[617]);
+        assert_eq!(adjacencies[4].symbol(), '$');
+        assert_eq!(adjacencies[4].position(), Point::new(3, 8));
+
+        // 114 and 58 are the only numbers next to no symbol
+        let adjacent: Vec<u32> = adjacencies
+            .iter()
+            .flat_map(|adjacency| adjacency.part_numbers())
+            .copied()
+            .collect();
+        assert!(!adjacent.contains(&114));
+        assert!(!adjacent.contains(&58));
+        assert_eq!(adjacent.iter().sum::<u32>(), part1(&schematic));
+
+        // a number next to two symbols is listed for both
+        let schematic: EngineSchematic = "#12%".parse().unwrap();
+        let adjacencies = schematic.symbol_adjacencies();
+        assert_eq!(adjacencies[0].part_numbers(), &[12]);
+        assert_eq!(adjacencies[1].symbol(), '%');
+        assert_eq!(adjacencies[1].part_numbers(), &[12]);
+    }
+
+    #[test]
+    fn test_invalid_schematic() {
+        assert!("ü12*".parse::<EngineSchematic>().is_err());
+        assert!("12*\n.".parse::<EngineSchematic>().is_err());
+        assert!("99999999999.".parse::<EngineSchematic>().is_err());
+        let empty: EngineSchematic = "".parse().unwrap();
+        assert_eq!(part1(&empty), 0);
     }
 }
@@ -2,6 +2,11 @@ use std::{collections::HashSet, str::FromStr};
 
 use anyhow::Context;
 
+use crate::{
+    solution::{Answer, Solution},
+    utils::parse_from_lines,
+};
+
 pub struct ScratchCard {
     chosen: HashSet<u32>,
     winning: HashSet<u32>,
@@ -70,21 +75,101 @@ pub fn part2(scratch_cards: &[ScratchCard]) -> u32 {
     num_cards_of_each.into_iter().sum()
 }
 
+///
+/// Solve both parts with a single pass over the number of winners in each card.
+/// Returns `(part1_points, part2_total_cards)`
+pub fn solve(scratch_cards: &[ScratchCard]) -> (u32, u32) {
+    let mut points = 0;
+    let mut num_cards_of_each = vec![1; scratch_cards.len()];
+    for (index, scratch_card) in scratch_cards.iter().enumerate() {
+        let winners = scratch_card.get_count_chosen_in_winning();
+        if winners > 0 {
+            points += 2_u32.pow((winners - 1) as u32);
+        }
+
+        let copies = num_cards_of_each[index];
+        let last_to_add = (index + winners).min(scratch_cards.len() - 1);
+        for num_cards in &mut num_cards_of_each[index + 1..=last_to_add] {
+            *num_cards += copies;
+        }
+    }
+
+    (points, num_cards_of_each.into_iter().sum())
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = Vec<ScratchCard>;
+
+    const TITLE: &'static str = "Scratchcards";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        parse_from_lines(input.lines())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::{get_day_test_input, parse_input};
+    use proptest::prelude::*;
+
+    use crate::{
+        testing::scratch_cards,
+        utils::{get_day_test_input, parse_input_streaming},
+    };
 
     use super::*;
 
     #[test]
     fn test_part1() {
-        let scratch_cards = parse_input(get_day_test_input("day4"));
+        let scratch_cards: Vec<ScratchCard> =
+            parse_input_streaming(get_day_test_input("day4")).unwrap();
         assert_eq!(part1(&scratch_cards), 13);
     }
 
     #[test]
     fn test_part2() {
-        let scratch_cards = parse_input(get_day_test_input("day4"));
+        let scratch_cards: Vec<ScratchCard> =
+            parse_input_streaming(get_day_test_input("day4")).unwrap();
         assert_eq!(part2(&scratch_cards), 30);
     }
+
+    #[test]
+    fn test_solve() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let scratch_cards: Vec<ScratchCard> =
+            input.lines().map(|line| line.parse().unwrap()).collect();
+        assert_eq!(solve(&scratch_cards), (13, 30));
+        assert_eq!(
+            solve(&scratch_cards),
+            (part1(&scratch_cards), part2(&scratch_cards))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_solve_matches_parts(cards in scratch_cards()) {
+            let scratch_cards: Vec<ScratchCard> =
+                cards.iter().map(|(line, _)| line.parse().unwrap()).collect();
+            for (scratch_card, (_, matches)) in scratch_cards.iter().zip(&cards) {
+                prop_assert_eq!(scratch_card.get_count_chosen_in_winning(), *matches);
+            }
+
+            let expected = (part1(&scratch_cards), part2(&scratch_cards));
+            prop_assert_eq!(solve(&scratch_cards), expected);
+        }
+    }
 }
@@ -7,6 +7,12 @@ use std::{
 use anyhow::{Context, Ok};
 use itertools::Itertools;
 
+use crate::{
+    progress::Progress,
+    solution::{Answer, Solution},
+    utils::{interval::split_range, merge_ranges},
+};
+
 #[derive(Debug)]
 struct SeedConversionLine {
     source: Range<u64>,
@@ -63,7 +69,7 @@ fn parse_map_block(lines: &mut Lines) -> anyhow::Result<Vec<SeedConversionLine>>
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum MappingType {
+pub(crate) enum MappingType {
     Seed,
     Soil,
     Fertilizier,
@@ -92,6 +98,41 @@ impl SeedConversion {
         // If no mapping, it's means it's 1 to 1
         source_num
     }
+
+    pub fn get_source_number(&self, dest_num: u64) -> u64 {
+        for mapping in &self.mappings {
+            if mapping.destination.contains(&dest_num) {
+                let diff_from_start = dest_num - mapping.destination.start;
+                return mapping.source.start + diff_from_start;
+            }
+        }
+
+        dest_num
+    }
+
+    ///
+    /// Map every number of the ranges at once, splitting them where the mapping lines start and
+    /// end. Like a single number, the parts no line covers map to themselves.
+    fn get_dest_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut unmapped = ranges.to_vec();
+        let mut mapped = Vec::new();
+        for mapping in &self.mappings {
+            let mut remaining = Vec::new();
+            for range in unmapped {
+                let (before, rest) = split_range(range, mapping.source.start);
+                let (inside, after) = split_range(rest, mapping.source.end);
+                if !inside.is_empty() {
+                    let start = mapping.destination.start + (inside.start - mapping.source.start);
+                    mapped.push(start..start + (inside.end - inside.start));
+                }
+                remaining.extend([before, after].into_iter().filter(|x| !x.is_empty()));
+            }
+            unmapped = remaining;
+        }
+
+        mapped.extend(unmapped);
+        mapped
+    }
 }
 
 impl From<Vec<SeedConversionLine>> for SeedConversion {
@@ -101,7 +142,7 @@ impl From<Vec<SeedConversionLine>> for SeedConversion {
 }
 
 #[derive(Debug)]
-pub struct MappingTo {
+pub(crate) struct MappingTo {
     conversion: SeedConversion,
     to: MappingType,
 }
@@ -241,55 +282,264 @@ impl Almanac {
         Ok(locations)
     }
 
-    pub fn get_location_for_seed_pairs(&self) -> anyhow::Result<Vec<u64>> {
-        let mut locations = Vec::new();
-        let seed_tuples = self.seeds.iter().tuples();
-
-        for (seed_start, length) in seed_tuples {
-            for seed in *seed_start..(seed_start + length) {
-                locations.push(
-                    self.follow_mapping_from_util(MappingType::Seed, MappingType::Location, seed)
-                        .context("failed to follow mapping for seed")?,
-                );
+    /// The mapping chain from seed to location, in order
+    fn get_mapping_chain(&self) -> anyhow::Result<Vec<&MappingTo>> {
+        let mut chain = Vec::new();
+        let mut current_type = MappingType::Seed;
+        while current_type != MappingType::Location {
+            let mapping = self
+                .mappings
+                .get(&current_type)
+                .with_context(|| format!("no mapping from {current_type:?}"))?;
+            chain.push(mapping);
+            current_type = mapping.to;
+        }
+
+        Ok(chain)
+    }
+
+    ///
+    /// The seeds read as pairs of a range's start and length
+    fn seed_ranges(&self) -> anyhow::Result<Vec<Range<u64>>> {
+        anyhow::ensure!(
+            self.seeds.len().is_multiple_of(2),
+            "seeds aren't pairs of a start and a length: {} numbers",
+            self.seeds.len()
+        );
+        let seed_ranges: Vec<Range<u64>> = self
+            .seeds
+            .iter()
+            .tuples()
+            .map(|(start, length)| {
+                let end = start
+                    .checked_add(*length)
+                    .with_context(|| format!("seed range {start} + {length} overflows"))?;
+                Ok(*start..end)
+            })
+            .collect::<anyhow::Result<_>>()?;
+        anyhow::ensure!(
+            seed_ranges.iter().any(|range| !range.is_empty()),
+            "no seeds in the seed ranges"
+        );
+
+        Ok(seed_ranges)
+    }
+
+    ///
+    /// Scan locations upwards, mapping each one back to a seed, and return the first location whose
+    /// seed falls in one of the seed ranges
+    pub fn min_location_by_reverse_scan(&self) -> anyhow::Result<u64> {
+        let chain = self.get_mapping_chain()?;
+        let seed_ranges = self.seed_ranges()?;
+
+        for location in 0..=u64::MAX {
+            let seed = chain.iter().rev().fold(location, |number, mapping| {
+                mapping.conversion.get_source_number(number)
+            });
+
+            if seed_ranges.iter().any(|range| range.contains(&seed)) {
+                return Ok(location);
             }
         }
-        Ok(locations)
+
+        anyhow::bail!("no location maps back to a seed")
+    }
+
+    ///
+    /// Follow the seed ranges through each stage of the chain as whole ranges, merging the ones
+    /// which end up overlapping so there are only ever a few of them
+    pub fn min_location_by_ranges(&self) -> anyhow::Result<u64> {
+        let mut ranges = self.seed_ranges()?;
+        merge_ranges(&mut ranges);
+        for mapping in self.get_mapping_chain()? {
+            ranges = mapping.conversion.get_dest_ranges(&ranges);
+            merge_ranges(&mut ranges);
+        }
+
+        ranges
+            .first()
+            .map(|range| range.start)
+            .context("no seed ranges")
+    }
+
+    ///
+    /// Follow every seed in the seed ranges to its location, keeping only the lowest one since a
+    /// real almanac has around a billion seeds
+    pub fn min_location_for_seed_pairs(&self) -> anyhow::Result<u64> {
+        let seed_ranges = self.seed_ranges()?;
+        let total_seeds = seed_ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum();
+        let progress = Progress::start("day5 seeds", total_seeds);
+
+        let mut min_location = u64::MAX;
+        for range in seed_ranges {
+            let length = range.end - range.start;
+            for seed in range {
+                let location = self
+                    .follow_mapping_from_util(MappingType::Seed, MappingType::Location, seed)
+                    .context("failed to follow mapping for seed")?;
+                min_location = min_location.min(location);
+            }
+            progress.inc(length);
+        }
+        Ok(min_location)
     }
 }
 
-pub fn part1(almanac: &Almanac) -> u64 {
+pub fn part1(almanac: &Almanac) -> anyhow::Result<u64> {
     almanac
-        .get_location_for_seeds()
-        .unwrap()
+        .get_location_for_seeds()?
         .into_iter()
         .min()
-        .unwrap()
+        .context("no seeds")
 }
 
-pub fn part2(almanac: &Almanac) -> u64 {
-    almanac
-        .get_location_for_seed_pairs()
-        .unwrap()
-        .into_iter()
-        .min()
-        .unwrap()
+pub fn part2(almanac: &Almanac) -> anyhow::Result<u64> {
+    almanac.min_location_for_seed_pairs()
+}
+
+pub fn part2_reverse_scan(almanac: &Almanac) -> anyhow::Result<u64> {
+    almanac.min_location_by_reverse_scan()
+}
+
+pub fn part2_ranges(almanac: &Almanac) -> anyhow::Result<u64> {
+    almanac.min_location_by_ranges()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = Almanac;
+
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    const CONCURRENT_PARTS: bool = true;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2_ranges(input)?.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::{get_day_test_input, parse_input};
+    use crate::utils::{get_day_test_input, try_parse_input};
 
     use super::*;
 
     #[test]
     fn test_part1() {
-        let almanac = parse_input(get_day_test_input("day5"));
-        assert_eq!(part1(&almanac), 35);
+        let almanac = try_parse_input(get_day_test_input("day5")).unwrap();
+        assert_eq!(part1(&almanac).unwrap(), 35);
     }
 
     #[test]
     fn test_part2() {
-        let almanac = parse_input(get_day_test_input("day5"));
-        assert_eq!(part2(&almanac), 46);
+        let almanac = try_parse_input(get_day_test_input("day5")).unwrap();
+        assert_eq!(part2(&almanac).unwrap(), 46);
+        assert_eq!(
+            almanac.min_location_by_reverse_scan().unwrap(),
+            almanac.min_location_by_ranges().unwrap()
+        );
+    }
+
+    const MAPS: &str = "seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    fn almanac(seeds: &str) -> Almanac {
+        format!("seeds:{seeds}\n\n{MAPS}").parse().unwrap()
+    }
+
+    #[test]
+    fn test_min_location_by_reverse_scan() {
+        let almanac = almanac(" 79 14 55 13");
+        assert_eq!(almanac.min_location_by_reverse_scan().unwrap(), 46);
+        assert_eq!(part2(&almanac).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_min_location_by_ranges() {
+        assert_eq!(
+            almanac(" 79 14 55 13").min_location_by_ranges().unwrap(),
+            46
+        );
+        // overlapping seed ranges are merged before the first stage
+        let overlapping = almanac(" 79 14 80 20 55 13");
+        assert_eq!(
+            part2_ranges(&overlapping).unwrap(),
+            part2(&overlapping).unwrap()
+        );
+        assert!(almanac("").min_location_by_ranges().is_err());
+    }
+
+    #[test]
+    fn test_get_dest_ranges() {
+        let conversion = SeedConversion::from(vec![
+            "50 98 2".parse().unwrap(),
+            "52 50 48".parse().unwrap(),
+        ]);
+        let mut ranges = conversion.get_dest_ranges(&[40..60, 95..105]);
+        ranges.sort_unstable_by_key(|range| range.start);
+        assert_eq!(ranges, vec![40..50, 50..52, 52..62, 97..100, 100..105]);
+        merge_ranges(&mut ranges);
+        assert_eq!(ranges, vec![40..62, 97..105]);
+    }
+
+    #[test]
+    fn test_reverse_scan_without_seed_ranges() {
+        assert!(almanac("").min_location_by_reverse_scan().is_err());
+        assert!(part2(&almanac("")).is_err());
+        assert!(almanac(" 79 0").min_location_by_reverse_scan().is_err());
+    }
+
+    #[test]
+    fn test_seed_range_overflow() {
+        let almanac = almanac(" 18446744073709551615 1");
+        assert!(almanac.min_location_by_reverse_scan().is_err());
+        assert!(almanac.min_location_by_ranges().is_err());
+    }
+
+    #[test]
+    fn test_reverse_scan_odd_seeds() {
+        assert!(almanac(" 79 14 55").min_location_by_reverse_scan().is_err());
     }
 }
@@ -3,6 +3,8 @@ use std::str::FromStr;
 use anyhow::Context;
 use itertools::Itertools;
 
+use crate::solution::{Answer, Solution};
+
 #[derive(Debug)]
 pub struct Race {
     race_time: u64,
@@ -40,7 +42,9 @@ impl Race {
         let first_root = (-t + sqrt) / 2.;
         let second_root = (-t - sqrt) / 2.;
 
-        let num_integers_between = first_root.ceil() as u64 - (second_root.floor() as u64 + 1);
+        // a record of exactly the best distance has a single root, which doesn't beat it
+        let num_integers_between =
+            (first_root.ceil() as u64).saturating_sub(second_root.floor() as u64 + 1);
         Some(num_integers_between)
     }
 }
@@ -139,22 +143,95 @@ pub fn part2(races: &Races) -> u64 {
     races.single_race_ways_to_win()
 }
 
+///
+/// Part 1 by trying every hold time, the baseline the quadratic formula is measured against
+pub fn part1_brute_force(races: &Races) -> u64 {
+    races
+        .races
+        .iter()
+        .map(|race| race.num_ways_to_win_brute_force().unwrap_or(0))
+        .product()
+}
+
+pub fn part2_brute_force(races: &Races) -> u64 {
+    races.single_race.num_ways_to_win_brute_force().unwrap_or(0)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = Races;
+
+    const TITLE: &'static str = "Wait For It";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
 
-    use crate::utils::{get_day_test_input, parse_input};
+    use crate::{
+        testing::{seeded_races, DIFFERENTIAL_SEEDS},
+        utils::{get_day_test_input, try_parse_input},
+    };
 
     use super::*;
 
     #[test]
     fn test_part1() {
-        let races = parse_input(get_day_test_input("day6"));
+        let races = try_parse_input(get_day_test_input("day6")).unwrap();
         assert_eq!(part1(&races), 288);
     }
 
     #[test]
     fn test_part2() {
-        let races = parse_input(get_day_test_input("day6"));
+        let races = try_parse_input(get_day_test_input("day6")).unwrap();
         assert_eq!(part2(&races), 71503);
     }
+
+    #[test]
+    fn test_seeded_inputs_match_brute_force() {
+        for seed in DIFFERENTIAL_SEEDS {
+            let input = seeded_races(seed);
+            let races: Races = input.parse().unwrap();
+            assert_eq!(
+                part1(&races),
+                part1_brute_force(&races),
+                "seed {seed}:\n{input}"
+            );
+            assert_eq!(
+                part2(&races),
+                part2_brute_force(&races),
+                "seed {seed}:\n{input}"
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_num_ways_to_win_matches_brute_force(
+            (race_time, record_distance) in (1u64..2000).prop_flat_map(|time| {
+                // records close to the best possible distance are the interesting ones
+                let best = time * time / 4;
+                (Just(time), prop_oneof![0..=best, best.saturating_sub(2)..=best + 1])
+            }),
+        ) {
+            let race = Race { race_time, record_distance };
+            prop_assert_eq!(
+                race.num_ways_to_win().unwrap_or(0),
+                race.num_ways_to_win_brute_force().unwrap_or(0)
+            );
+        }
+    }
 }
@@ -3,10 +3,12 @@ use std::{cmp::Ordering, str::FromStr};
 use anyhow::Context;
 use itertools::Itertools;
 
+use crate::solution::{Answer, Solution};
+
 // Order is important here because we derive PartialOrd
 // not sure if giving them value is good
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-pub enum Card {
+pub(crate) enum Card {
     J = 0,
     Two = 1,
     Three = 2,
@@ -49,7 +51,7 @@ impl TryFrom<char> for Card {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum HandKind {
     HighCard = 0,
     OnePair = 1,
@@ -103,7 +105,7 @@ impl HandKind {
 
 // They can only be equal if the cards are equal
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Hand {
+pub(crate) struct Hand {
     cards: [Card; 5],
 }
 
@@ -136,6 +138,10 @@ fn hand_kind_exluding_special_j(non_zero: &[i32]) -> HandKind {
 
 impl Hand {
     fn get_hand_kind(&self) -> HandKind {
+        self.get_hand_kind_with_jokers(true)
+    }
+
+    fn get_hand_kind_with_jokers(&self, jokers: bool) -> HandKind {
         let mut card_count = [0; NUM_CARDS];
         let mut num_j = 0;
         for card in &self.cards {
@@ -148,7 +154,12 @@ impl Hand {
 
         let non_zero = card_count.into_iter().filter(|x| *x != 0).collect_vec();
         let hand_kind = hand_kind_exluding_special_j(&non_zero);
-        hand_kind.upgrade_by_j_count(num_j).unwrap()
+        if !jokers {
+            return hand_kind;
+        }
+        hand_kind
+            .upgrade_by_j_count(num_j)
+            .expect("every hand of 5 cards can be upgraded by its jokers")
     }
 }
 
@@ -199,7 +210,7 @@ impl FromStr for Hand {
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct HandBid {
+pub(crate) struct HandBid {
     hand: Hand,
     bid: u32,
 }
@@ -264,6 +275,20 @@ impl FromStr for HandSet {
     }
 }
 
+impl HandSet {
+    ///
+    /// The most frequent hand kind across all hands, preferring the stronger kind on ties
+    pub fn mode_kind(&self, jokers: bool) -> Option<HandKind> {
+        self.hand_bids
+            .iter()
+            .map(|x| x.hand.get_hand_kind_with_jokers(jokers))
+            .counts()
+            .into_iter()
+            .max_by_key(|(hand_kind, count)| (*count, *hand_kind))
+            .map(|(hand_kind, _)| hand_kind)
+    }
+}
+
 pub fn part2(hand_set: &HandSet) -> u32 {
     let mut sorted_hand = hand_set.hand_bids.iter().map(|x| x).collect_vec();
     sorted_hand.sort();
@@ -275,21 +300,59 @@ pub fn part2(hand_set: &HandSet) -> u32 {
         .sum()
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = HandSet;
+
+    const TITLE: &'static str = "Camel Cards";
+
+    const PART1_IMPLEMENTED: bool = false;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(_input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        anyhow::bail!("part 1 of day 7 isn't implemented")
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::{get_day_test_input, parse_input};
+    use crate::utils::{get_day_test_input, try_parse_input};
 
     use super::*;
 
     // #[test]
     // fn test_part1() {
-    //     let hand_set = parse_input(get_day_test_input("day7"));
+    //     let hand_set = try_parse_input(get_day_test_input("day7")).unwrap();
     //     assert_eq!(part1(&hand_set), 6440);
     // }
 
     #[test]
     fn test_part2() {
-        let hand_set = parse_input(get_day_test_input("day7"));
+        let hand_set = try_parse_input(get_day_test_input("day7")).unwrap();
         assert_eq!(part2(&hand_set), 5905);
     }
+
+    #[test]
+    fn test_mode_kind() {
+        let hand_set: HandSet = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483"
+            .parse()
+            .unwrap();
+        assert_eq!(hand_set.mode_kind(true), Some(HandKind::FourOfAKind));
+        assert_eq!(hand_set.mode_kind(false), Some(HandKind::ThreeOfAKind));
+
+        let empty: HandSet = "".parse().unwrap();
+        assert_eq!(empty.mode_kind(true), None);
+    }
 }
@@ -1,5 +1,6 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
+    fmt::Display,
     iter::{Cycle, FusedIterator},
     str::FromStr,
 };
@@ -7,8 +8,13 @@ use std::{
 use anyhow::Context;
 use itertools::Itertools;
 
+use crate::{
+    solution::{Answer, Solution},
+    utils::math::lcm,
+};
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct NodeName([char; 3]);
+pub struct NodeName([char; 3]);
 
 impl FromStr for NodeName {
     type Err = anyhow::Error;
@@ -22,6 +28,12 @@ impl FromStr for NodeName {
     }
 }
 
+impl Display for NodeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|c| write!(f, "{c}"))
+    }
+}
+
 impl NodeName {
     fn ends_with(&self) -> char {
         self.0[2]
@@ -50,8 +62,12 @@ impl FromStr for NodeDescription {
         let second_part = it.next().context("missing left and right nodes")?;
 
         // (BBB, CCC)
-        anyhow::ensure!(second_part.len() > 1);
-        let second_part = &second_part[1..second_part.len() - 1];
+        let second_part = second_part
+            .strip_prefix('(')
+            .and_then(|x| x.strip_suffix(')'))
+            .with_context(|| {
+                format!("left and right nodes aren't in parentheses: {second_part}")
+            })?;
         // BBB, CCC
         let mut it = second_part.split(", ");
         let left = it
@@ -173,7 +189,7 @@ fn create_network_from_node_description_str(node_descriptions: &[&str]) -> anyho
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum Instruction {
+pub(crate) enum Instruction {
     Left,
     Right,
 }
@@ -191,7 +207,7 @@ impl TryFrom<char> for Instruction {
 }
 
 #[derive(Debug)]
-pub struct NetworkNode {
+pub(crate) struct NetworkNode {
     name: NodeName,
     left: Option<usize>,
     right: Option<usize>,
@@ -252,6 +268,29 @@ impl Network {
         }
     }
 
+    ///
+    /// Nodes that can't be reached from the head by any sequence of left and right choices
+    pub fn unreachable_from_head(&self) -> Vec<NodeName> {
+        let mut reachable = vec![false; self.nodes.len()];
+        let mut to_visit: VecDeque<usize> = self.head.into_iter().collect();
+
+        while let Some(index) = to_visit.pop_front() {
+            if reachable[index] {
+                continue;
+            }
+            reachable[index] = true;
+
+            let node = &self.nodes[index];
+            to_visit.extend(node.left.into_iter().chain(node.right));
+        }
+
+        self.nodes
+            .iter()
+            .zip(reachable)
+            .filter_map(|(node, reachable)| (!reachable).then_some(node.name))
+            .collect()
+    }
+
     fn get_heads(&self) -> Vec<&NetworkNode> {
         self.heads
             .iter()
@@ -302,28 +341,16 @@ impl<'a, T> FusedIterator for InfiniteNetworkIterator<'a, T> where
 {
 }
 
-fn lcm(a: u64, b: u64) -> u64 {
-    a * b / gcd(a, b)
-}
-
-fn gcd(a: u64, b: u64) -> u64 {
-    let (mut a, mut b) = (a.max(b), b.min(a));
-
-    while b != 0 {
-        let tmp = b;
-        b = a % b;
-        a = tmp;
-    }
-
-    a
-}
-
 pub struct Map {
     instructions: Vec<Instruction>,
     network: Network,
 }
 
 impl Map {
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+
     fn get_num_steps(&self, target_node: NodeName) -> anyhow::Result<u32> {
         let mut current_node = self.network.get_head().context("missing head")?;
         let mut num_steps = 0;
@@ -405,34 +432,131 @@ impl FromStr for Map {
     }
 }
 
-pub fn part1(map: &Map) -> u32 {
-    map.get_num_steps(NodeName(['Z', 'Z', 'Z'])).unwrap()
+pub fn part1(map: &Map) -> anyhow::Result<u32> {
+    map.get_num_steps(NodeName(['Z', 'Z', 'Z']))
 }
 
 pub fn part2(map: &Map) -> u64 {
     map.get_num_steps_for_all_heads()
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input<'a> = Map;
+
+    const TITLE: &'static str = "Haunted Wasteland";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input.parse()
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
-    use crate::utils::{get_day_extra_test_input, get_day_test_input, parse_input};
+    use crate::{
+        testing::{network_path, seeded_ghost_network, DIFFERENTIAL_SEEDS},
+        utils::{get_day_extra_test_input, get_day_test_input, try_parse_input},
+    };
 
     #[test]
     fn test_part1() {
-        let map = parse_input(get_day_test_input("day8"));
-        assert_eq!(part1(&map), 2);
+        let map = try_parse_input(get_day_test_input("day8")).unwrap();
+        assert_eq!(part1(&map).unwrap(), 2);
     }
 
     #[test]
     fn test_part1_extra() {
-        let map = parse_input(get_day_extra_test_input("day8", 2));
-        assert_eq!(part1(&map), 6);
+        let map = try_parse_input(get_day_extra_test_input("day8", 2)).unwrap();
+        assert_eq!(part1(&map).unwrap(), 6);
     }
 
     #[test]
     fn test_part2() {
-        let map = parse_input(get_day_extra_test_input("day8", 3));
+        let map = try_parse_input(get_day_extra_test_input("day8", 3)).unwrap();
         assert_eq!(part2(&map), 6);
     }
+
+    ///
+    /// Walk all the ghosts at the same time until they're all at an end node
+    fn part2_brute_force(map: &Map) -> u64 {
+        let mut walks: Vec<_> = map
+            .network
+            .get_heads()
+            .into_iter()
+            .map(|head| map.network_iter(head))
+            .collect();
+
+        let mut num_steps = 0;
+        loop {
+            num_steps += 1;
+            let at_end: Vec<bool> = walks
+                .iter_mut()
+                .map(|walk| walk.next().unwrap().is_end_node())
+                .collect();
+            if at_end.into_iter().all(|at_end| at_end) {
+                return num_steps;
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded_inputs_match_brute_force() {
+        for seed in DIFFERENTIAL_SEEDS {
+            let input = seeded_ghost_network(seed);
+            let map: Map = input.parse().unwrap();
+            assert_eq!(
+                part2(&map),
+                part2_brute_force(&map),
+                "seed {seed}:\n{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unreachable_from_head() {
+        let network = create_network_from_node_description_str(&[
+            "AAA = (BBB, BBB)",
+            "BBB = (AAA, ZZZ)",
+            "ZZZ = (ZZZ, ZZZ)",
+            "XXX = (ZZZ, XXX)",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            network.unreachable_from_head(),
+            vec![NodeName(['X', 'X', 'X'])]
+        );
+
+        let map: Map = "LR\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\nXXX = (ZZZ, XXX)"
+            .parse()
+            .unwrap();
+        let unreachable = map.network().unreachable_from_head();
+        assert_eq!(unreachable.iter().join(","), "XXX");
+    }
+
+    #[test]
+    fn test_invalid_node_description() {
+        assert!("AAA = BBB, CCC".parse::<NodeDescription>().is_err());
+        assert!("AAA = ú".parse::<NodeDescription>().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1_follows_the_path((input, len) in network_path(20)) {
+            let map: Map = input.parse().unwrap();
+            prop_assert_eq!(part1(&map).unwrap() as usize, len);
+        }
+    }
 }
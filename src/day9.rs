@@ -3,6 +3,15 @@ use std::str::FromStr;
 use anyhow::Context;
 use itertools::Itertools;
 
+#[cfg(feature = "arena")]
+use crate::utils::arena::ParseContext;
+use crate::{
+    solution::{Answer, Solution},
+    utils::parse_from_lines,
+};
+#[cfg(feature = "arena")]
+use bumpalo::collections::Vec as BumpVec;
+
 pub struct History {
     values: Vec<i32>,
 }
@@ -47,60 +56,155 @@ impl History {
         results
     }
 
+    /// The number of difference levels needed to reach all zeros, which is the degree of the
+    /// polynomial generating the sequence
+    pub fn degree(&self) -> usize {
+        self.get_all_intermidiate_results().len() - 1
+    }
+
     pub fn extrapolate_last_value(&self) -> anyhow::Result<i32> {
-        let results = self.get_all_intermidiate_results();
-        anyhow::ensure!(results.iter().all(|x| x.len() > 0));
-
-        Ok(results.into_iter().rev().fold(0, |previous_diff, current| {
-            previous_diff
-                + current
-                    .last()
-                    .expect("we checked before every vec contains at least one value")
-        }))
+        extrapolate_last_value(&self.get_all_intermidiate_results())
     }
 
     pub fn extrapolate_first_value(&self) -> anyhow::Result<i32> {
-        let results = self.get_all_intermidiate_results();
-        anyhow::ensure!(results.iter().all(|x| x.len() > 0));
-
-        Ok(results.into_iter().rev().fold(0, |previous_diff, current| {
-            current
-                .first()
-                .expect("we checked before every vec contains at least one value")
-                - previous_diff
-        }))
+        extrapolate_first_value(&self.get_all_intermidiate_results())
+    }
+
+    ///
+    /// Like `get_all_intermidiate_results`, but every level is allocated in the arena instead of
+    /// cloned into its own `Vec`
+    #[cfg(feature = "arena")]
+    fn intermediate_results_in<'a>(&'a self, arena: &'a ParseContext) -> BumpVec<'a, &'a [i32]> {
+        let mut results = arena.vec();
+        let mut level = self.values.as_slice();
+        loop {
+            results.push(level);
+            let diffs = arena.alloc_slice_with(level.len().saturating_sub(1), |index| {
+                level[index + 1] - level[index]
+            });
+            if diffs.iter().all(|x| *x == 0) {
+                return results;
+            }
+            level = diffs;
+        }
     }
 }
 
-pub fn part1(history: &[History]) -> i32 {
-    history
-        .iter()
-        .map(|x| x.extrapolate_last_value().unwrap())
-        .sum()
+fn extrapolate_last_value<L: AsRef<[i32]>>(results: &[L]) -> anyhow::Result<i32> {
+    anyhow::ensure!(results.iter().all(|x| !x.as_ref().is_empty()));
+
+    Ok(results.iter().rev().fold(0, |previous_diff, current| {
+        previous_diff
+            + current
+                .as_ref()
+                .last()
+                .expect("we checked before every level contains at least one value")
+    }))
 }
 
-pub fn part2(history: &[History]) -> i32 {
-    history
-        .iter()
-        .map(|x| x.extrapolate_first_value().unwrap())
-        .sum()
+fn extrapolate_first_value<L: AsRef<[i32]>>(results: &[L]) -> anyhow::Result<i32> {
+    anyhow::ensure!(results.iter().all(|x| !x.as_ref().is_empty()));
+
+    Ok(results.iter().rev().fold(0, |previous_diff, current| {
+        current
+            .as_ref()
+            .first()
+            .expect("we checked before every level contains at least one value")
+            - previous_diff
+    }))
+}
+
+pub fn part1(history: &[History]) -> anyhow::Result<i32> {
+    history.iter().map(|x| x.extrapolate_last_value()).sum()
+}
+
+pub fn part2(history: &[History]) -> anyhow::Result<i32> {
+    history.iter().map(|x| x.extrapolate_first_value()).sum()
+}
+
+///
+/// Sum the extrapolated values with a single arena, reset after every history
+#[cfg(feature = "arena")]
+fn sum_in_arena(
+    history: &[History],
+    extrapolate: fn(&[&[i32]]) -> anyhow::Result<i32>,
+) -> anyhow::Result<i32> {
+    let mut arena = ParseContext::new();
+    let mut sum = 0;
+    for x in history {
+        sum += extrapolate(&x.intermediate_results_in(&arena))?;
+        arena.reset();
+    }
+    Ok(sum)
+}
+
+#[cfg(feature = "arena")]
+pub fn part1_arena(history: &[History]) -> anyhow::Result<i32> {
+    sum_in_arena(history, |results| extrapolate_last_value(results))
+}
+
+#[cfg(feature = "arena")]
+pub fn part2_arena(history: &[History]) -> anyhow::Result<i32> {
+    sum_in_arena(history, |results| extrapolate_first_value(results))
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input<'a> = Vec<History>;
+
+    const TITLE: &'static str = "Mirage Maintenance";
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        parse_from_lines(input.lines())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+        Ok(part2(input)?.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::{get_day_test_input, parse_input_lines};
+    use crate::utils::{get_day_test_input, parse_input_streaming};
 
     use super::*;
 
     #[test]
     fn test_part1() {
-        let history = parse_input_lines(get_day_test_input("day9"));
-        assert_eq!(part1(&history), 114);
+        let history: Vec<History> = parse_input_streaming(get_day_test_input("day9")).unwrap();
+        assert_eq!(part1(&history).unwrap(), 114);
     }
 
     #[test]
     fn test_part2() {
-        let history = parse_input_lines(get_day_test_input("day9"));
-        assert_eq!(part2(&history), 2);
+        let history: Vec<History> = parse_input_streaming(get_day_test_input("day9")).unwrap();
+        assert_eq!(part2(&history).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_degree() {
+        let linear: History = "0 3 6 9 12 15".parse().unwrap();
+        assert_eq!(linear.degree(), 1);
+
+        let quadratic: History = "1 3 6 10 15 21".parse().unwrap();
+        assert_eq!(quadratic.degree(), 2);
+
+        let constant: History = "7 7 7".parse().unwrap();
+        assert_eq!(constant.degree(), 0);
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn test_arena_matches_heap() {
+        let history: Vec<History> =
+            parse_from_lines(["0 3 6 9 12 15", "1 3 6 10 15 21", "7 7 7"]).unwrap();
+        assert_eq!(part1_arena(&history).unwrap(), part1(&history).unwrap());
+        assert_eq!(part2_arena(&history).unwrap(), part2(&history).unwrap());
+        assert_eq!(part1_arena(&history).unwrap(), 18 + 28 + 7);
     }
 }
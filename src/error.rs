@@ -0,0 +1,162 @@
+use std::{fmt::Display, io, path::PathBuf};
+
+use crate::solution::Part;
+
+///
+/// The errors returned at the library boundary, so consumers can tell what went wrong without
+/// matching on messages. Internally everything is still `anyhow`, the original error is kept as
+/// the source.
+///
+#[derive(Debug, thiserror::Error)]
+pub enum AocError {
+    #[error("failed to parse input for day {day}{}", Location(*line, *column))]
+    ParseError {
+        day: u32,
+        /// 1-based, when the parser reported where it failed
+        line: Option<usize>,
+        column: Option<usize>,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("failed to read {}", path.display())]
+    IoError {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to solve day {day} part {part}")]
+    UnsolvableError {
+        day: u32,
+        part: Part,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("day {0} isn't implemented")]
+    UnsupportedDay(u32),
+}
+
+impl AocError {
+    ///
+    /// A parse error for a day, with the location taken from a `ParseLocation` in the error chain,
+    /// if any
+    pub fn parse(day: u32, source: anyhow::Error) -> Self {
+        let location = source.downcast_ref::<ParseLocation>();
+
+        Self::ParseError {
+            day,
+            line: location.map(|location| location.line),
+            column: location.and_then(|location| location.column),
+            source,
+        }
+    }
+}
+
+struct Location(Option<usize>, Option<usize>);
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location(Some(line), Some(column)) => write!(f, " at line {line}, column {column}"),
+            Location(Some(line), None) => write!(f, " at line {line}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+///
+/// Context attached by the shared parsers saying where in the input parsing failed, so it can be
+/// recovered from an `anyhow::Error`. Lines and columns are 1-based.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLocation {
+    pub line: usize,
+    pub column: Option<usize>,
+    content: String,
+}
+
+impl ParseLocation {
+    pub fn line(line: usize, content: &str) -> Self {
+        Self {
+            line,
+            column: None,
+            content: content.to_string(),
+        }
+    }
+
+    pub fn cell(line: usize, column: usize, c: char) -> Self {
+        Self {
+            line,
+            column: Some(column),
+            content: c.to_string(),
+        }
+    }
+}
+
+impl Display for ParseLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(column) => write!(
+                f,
+                "invalid cell {:?} at line {}, column {column}",
+                self.content, self.line
+            ),
+            None => write!(f, "failed to parse line {}: {}", self.line, self.content),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{solve, utils::grid::Grid};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_location() {
+        let error = solve(4, Part::One, "Card 1: 1 2 | 3 4\nCard 2: 1 x | 3 4\n").unwrap_err();
+        assert!(matches!(
+            error,
+            AocError::ParseError {
+                day: 4,
+                line: Some(2),
+                column: None,
+                ..
+            }
+        ));
+        assert_eq!(
+            error.to_string(),
+            "failed to parse input for day 4 at line 2"
+        );
+
+        let grid = Grid::parse_chars("..\n.x", |c| match c {
+            '.' => Ok(()),
+            _ => anyhow::bail!("unknown cell"),
+        });
+        let error = AocError::parse(10, grid.unwrap_err());
+        assert!(matches!(
+            error,
+            AocError::ParseError {
+                line: Some(2),
+                column: Some(2),
+                ..
+            }
+        ));
+        assert_eq!(
+            format!("{:#}", anyhow::Error::from(error)),
+            "failed to parse input for day 10 at line 2, column 2: invalid cell \"x\" at line 2, \
+             column 2: unknown cell"
+        );
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert!(matches!(
+            solve(26, Part::One, ""),
+            Err(AocError::UnsupportedDay(26))
+        ));
+        assert!(matches!(
+            solve(6, Part::One, "Time: x"),
+            Err(AocError::ParseError { day: 6, .. })
+        ));
+    }
+}
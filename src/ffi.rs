@@ -0,0 +1,156 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    slice, str,
+};
+
+use crate::{error::AocError, solution::Part};
+
+pub const AOC_OK: i32 = 0;
+/// A pointer argument was null
+pub const AOC_NULL_POINTER: i32 = -1;
+/// The input isn't valid utf-8
+pub const AOC_INVALID_INPUT: i32 = -2;
+pub const AOC_UNKNOWN_DAY: i32 = -3;
+pub const AOC_INVALID_PART: i32 = -4;
+/// Parsing the input or solving the part failed, the error message is written as the output
+pub const AOC_SOLVE_FAILED: i32 = -5;
+/// The output doesn't fit, `out_len` is set to the required length and nothing is written
+pub const AOC_BUFFER_TOO_SMALL: i32 = -6;
+/// The solver panicked
+pub const AOC_PANIC: i32 = -7;
+
+fn write_output(output: &str, out_buf: &mut [u8], out_len: &mut usize) -> bool {
+    *out_len = output.len();
+    match out_buf.get_mut(..output.len()) {
+        Some(buf) => {
+            buf.copy_from_slice(output.as_bytes());
+            true
+        }
+        None => false,
+    }
+}
+
+fn solve_into(day: u32, part: u32, input: &[u8], out_buf: &mut [u8], out_len: &mut usize) -> i32 {
+    let Ok(input) = str::from_utf8(input) else {
+        return AOC_INVALID_INPUT;
+    };
+    let Some(part) = u8::try_from(part)
+        .ok()
+        .and_then(|part| Part::try_from(part).ok())
+    else {
+        return AOC_INVALID_PART;
+    };
+
+    let (code, output) = match crate::solve(day, part, input) {
+        Ok(answer) => (AOC_OK, answer),
+        Err(AocError::UnsupportedDay(_)) => return AOC_UNKNOWN_DAY,
+        Err(e) => (AOC_SOLVE_FAILED, format!("{:#}", anyhow::Error::from(e))),
+    };
+    if !write_output(&output, out_buf, out_len) {
+        return AOC_BUFFER_TOO_SMALL;
+    }
+
+    code
+}
+
+///
+/// Solve a part of a day from C. `out_len` holds the size of `out_buf` and is set to the length
+/// of the answer, which isn't nul terminated. On `AOC_SOLVE_FAILED` the error message is written
+/// instead of the answer.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `input_len` readable bytes, `out_buf` to `*out_len` writable bytes,
+/// and `out_len` must be a valid pointer.
+///
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if input_ptr.is_null() || out_buf.is_null() || out_len.is_null() {
+        return AOC_NULL_POINTER;
+    }
+
+    let input = slice::from_raw_parts(input_ptr, input_len);
+    let out_buf = slice::from_raw_parts_mut(out_buf, *out_len);
+    let out_len = &mut *out_len;
+
+    // unwinding into C is undefined behaviour
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        solve_into(day, part, input, out_buf, out_len)
+    }))
+    .unwrap_or(AOC_PANIC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+    fn call_solve(day: u32, part: u32, input: &[u8], capacity: usize) -> (i32, String) {
+        let mut out_buf = vec![0u8; capacity.max(1)];
+        let mut out_len = capacity;
+        let code = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out_buf.as_mut_ptr(),
+                &mut out_len,
+            )
+        };
+        let output = match code {
+            AOC_OK | AOC_SOLVE_FAILED => String::from_utf8_lossy(&out_buf[..out_len]).to_string(),
+            _ => out_len.to_string(),
+        };
+        (code, output)
+    }
+
+    #[test]
+    fn test_aoc_solve() {
+        assert_eq!(
+            call_solve(6, 1, INPUT.as_bytes(), 64),
+            (AOC_OK, "288".to_string())
+        );
+        assert_eq!(
+            call_solve(6, 2, INPUT.as_bytes(), 64),
+            (AOC_OK, "71503".to_string())
+        );
+        // the required length is reported back
+        assert_eq!(
+            call_solve(6, 2, INPUT.as_bytes(), 2),
+            (AOC_BUFFER_TOO_SMALL, "5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_aoc_solve_errors() {
+        assert_eq!(call_solve(26, 1, INPUT.as_bytes(), 64).0, AOC_UNKNOWN_DAY);
+        assert_eq!(call_solve(6, 3, INPUT.as_bytes(), 64).0, AOC_INVALID_PART);
+        assert_eq!(call_solve(6, 1, &[0xff, 0xfe], 64).0, AOC_INVALID_INPUT);
+
+        let (code, message) = call_solve(6, 1, b"Time: x", 256);
+        assert_eq!(code, AOC_SOLVE_FAILED);
+        assert!(!message.is_empty());
+
+        let mut out_len = 0;
+        let code = unsafe {
+            aoc_solve(
+                6,
+                1,
+                std::ptr::null(),
+                0,
+                std::ptr::null_mut(),
+                &mut out_len,
+            )
+        };
+        assert_eq!(code, AOC_NULL_POINTER);
+    }
+}
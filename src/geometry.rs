@@ -0,0 +1,54 @@
+///
+/// The area enclosed by a closed polygon, given its vertices in order (either orientation).
+/// The last vertex is implicitly connected back to the first one. The cross products are summed
+/// as `i128`, since they overflow an `i64` long before the area does once coordinates reach the
+/// billions.
+///
+/// Panics if the area itself doesn't fit in an `i64`.
+///
+pub fn shoelace_area(polygon: &[(i64, i64)]) -> i64 {
+    let twice_area: i128 = polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| {
+            i128::from(x1) * i128::from(y2) - i128::from(x2) * i128::from(y1)
+        })
+        .sum();
+
+    (twice_area.abs() / 2)
+        .try_into()
+        .expect("polygon area overflows i64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shoelace_area_rectangle() {
+        let rectangle = [(0, 0), (4, 0), (4, 3), (0, 3)];
+        assert_eq!(shoelace_area(&rectangle), 12);
+
+        let reversed = [(0, 3), (4, 3), (4, 0), (0, 0)];
+        assert_eq!(shoelace_area(&reversed), 12);
+    }
+
+    #[test]
+    fn test_shoelace_area_large_coordinates() {
+        // the cross products overflow an i64 even though the area is tiny
+        let offset = 4_000_000_000;
+        let square = [
+            (offset, offset),
+            (offset + 10, offset),
+            (offset + 10, offset + 10),
+            (offset, offset + 10),
+        ];
+        assert_eq!(shoelace_area(&square), 100);
+    }
+
+    #[test]
+    fn test_shoelace_area_degenerate() {
+        assert_eq!(shoelace_area(&[]), 0);
+        assert_eq!(shoelace_area(&[(1, 1), (5, 1)]), 0);
+    }
+}
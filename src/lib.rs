@@ -1,16 +1,138 @@
-// mod day1;
-// pub mod day2;
-// pub mod day3;
-// pub mod day4;
-// pub mod day5;
-pub mod day6;
-// pub mod day7;
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+pub mod day1;
 pub mod day10;
 pub mod day11;
+pub mod day12;
 pub mod day13;
+pub mod day14;
 pub mod day15;
 pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
 pub mod day8;
 pub mod day9;
 
+// the command line tooling reads inputs from disk and talks to the website
+#[cfg(feature = "bench")]
+pub mod bench;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cache;
+pub mod check;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod config;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod gen;
+pub mod geometry;
+pub mod memory;
+pub mod prelude;
+#[cfg(feature = "profile")]
+pub mod profile;
+pub mod progress;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod report;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod runner;
+#[cfg(feature = "serve")]
+pub mod server;
+pub mod solution;
+#[cfg(test)]
+pub mod testing;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod threads;
+#[cfg(feature = "tui")]
+pub mod tui;
 pub mod utils;
+pub mod verify;
+pub mod viz;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use std::collections::BTreeMap;
+
+use error::AocError;
+use solution::{DayMetadata, DynSolution, Part};
+
+///
+/// Every implemented day, keyed by its day number
+///
+pub fn registry() -> BTreeMap<u32, Box<dyn DynSolution>> {
+    let days: [(u32, Box<dyn DynSolution>); 25] = [
+        (1, Box::new(day1::Day1)),
+        (2, Box::new(day2::Day2)),
+        (3, Box::new(day3::Day3)),
+        (4, Box::new(day4::Day4)),
+        (5, Box::new(day5::Day5)),
+        (6, Box::new(day6::Day6)),
+        (7, Box::new(day7::Day7)),
+        (8, Box::new(day8::Day8)),
+        (9, Box::new(day9::Day9)),
+        (10, Box::new(day10::Day10)),
+        (11, Box::new(day11::Day11)),
+        (12, Box::new(day12::Day12)),
+        (13, Box::new(day13::Day13)),
+        (14, Box::new(day14::Day14)),
+        (15, Box::new(day15::Day15)),
+        (16, Box::new(day16::Day16)),
+        (17, Box::new(day17::Day17)),
+        (18, Box::new(day18::Day18)),
+        (19, Box::new(day19::Day19)),
+        (20, Box::new(day20::Day20)),
+        (21, Box::new(day21::Day21)),
+        (22, Box::new(day22::Day22)),
+        (23, Box::new(day23::Day23)),
+        (24, Box::new(day24::Day24)),
+        (25, Box::new(day25::Day25)),
+    ];
+
+    BTreeMap::from(days)
+}
+
+pub fn get_solution(day: u32) -> Option<Box<dyn DynSolution>> {
+    registry().remove(&day)
+}
+
+///
+/// The title and progress of every implemented day, in order
+pub fn metadata() -> Vec<DayMetadata> {
+    registry()
+        .iter()
+        .map(|(&day, solution)| DayMetadata::new(day, solution.as_ref()))
+        .collect()
+}
+
+pub fn day_metadata(day: u32) -> Option<DayMetadata> {
+    get_solution(day).map(|solution| DayMetadata::new(day, solution.as_ref()))
+}
+
+///
+/// Solve a single part of a day, a plain string in, string out entry point for embedding the
+/// solvers. Unlike `DynSolution::run` nothing is timed, since there may not be a clock.
+///
+pub fn solve(day: u32, part: Part, input: &str) -> Result<String, AocError> {
+    let solution = get_solution(day).ok_or(AocError::UnsupportedDay(day))?;
+    let prepared = solution
+        .prepare(input)
+        .map_err(|e| AocError::parse(day, e))?;
+    let answer =
+        prepared(part).map_err(|source| AocError::UnsolvableError { day, part, source })?;
+    Ok(answer.to_string())
+}
@@ -1,11 +1,717 @@
-use std::fs::read_to_string;
+#[cfg(feature = "serve")]
+use std::net::SocketAddr;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-use aoc::utils::{get_day_input, parse_input};
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+#[cfg(feature = "bench")]
+use criterion::Criterion;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use owo_colors::{OwoColorize, Stream};
+use tracing_subscriber::EnvFilter;
 
-use aoc::day16;
+use aoc::{
+    cache::{code_fingerprint, Cache, CACHE_FILE},
+    check::check,
+    config::Config,
+    day2::{self, CubeConstraints, GameSet},
+    gen::generate,
+    get_solution,
+    memory::format_bytes,
+    metadata,
+    progress::{self, ProgressHook, ProgressTask},
+    registry,
+    report::{render_report, visualize_day, DayReport, ReportFormat},
+    runner::{
+        format_completion_matrix, format_duration, format_summary_table, read_day_input, run_all,
+        run_day_with_input, run_days,
+    },
+    solution::{Answer, DayRun, Part},
+    threads::init_thread_pool,
+    utils::{read_input_file, set_input_dir},
+    verify::{compare, verify, ExpectedAnswers, VerifyEntry, VerifyStatus, ANSWERS_FILE},
+    viz::{write_viz, VizFormat, VIZ_DIR},
+};
 
-fn main() {
-    let contraption = parse_input(get_day_input("day16"));
-    println!("{}", day16::part1(&contraption));
-    println!("{}", day16::part2(&contraption));
+#[cfg(feature = "bench")]
+use aoc::bench::{bench_comparisons, bench_days, compare_to_baseline, BENCH_DIR};
+#[cfg(feature = "profile")]
+use aoc::profile::{write_flamegraph, PROFILE_DIR};
+#[cfg(feature = "serve")]
+use aoc::server;
+#[cfg(feature = "tui")]
+use aoc::tui::run_tui;
+#[cfg(feature = "client")]
+use aoc::{client::AocClient, runner::run_day, utils::get_day_input};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "aoc",
+    about = "Run the Advent of Code 2023 solutions",
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The day to run, either as `day8` or `8`
+    target: Option<String>,
+
+    /// The day to run
+    #[arg(short, long, conflicts_with = "target")]
+    day: Option<u32>,
+
+    /// Run against this input file instead of the day's actual input
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Only run this part, otherwise both parts are run
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Also print how long parsing the input and solving each part took
+    #[arg(short, long)]
+    time: bool,
+
+    /// Also draw the day's input into the viz directory, for the days that support it
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "ascii")]
+    viz: Option<VizFormat>,
+
+    /// Answer day 2 part 1 for a bag with these cubes instead, e.g. `--bag "7 red, 9 blue"`
+    #[arg(long, value_name = "CUBES")]
+    bag: Option<CubeConstraints>,
+
+    /// Return the answers of a previous run when the input and code haven't changed since,
+    /// and remember the new answers
+    #[arg(long, global = true)]
+    cached: bool,
+
+    /// Print debug logs, `RUST_LOG` can be used instead for finer control
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Show progress bars for the long running parts
+    #[arg(long, global = true)]
+    progress: bool,
+
+    /// How many threads the parallel solvers and the days run on, otherwise `AOC_THREADS` or a
+    /// thread per core
+    #[arg(long, global = true)]
+    threads: Option<usize>,
+
+    /// The config file, otherwise the first aoc.toml in the working directory or its parents
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run every implemented day and print a summary table with timings
+    All,
+    /// Solve a part and submit the answer to adventofcode.com, using the session token from
+    /// the AOC_SESSION environment variable or the config's session file
+    #[cfg(feature = "client")]
+    Submit {
+        #[arg(short, long)]
+        day: u32,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+    /// Download a day's input into the input directory, using the same session token as submit
+    #[cfg(feature = "client")]
+    Download {
+        #[arg(short, long)]
+        day: u32,
+
+        /// Download the input even if it was already downloaded
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Run the selected days and render a report with the answers and timings
+    Report {
+        /// The days to include, every implemented day if none are given
+        #[arg(short, long)]
+        day: Vec<u32>,
+
+        /// Markdown unless the config sets another format
+        #[arg(short, long, value_enum)]
+        format: Option<ReportFormat>,
+
+        /// Include drawings of the input for the days that support them
+        #[arg(long)]
+        visualize: bool,
+
+        /// Write the report to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Run the days in the answers file and check the answers match
+    Verify {
+        /// A toml file with a `[dayN]` table per day, holding `part1` and `part2` answers
+        #[arg(short, long, default_value = ANSWERS_FILE)]
+        answers: PathBuf,
+    },
+    /// Benchmark the selected days with criterion, optionally saving the timings as a named
+    /// baseline or checking them against one
+    #[cfg(feature = "bench")]
+    Bench {
+        /// The days to benchmark, every implemented day if none are given
+        #[arg(short, long)]
+        day: Vec<u32>,
+
+        /// Save the timings as this baseline
+        #[arg(long, conflicts_with = "compare")]
+        save_baseline: Option<String>,
+
+        /// Compare the timings against this baseline, failing if a part regressed
+        #[arg(long)]
+        compare: Option<String>,
+
+        /// How many percent slower than the baseline a part may get before it's a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+
+        /// Also benchmark the alternative solutions of the days which have them, like brute
+        /// force, side by side with the optimized ones
+        #[arg(long)]
+        strategies: bool,
+
+        /// Take fewer samples of the days with a slow part
+        #[arg(long)]
+        quick: bool,
+    },
+    /// Generate a pseudo-random input for a day, scaled beyond the size of the real inputs
+    Gen {
+        #[arg(short, long)]
+        day: u32,
+
+        /// The size of the grid for the grid days, otherwise the number of lines
+        #[arg(short, long)]
+        scale: usize,
+
+        /// The same seed always generates the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Write the input to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Profile solving a part and write a flamegraph into the profile directory
+    #[cfg(feature = "profile")]
+    Profile {
+        #[arg(short, long)]
+        day: u32,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Profile against this file instead of the day's actual input
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// List every day of the calendar with its title and which parts are implemented
+    List,
+    /// Only parse a day's input, printing its shape and any parse errors without solving
+    Check {
+        #[arg(short, long)]
+        day: u32,
+
+        /// Check this file instead of the day's actual input
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Start an HTTP server solving `POST /solve/{day}/{part}` requests, with the raw input as
+    /// the body, and responding with the answer and timings as JSON
+    #[cfg(feature = "serve")]
+    Serve {
+        #[arg(short, long, default_value = "127.0.0.1:3000")]
+        addr: SocketAddr,
+    },
+    /// Show a dashboard of every day with its answers and timings, checked against the answers
+    /// file when it exists
+    #[cfg(feature = "tui")]
+    Tui {
+        #[arg(short, long, default_value = ANSWERS_FILE)]
+        answers: PathBuf,
+    },
+}
+
+fn parse_target(target: &str) -> anyhow::Result<u32> {
+    let day = target.strip_prefix("day").unwrap_or(target);
+    day.parse()
+        .with_context(|| format!("invalid day: {target}, expected something like day8 or 8"))
+}
+
+fn get_parts(part: Option<u8>) -> anyhow::Result<Vec<Part>> {
+    match part {
+        Some(part) => Ok(vec![part.try_into()?]),
+        None => Ok(Part::ALL.to_vec()),
+    }
+}
+
+///
+/// Run through the answers cache, saving it afterwards
+fn with_cache<T>(run: impl FnOnce(&mut Cache, &str) -> T) -> anyhow::Result<T> {
+    let path = Path::new(CACHE_FILE);
+    let mut cache = Cache::load(path)?;
+    let result = run(&mut cache, &code_fingerprint());
+    cache.save(path)?;
+
+    Ok(result)
+}
+
+fn run_days_cached(
+    days: &[u32],
+    parts: &[Part],
+) -> anyhow::Result<Vec<(u32, anyhow::Result<DayRun>)>> {
+    with_cache(|cache, code_hash| {
+        days.iter()
+            .map(|&day| (day, cache.run_day(day, parts, code_hash)))
+            .collect()
+    })
+}
+
+///
+/// The answer in green when it matches the expected one, otherwise in red followed by the expected
+/// answer. Without an expected answer it's left as is.
+fn format_answer(answer: &Answer, status: Option<&VerifyStatus>) -> String {
+    match status {
+        Some(VerifyStatus::Pass) => answer
+            .if_supports_color(Stream::Stdout, |x| x.green())
+            .to_string(),
+        Some(VerifyStatus::Fail { expected, actual }) => format!(
+            "{} (expected {})",
+            actual.if_supports_color(Stream::Stdout, |x| x.red()),
+            expected.if_supports_color(Stream::Stdout, |x| x.green())
+        ),
+        Some(VerifyStatus::Error(_)) | None => answer.to_string(),
+    }
+}
+
+fn format_entry(entry: &VerifyEntry) -> String {
+    match entry.status {
+        VerifyStatus::Pass => entry
+            .if_supports_color(Stream::Stdout, |x| x.green())
+            .to_string(),
+        VerifyStatus::Fail { .. } => entry
+            .if_supports_color(Stream::Stdout, |x| x.red())
+            .to_string(),
+        VerifyStatus::Error(_) => entry
+            .if_supports_color(Stream::Stdout, |x| x.yellow())
+            .to_string(),
+    }
+}
+
+fn run_single_day(cli: &Cli) -> anyhow::Result<()> {
+    let day = match (&cli.target, cli.day) {
+        (Some(target), None) => parse_target(target)?,
+        (None, Some(day)) => day,
+        _ => anyhow::bail!("specify a day to run, e.g. `aoc day8` or `aoc --day 8`"),
+    };
+
+    // check the day exists first, so unimplemented days don't complain about a missing input
+    let solution = get_solution(day).with_context(|| format!("day {day} isn't implemented"))?;
+    let input = match &cli.input {
+        Some(path) => read_input_file(path)?,
+        None => read_day_input(day)?,
+    };
+
+    if let Some(bag) = &cli.bag {
+        anyhow::ensure!(day == 2, "--bag only applies to day 2");
+        let games: GameSet = input.parse()?;
+        let answer = day2::part1_with_constraints(&games, bag);
+        println!("day 2 part 1 with {bag}: {answer}");
+        return Ok(());
+    }
+
+    let mut parts = get_parts(cli.part)?;
+    // without a part to run, a day with a single part doesn't fail on the other's placeholder
+    if cli.part.is_none() {
+        parts.retain(|&part| solution.implemented(part));
+    }
+    let day_run = if cli.cached {
+        with_cache(|cache, code_hash| cache.run_day_with_input(day, &input, &parts, code_hash))??
+    } else {
+        run_day_with_input(day, &input, &parts)?
+    };
+    if cli.time {
+        println!("day {day} parse: {}", format_duration(day_run.parse_time));
+    }
+
+    // only the actual input's answers are known
+    let expected = match cli.input {
+        Some(_) => None,
+        None => ExpectedAnswers::load_if_exists(Path::new(ANSWERS_FILE))?,
+    };
+    let mut mismatches = 0;
+    let mut failures = 0;
+    for part_run in day_run.parts {
+        let status = expected
+            .as_ref()
+            .and_then(|x| x.get(day, part_run.part))
+            .zip(part_run.answer.as_ref().ok())
+            .map(|(expected, actual)| compare(expected, actual));
+        if matches!(status, Some(VerifyStatus::Fail { .. })) {
+            mismatches += 1;
+        }
+        let answer = match &part_run.answer {
+            Ok(answer) => format_answer(answer, status.as_ref()),
+            Err(e) => {
+                failures += 1;
+                format!("error: {e:#}")
+            }
+        };
+        if cli.time {
+            let memory = part_run
+                .peak_memory
+                .map(|x| format!(", peak memory: {}", format_bytes(x)))
+                .unwrap_or_default();
+            println!(
+                "day {day} part {}: {answer} (solve: {}{memory})",
+                part_run.part,
+                format_duration(part_run.solve_time)
+            );
+        } else {
+            println!("day {day} part {}: {answer}", part_run.part);
+        }
+    }
+
+    if let Some(format) = cli.viz {
+        let path = write_viz(day, &input, format, Path::new(VIZ_DIR))?;
+        println!("day {day} visualization: {}", path.display());
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{failures} parts of day {day} failed");
+    }
+    if mismatches > 0 {
+        anyhow::bail!("{mismatches} answers didn't match {ANSWERS_FILE}");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "client")]
+fn submit(day: u32, part: u8, session_file: Option<&Path>) -> anyhow::Result<()> {
+    let part: Part = part.try_into()?;
+    let client = AocClient::from_env_or_file(session_file)?;
+
+    let day_run = run_day(day, &[part])?;
+    let answer = day_run
+        .parts
+        .into_iter()
+        .next()
+        .context("no answer was produced")?
+        .answer?;
+
+    println!("submitting {answer} for day {day} part {part}");
+    let result = client.submit_answer(day, part, &answer.to_string())?;
+    println!("{result}");
+
+    Ok(())
+}
+
+#[cfg(feature = "client")]
+fn download(day: u32, force: bool, session_file: Option<&Path>) -> anyhow::Result<()> {
+    let existing = get_day_input(&format!("day{day}"));
+    if existing.exists() && !force {
+        println!("{} already exists", existing.display());
+        return Ok(());
+    }
+
+    let client = AocClient::from_env_or_file(session_file)?;
+    let path = client.download_input(day)?;
+    println!("downloaded the input for day {day} to {}", path.display());
+
+    Ok(())
+}
+
+fn report(
+    days: Vec<u32>,
+    format: ReportFormat,
+    visualize: bool,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let days = if days.is_empty() {
+        registry().into_keys().collect()
+    } else {
+        days
+    };
+
+    let day_reports: Vec<DayReport> = run_days(&days, &Part::ALL)
+        .into_iter()
+        .map(|(day, run)| DayReport {
+            day,
+            run,
+            visualization: if visualize { visualize_day(day) } else { None },
+        })
+        .collect();
+
+    let report = render_report(&day_reports, format);
+    match output {
+        Some(path) => fs::write(&path, report)
+            .with_context(|| format!("failed to write report to {}", path.display())),
+        None => {
+            print!("{report}");
+            Ok(())
+        }
+    }
+}
+
+fn verify_answers(answers: &Path, cached: bool) -> anyhow::Result<()> {
+    let expected = ExpectedAnswers::load(answers)?;
+    let days = expected.days();
+    let results = if cached {
+        run_days_cached(&days, &Part::ALL)?
+    } else {
+        run_days(&days, &Part::ALL)
+    };
+
+    let entries = verify(&expected, &results);
+    for entry in &entries {
+        println!("{}", format_entry(entry));
+    }
+
+    let failed = entries
+        .iter()
+        .filter(|x| x.status != VerifyStatus::Pass)
+        .count();
+    println!("{} passed, {failed} failed", entries.len() - failed);
+    if failed > 0 {
+        anyhow::bail!("{failed} answers didn't match");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "bench")]
+fn bench(
+    days: Vec<u32>,
+    save_baseline: Option<String>,
+    compare: Option<String>,
+    threshold: f64,
+    strategies: bool,
+    quick: bool,
+) -> anyhow::Result<()> {
+    let days = if days.is_empty() {
+        registry().into_keys().collect()
+    } else {
+        days
+    };
+
+    let dir = Path::new(BENCH_DIR);
+    // the output directory is set explicitly, since criterion only finds the target directory
+    // by itself when run through cargo
+    let mut criterion = Criterion::default().output_directory(dir);
+    if let Some(baseline) = save_baseline {
+        criterion = criterion.save_baseline(baseline);
+    }
+    if let Some(baseline) = &compare {
+        criterion = criterion.retain_baseline(baseline.clone(), false);
+    }
+    let parts = bench_days(&mut criterion, &days, quick);
+    if strategies {
+        bench_comparisons(&mut criterion, &days, quick);
+    }
+    criterion.final_summary();
+
+    let Some(baseline) = compare else {
+        return Ok(());
+    };
+    let comparisons = compare_to_baseline(dir, &parts, &baseline)?;
+    println!("compared to {baseline}:");
+    for comparison in &comparisons {
+        println!("{comparison}");
+    }
+
+    let regressed: Vec<String> = comparisons
+        .iter()
+        .filter(|x| x.regressed(threshold))
+        .map(|x| format!("day {} part {}", x.day, x.part))
+        .collect();
+    if !regressed.is_empty() {
+        anyhow::bail!(
+            "{} regressed by more than {threshold}%",
+            regressed.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+fn gen_input(day: u32, scale: usize, seed: u64, output: Option<PathBuf>) -> anyhow::Result<()> {
+    let input = generate(day, scale, seed)?;
+    match output {
+        Some(path) => fs::write(&path, input)
+            .with_context(|| format!("failed to write input to {}", path.display())),
+        None => {
+            print!("{input}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "profile")]
+fn profile(day: u32, part: u8, input: Option<&Path>) -> anyhow::Result<()> {
+    let part: Part = part.try_into()?;
+    let input = match input {
+        Some(path) => read_input_file(path)?,
+        None => read_day_input(day)?,
+    };
+
+    let (path, runs) = write_flamegraph(day, part, &input, Path::new(PROFILE_DIR))?;
+    println!(
+        "day {day} part {part} flamegraph over {runs} runs: {}",
+        path.display()
+    );
+
+    Ok(())
+}
+
+fn check_input(day: u32, input: Option<&Path>) -> anyhow::Result<()> {
+    let input = match input {
+        Some(path) => read_input_file(path)?,
+        None => read_day_input(day)?,
+    };
+
+    let report = check(day, &input)?;
+    println!("{report}");
+    if report.parse.is_err() {
+        anyhow::bail!("day {day} input failed to parse");
+    }
+
+    Ok(())
+}
+
+///
+/// Logs go to stderr so they don't mix with the answers. `RUST_LOG` takes precedence over
+/// `--verbose`, otherwise only warnings are shown.
+fn init_tracing(verbose: bool) {
+    let default_level = if verbose { "debug" } else { "warn" };
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
+struct IndicatifHook {
+    bars: MultiProgress,
+}
+
+struct IndicatifTask(ProgressBar);
+
+impl ProgressHook for IndicatifHook {
+    fn start(&self, label: &str, total: u64) -> Box<dyn ProgressTask> {
+        let bar = self.bars.add(ProgressBar::new(total));
+        bar.set_style(
+            ProgressStyle::with_template("{prefix} [{bar:40}] {human_pos}/{human_len} ({eta})")
+                .expect("the template is valid")
+                .progress_chars("=> "),
+        );
+        bar.set_prefix(label.to_string());
+        Box::new(IndicatifTask(bar))
+    }
+}
+
+impl ProgressTask for IndicatifTask {
+    fn inc(&self, delta: u64) {
+        self.0.inc(delta);
+    }
+
+    fn finish(&self) {
+        self.0.finish_and_clear();
+    }
+}
+
+///
+/// Apply the settings that are global to every command
+fn apply_config(config: &Config, threads: Option<usize>) -> anyhow::Result<()> {
+    if let Some(input_dir) = &config.input_dir {
+        set_input_dir(input_dir.clone())?;
+    }
+    init_thread_pool(threads, config.threads)?;
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    init_tracing(cli.verbose);
+    let config = match &cli.config {
+        Some(path) => Config::load(path)?,
+        None => Config::discover()?,
+    };
+    tracing::debug!(?config, "loaded config");
+    apply_config(&config, cli.threads)?;
+    if cli.progress {
+        progress::set_hook(Box::new(IndicatifHook {
+            bars: MultiProgress::new(),
+        }))?;
+    }
+    match cli.command {
+        Some(Command::All) => {
+            let results = if cli.cached {
+                let days: Vec<u32> = registry().into_keys().collect();
+                run_days_cached(&days, &Part::ALL)?
+            } else {
+                run_all(&Part::ALL)
+            };
+            print!("{}", format_summary_table(&results));
+            Ok(())
+        }
+        #[cfg(feature = "client")]
+        Some(Command::Submit { day, part }) => submit(day, part, config.session_file.as_deref()),
+        #[cfg(feature = "client")]
+        Some(Command::Download { day, force }) => {
+            download(day, force, config.session_file.as_deref())
+        }
+        Some(Command::Report {
+            day,
+            format,
+            visualize,
+            output,
+        }) => {
+            let format = format.or(config.format).unwrap_or(ReportFormat::Markdown);
+            report(day, format, visualize, output)
+        }
+        Some(Command::Verify { ref answers }) => verify_answers(answers, cli.cached),
+        #[cfg(feature = "bench")]
+        Some(Command::Bench {
+            day,
+            save_baseline,
+            compare,
+            threshold,
+            strategies,
+            quick,
+        }) => bench(day, save_baseline, compare, threshold, strategies, quick),
+        Some(Command::Gen {
+            day,
+            scale,
+            seed,
+            output,
+        }) => gen_input(day, scale, seed, output),
+        #[cfg(feature = "profile")]
+        Some(Command::Profile {
+            day,
+            part,
+            ref input,
+        }) => profile(day, part, input.as_deref()),
+        Some(Command::List) => {
+            print!("{}", format_completion_matrix(&metadata()));
+            Ok(())
+        }
+        Some(Command::Check { day, ref input }) => check_input(day, input.as_deref()),
+        #[cfg(feature = "serve")]
+        Some(Command::Serve { addr }) => server::serve(addr),
+        #[cfg(feature = "tui")]
+        Some(Command::Tui { ref answers }) => run_tui(answers),
+        None => run_single_day(&cli),
+    }
 }
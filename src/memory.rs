@@ -0,0 +1,127 @@
+#[cfg(feature = "alloc-stats")]
+mod tracking {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    ///
+    /// The system allocator, keeping count of the bytes currently allocated and their peak
+    ///
+    pub struct TrackingAllocator;
+
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    #[global_allocator]
+    static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+    impl TrackingAllocator {
+        fn record_alloc(size: usize) {
+            let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+
+        fn record_dealloc(size: usize) {
+            CURRENT.fetch_sub(size, Ordering::Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for TrackingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                Self::record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                Self::record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            Self::record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                Self::record_dealloc(layout.size());
+                Self::record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        let result = f();
+        let peak = PEAK.load(Ordering::Relaxed).saturating_sub(baseline);
+        (result, Some(peak))
+    }
+}
+
+///
+/// Run `f` and return the peak heap usage while it ran, above the usage when it started.
+/// The counts are for the whole process, so anything running concurrently is counted too.
+/// Without the `alloc-stats` feature, which installs a counting global allocator, nothing is
+/// measured and the peak is `None`.
+///
+pub fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    #[cfg(feature = "alloc-stats")]
+    return tracking::measure_peak(f);
+
+    #[cfg(not(feature = "alloc-stats"))]
+    (f(), None)
+}
+
+///
+/// A byte count in the largest binary unit that keeps it at least 1, e.g. `1.50 MiB`
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.2} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(100), "100 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.00 MiB");
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn test_measure_peak() {
+        let (len, peak) = measure_peak(|| vec![0u8; 1 << 20].len());
+        assert_eq!(len, 1 << 20);
+        assert!(peak.unwrap() >= 1 << 20);
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    #[test]
+    fn test_measure_peak_disabled() {
+        assert_eq!(measure_peak(|| 1), (1, None));
+    }
+}
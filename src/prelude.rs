@@ -0,0 +1,55 @@
+//! Everything needed to use the solvers as a library, with `use aoc::prelude::*`. `solve` takes
+//! any day by its number. For a day's parsed input use its `Day` impl, e.g. `Day6::parse` returns
+//! the `Races` that `Day6::part1` solves.
+
+pub use crate::{
+    day1::Day1,
+    day10::{Day10, Grid as PipeGrid},
+    day11::{Day11, Image},
+    day12::{ConditionRecord, Day12},
+    day13::{Day13, GridPatterns},
+    day14::{Day14, Platform},
+    day15::Day15,
+    day16::{Beams, Contraption, Day16},
+    day17::{Day17, HeatLossMap},
+    day18::{Day18, DigInstruction},
+    day19::{Day19, System},
+    day2::{
+        Color, ColorNames, CubeConstraints, Day2, Game, GameSet, MinimumCubeSet, RevealedCubes,
+    },
+    day20::{Day20, Network},
+    day21::{Day21, Garden},
+    day22::{Brick, Day22},
+    day23::{Day23, TrailMap},
+    day24::{Day24, Hailstone},
+    day25::{Day25, WiringDiagram},
+    day3::{Day3, EngineSchematic, SymbolAdjacency},
+    day4::{Day4, ScratchCard},
+    day5::{Almanac, Day5},
+    day6::{Day6, Races},
+    day7::{Day7, HandSet},
+    day8::{Day8, Map, NodeName},
+    day9::{Day9, History},
+    day_metadata,
+    error::AocError,
+    get_solution, metadata, registry,
+    solution::{Answer, DayMetadata, DynSolution, Part, Solution as Day},
+    solve,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let races: Races = Day6::parse(input).unwrap();
+        assert_eq!(Day6::part1(&races).unwrap(), Answer::U64(288));
+        assert_eq!(solve(6, Part::Two, input).unwrap(), "71503");
+        assert!(matches!(
+            solve(26, Part::One, input),
+            Err(AocError::UnsupportedDay(26))
+        ));
+    }
+}
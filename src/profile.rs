@@ -0,0 +1,85 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use pprof::ProfilerGuardBuilder;
+
+use crate::{get_solution, solution::Part};
+
+pub const PROFILE_DIR: &str = "profile";
+
+/// Samples per second, not a round number so it doesn't line up with other periodic work
+const FREQUENCY: i32 = 997;
+
+/// Fast parts are solved repeatedly until at least this long has passed, so there are enough
+/// samples to show anything
+const MIN_DURATION: Duration = Duration::from_secs(1);
+
+///
+/// Profile solving a part of a day against the given input, writing a flamegraph into
+/// `dir/day<N>-part<M>.svg`. Parsing isn't profiled. Returns the path and the number of times the
+/// part was solved.
+pub fn write_flamegraph(
+    day: u32,
+    part: Part,
+    input: &str,
+    dir: &Path,
+) -> anyhow::Result<(PathBuf, usize)> {
+    let solution = get_solution(day).with_context(|| format!("day {day} isn't implemented"))?;
+    let prepared = solution
+        .prepare(input)
+        .with_context(|| format!("failed to parse input for day {day}"))?;
+
+    let guard = ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .context("failed to start the profiler")?;
+    let start = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || start.elapsed() < MIN_DURATION {
+        prepared(part)?;
+        runs += 1;
+    }
+    let report = guard
+        .report()
+        .build()
+        .context("failed to build the profile")?;
+
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let path = dir.join(format!("day{day}-part{part}.svg"));
+    let file =
+        File::create(&path).with_context(|| format!("failed to create {}", path.display()))?;
+    report
+        .flamegraph(file)
+        .context("failed to write the flamegraph")?;
+
+    Ok((path, runs))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_write_flamegraph() {
+        let dir = env::temp_dir().join(format!("aoc-profile-test-{}", std::process::id()));
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let result = write_flamegraph(6, Part::Two, input, &dir);
+        let svg = result
+            .as_ref()
+            .ok()
+            .map(|(path, _)| fs::read_to_string(path).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+
+        let (path, runs) = result.unwrap();
+        assert_eq!(path, dir.join("day6-part2.svg"));
+        assert!(runs > 1);
+        assert!(svg.unwrap().contains("<svg"));
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::OnceLock;
+
+///
+/// Receives the progress of long running solvers, e.g. to draw progress bars.
+/// Nothing is reported unless a hook is installed with `set_hook`.
+///
+pub trait ProgressHook: Send + Sync {
+    fn start(&self, label: &str, total: u64) -> Box<dyn ProgressTask>;
+}
+
+pub trait ProgressTask: Send + Sync {
+    fn inc(&self, delta: u64);
+
+    fn finish(&self);
+}
+
+static HOOK: OnceLock<Box<dyn ProgressHook>> = OnceLock::new();
+
+///
+/// Install the hook used by every solver from now on. Only the first call has any effect
+pub fn set_hook(hook: Box<dyn ProgressHook>) -> anyhow::Result<()> {
+    HOOK.set(hook)
+        .map_err(|_| anyhow::anyhow!("a progress hook is already installed"))
+}
+
+///
+/// A running task, finished when dropped
+///
+pub struct Progress {
+    task: Option<Box<dyn ProgressTask>>,
+}
+
+impl Progress {
+    ///
+    /// Start reporting a task with `total` steps, a no-op if no hook is installed
+    pub fn start(label: &str, total: u64) -> Self {
+        Self {
+            task: HOOK.get().map(|hook| hook.start(label, total)),
+        }
+    }
+
+    pub fn inc(&self, delta: u64) {
+        if let Some(task) = &self.task {
+            task.inc(delta);
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.finish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_without_hook() {
+        // installing a hook would affect every other test, so only the silent path is tested
+        let progress = Progress::start("silent", 10);
+        progress.inc(5);
+        assert!(progress.task.is_none());
+    }
+}
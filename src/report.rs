@@ -0,0 +1,255 @@
+use std::fmt::Write;
+
+use serde::Deserialize;
+
+use crate::{
+    day_metadata,
+    runner::{format_duration, read_day_input},
+    solution::DayRun,
+    viz::{self, VizFormat},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+///
+/// Everything about a single day that goes into the report
+///
+pub struct DayReport {
+    pub day: u32,
+    pub run: anyhow::Result<DayRun>,
+    pub visualization: Option<String>,
+}
+
+///
+/// A text drawing of the parsed input for the days that have one
+///
+pub fn visualize(day: u32, input: &str) -> Option<anyhow::Result<String>> {
+    viz::render(day, input, VizFormat::Ascii)
+}
+
+///
+/// Visualize a day against its actual input. Failures end up in the report instead of the drawing
+///
+pub fn visualize_day(day: u32) -> Option<String> {
+    let drawing = match read_day_input(day) {
+        Ok(input) => visualize(day, &input)?,
+        Err(e) => Err(e),
+    };
+
+    Some(drawing.unwrap_or_else(|e| format!("failed to visualize: {e:#}")))
+}
+
+///
+/// `Day N: Title`, or just `Day N` for a day that isn't registered
+fn heading(day: u32) -> String {
+    match day_metadata(day) {
+        Some(metadata) => format!("Day {day}: {}", metadata.title),
+        None => format!("Day {day}"),
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+///
+/// The rows of the results table of a day: part, answer, parse time and solve time
+///
+fn get_result_rows(run: &anyhow::Result<DayRun>) -> Vec<[String; 4]> {
+    let day_run = match run {
+        Ok(day_run) => day_run,
+        Err(e) => {
+            return vec![[
+                "-".to_string(),
+                format!("error: {e:#}"),
+                "-".to_string(),
+                "-".to_string(),
+            ]]
+        }
+    };
+
+    day_run
+        .parts
+        .iter()
+        .map(|part_run| {
+            let answer = match &part_run.answer {
+                Ok(answer) => answer.to_string(),
+                Err(e) => format!("error: {e:#}"),
+            };
+            [
+                part_run.part.to_string(),
+                answer,
+                format_duration(day_run.parse_time),
+                format_duration(part_run.solve_time),
+            ]
+        })
+        .collect()
+}
+
+const HEADERS: [&str; 4] = ["Part", "Answer", "Parse", "Solve"];
+
+fn render_markdown(days: &[DayReport]) -> Result<String, std::fmt::Error> {
+    let mut report = String::new();
+    writeln!(report, "# Advent of Code 2023")?;
+
+    for day_report in days {
+        writeln!(report)?;
+        writeln!(report, "## {}", heading(day_report.day))?;
+        writeln!(report)?;
+        writeln!(report, "| {} |", HEADERS.join(" | "))?;
+        writeln!(report, "|{}", "---|".repeat(HEADERS.len()))?;
+        for row in get_result_rows(&day_report.run) {
+            // pipes would break the table
+            let row = row.map(|cell| cell.replace('|', "\\|"));
+            writeln!(report, "| {} |", row.join(" | "))?;
+        }
+
+        if let Some(visualization) = &day_report.visualization {
+            writeln!(report)?;
+            writeln!(report, "```text")?;
+            write!(report, "{visualization}")?;
+            if !visualization.ends_with('\n') {
+                writeln!(report)?;
+            }
+            writeln!(report, "```")?;
+        }
+    }
+
+    Ok(report)
+}
+
+fn render_html(days: &[DayReport]) -> Result<String, std::fmt::Error> {
+    let mut report = String::new();
+    writeln!(report, "<!DOCTYPE html>")?;
+    writeln!(report, "<html>")?;
+    writeln!(
+        report,
+        "<head><meta charset=\"utf-8\"><title>Advent of Code 2023</title></head>"
+    )?;
+    writeln!(report, "<body>")?;
+    writeln!(report, "<h1>Advent of Code 2023</h1>")?;
+
+    for day_report in days {
+        writeln!(report, "<h2>{}</h2>", escape_html(&heading(day_report.day)))?;
+        writeln!(report, "<table>")?;
+        let headers: String = HEADERS.iter().map(|x| format!("<th>{x}</th>")).collect();
+        writeln!(report, "<tr>{headers}</tr>")?;
+        for row in get_result_rows(&day_report.run) {
+            let cells: String = row
+                .iter()
+                .map(|cell| format!("<td>{}</td>", escape_html(cell)))
+                .collect();
+            writeln!(report, "<tr>{cells}</tr>")?;
+        }
+        writeln!(report, "</table>")?;
+
+        if let Some(visualization) = &day_report.visualization {
+            writeln!(report, "<pre>{}</pre>", escape_html(visualization))?;
+        }
+    }
+
+    writeln!(report, "</body>")?;
+    writeln!(report, "</html>")?;
+
+    Ok(report)
+}
+
+pub fn render_report(days: &[DayReport], format: ReportFormat) -> String {
+    let report = match format {
+        ReportFormat::Markdown => render_markdown(days),
+        ReportFormat::Html => render_html(days),
+    };
+    report.expect("writing to a string can't fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::solution::{Answer, Part, PartRun};
+
+    use super::*;
+
+    fn get_day_reports() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                day: 10,
+                run: Ok(DayRun {
+                    parse_time: Duration::from_millis(1),
+                    parts: vec![PartRun {
+                        part: Part::One,
+                        answer: Ok(Answer::U64(4)),
+                        solve_time: Duration::from_millis(2),
+                        peak_memory: None,
+                    }],
+                }),
+                visualization: visualize(10, ".....\n.S-7.\n.|.|.\n.L-J.\n.....")
+                    .map(Result::unwrap),
+            },
+            DayReport {
+                day: 11,
+                run: Err(anyhow::anyhow!("<missing> input")),
+                visualization: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let report = render_report(&get_day_reports(), ReportFormat::Markdown);
+        assert_eq!(
+            report,
+            "# Advent of Code 2023
+
+## Day 10: Pipe Maze
+
+| Part | Answer | Parse | Solve |
+|---|---|---|---|
+| 1 | 4 | 1.00ms | 2.00ms |
+
+```text
+.....
+.###.
+.#.#.
+.###.
+.....
+```
+
+## Day 11: Cosmic Expansion
+
+| Part | Answer | Parse | Solve |
+|---|---|---|---|
+| - | error: <missing> input | - | - |
+"
+        );
+    }
+
+    #[test]
+    fn test_render_html() {
+        let report = render_report(&get_day_reports(), ReportFormat::Html);
+        assert!(report.contains("<h2>Day 10: Pipe Maze</h2>"));
+        assert!(report.contains("<tr><td>1</td><td>4</td><td>1.00ms</td><td>2.00ms</td></tr>"));
+        assert!(report.contains("<pre>.....\n.###.\n"));
+        assert!(report.contains("<td>error: &lt;missing&gt; input</td>"));
+    }
+
+    #[test]
+    fn test_visualize_unsupported_day() {
+        assert!(visualize(6, "Time: 7\nDistance: 9").is_none());
+    }
+}
@@ -0,0 +1,271 @@
+use std::{fmt::Write, time::Duration};
+
+use anyhow::Context;
+use rayon::prelude::*;
+
+use crate::{
+    get_solution,
+    memory::format_bytes,
+    registry,
+    solution::{puzzle_url, DayMetadata, DayRun, Part},
+    utils::{get_day_input, read_input_file},
+};
+
+/// The calendar ends on Christmas day
+const LAST_DAY: u32 = 25;
+
+pub fn read_day_input(day: u32) -> anyhow::Result<String> {
+    let path = get_day_input(&format!("day{day}"));
+    read_input_file(&path).with_context(|| format!("failed to read input for day {day}"))
+}
+
+///
+/// Run a single day against its actual input
+pub fn run_day(day: u32, parts: &[Part]) -> anyhow::Result<DayRun> {
+    // check the day exists first, so unimplemented days don't complain about a missing input
+    get_solution(day).with_context(|| format!("day {day} isn't implemented"))?;
+    let input = read_day_input(day)?;
+    run_day_with_input(day, &input, parts)
+}
+
+///
+/// Run a single day against the given input
+pub fn run_day_with_input(day: u32, input: &str, parts: &[Part]) -> anyhow::Result<DayRun> {
+    let _span = tracing::info_span!("day", day).entered();
+    let solution = get_solution(day).with_context(|| format!("day {day} isn't implemented"))?;
+    solution
+        .run(input, parts)
+        .with_context(|| format!("failed to parse input for day {day}"))
+}
+
+///
+/// Run each of the given days against its actual input. The days run concurrently on the rayon
+/// thread pool, but the results are in the given order. A failing day doesn't affect the others.
+/// With the `alloc-stats` feature the days run one after another, so their memory isn't mixed up.
+pub fn run_days(days: &[u32], parts: &[Part]) -> Vec<(u32, anyhow::Result<DayRun>)> {
+    if cfg!(feature = "alloc-stats") {
+        return days.iter().map(|&day| (day, run_day(day, parts))).collect();
+    }
+
+    days.par_iter()
+        .map(|&day| (day, run_day(day, parts)))
+        .collect()
+}
+
+///
+/// Run every implemented day against its actual input, in day order
+pub fn run_all(parts: &[Part]) -> Vec<(u32, anyhow::Result<DayRun>)> {
+    let days: Vec<u32> = registry().into_keys().collect();
+    run_days(&days, parts)
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{duration:.2?}")
+}
+
+///
+/// A table with a row per day and part, containing the answer and how long parsing and solving took.
+/// The peak memory of each solve is added when it was measured.
+///
+pub fn format_summary_table(results: &[(u32, anyhow::Result<DayRun>)]) -> String {
+    let show_memory = results
+        .iter()
+        .filter_map(|(_, day_run)| day_run.as_ref().ok())
+        .flat_map(|day_run| &day_run.parts)
+        .any(|part_run| part_run.peak_memory.is_some());
+
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Part".to_string(),
+        "Answer".to_string(),
+        "Parse".to_string(),
+        "Solve".to_string(),
+    ]];
+    if show_memory {
+        rows[0].push("Memory".to_string());
+    }
+    let mut total = Duration::ZERO;
+
+    for (day, day_run) in results {
+        let day_run = match day_run {
+            Ok(day_run) => day_run,
+            Err(e) => {
+                let mut row = vec![
+                    day.to_string(),
+                    "-".to_string(),
+                    format!("error: {e:#}"),
+                    "-".to_string(),
+                    "-".to_string(),
+                ];
+                if show_memory {
+                    row.push("-".to_string());
+                }
+                rows.push(row);
+                continue;
+            }
+        };
+
+        total += day_run.total_time();
+        for part_run in &day_run.parts {
+            let answer = match &part_run.answer {
+                Ok(answer) => answer.to_string(),
+                Err(e) => format!("error: {e:#}"),
+            };
+            let mut row = vec![
+                day.to_string(),
+                part_run.part.to_string(),
+                answer,
+                format_duration(day_run.parse_time),
+                format_duration(part_run.solve_time),
+            ];
+            if show_memory {
+                row.push(part_run.peak_memory.map_or("-".to_string(), format_bytes));
+            }
+            rows.push(row);
+        }
+    }
+
+    let mut table = format_rows(&rows);
+    writeln!(table, "Total: {}", format_duration(total)).expect("writing to a string can't fail");
+
+    table
+}
+
+///
+/// Align the columns of the rows, the first row being the header
+fn format_rows(rows: &[Vec<String>]) -> String {
+    let mut widths = vec![0; rows[0].len()];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        writeln!(table, "{}", line.trim_end()).expect("writing to a string can't fail");
+    }
+
+    table
+}
+
+///
+/// A row per day of the calendar with a star for each implemented part, from the metadata of the
+/// implemented days
+///
+pub fn format_completion_matrix(days: &[DayMetadata]) -> String {
+    let star = |implemented: bool| if implemented { "*" } else { "-" }.to_string();
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Title".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+        "Link".to_string(),
+    ]];
+
+    for day in 1..=LAST_DAY {
+        let metadata = days.iter().find(|metadata| metadata.day == day);
+        rows.push(vec![
+            day.to_string(),
+            metadata.map_or("", |metadata| metadata.title).to_string(),
+            star(metadata.is_some_and(|metadata| metadata.part1_implemented)),
+            star(metadata.is_some_and(|metadata| metadata.part2_implemented)),
+            puzzle_url(day),
+        ]);
+    }
+
+    let mut table = format_rows(&rows);
+    let stars: u32 = days.iter().map(DayMetadata::stars).sum();
+    writeln!(table, "Stars: {stars}/{}", LAST_DAY * 2).expect("writing to a string can't fail");
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::{Answer, PartRun};
+
+    use super::*;
+
+    #[test]
+    fn test_format_completion_matrix() {
+        let matrix = format_completion_matrix(&crate::metadata());
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert_eq!(lines.len(), 27);
+        assert!(lines[0].starts_with("Day | Title"));
+        assert!(lines[6].starts_with("6   | Wait For It"));
+        assert!(lines[6].ends_with("| *      | *      | https://adventofcode.com/2023/day/6"));
+        assert!(lines[7].ends_with("| -      | *      | https://adventofcode.com/2023/day/7"));
+        assert!(lines[25].starts_with("25  | Snowverload"));
+        assert!(lines[25].ends_with("| *      | -      | https://adventofcode.com/2023/day/25"));
+        assert!(lines[26].starts_with("Stars: "));
+        assert!(lines[26].ends_with("/50"));
+    }
+
+    #[test]
+    fn test_run_days_keeps_order() {
+        let results = run_days(&[25, 12, 26], &Part::ALL);
+        let days: Vec<u32> = results.iter().map(|(day, _)| *day).collect();
+        assert_eq!(days, vec![25, 12, 26]);
+        assert!(results.iter().all(|(_, day_run)| day_run.is_err()));
+    }
+
+    #[test]
+    fn test_format_summary_table() {
+        let results = vec![
+            (
+                6,
+                Ok(DayRun {
+                    parse_time: Duration::from_millis(1),
+                    parts: vec![PartRun {
+                        part: Part::One,
+                        answer: Ok(Answer::U64(288)),
+                        solve_time: Duration::from_millis(2),
+                        peak_memory: None,
+                    }],
+                }),
+            ),
+            (7, Err(anyhow::anyhow!("missing input"))),
+        ];
+
+        let table = format_summary_table(&results);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Day | Part | Answer               | Parse  | Solve"
+        );
+        assert_eq!(
+            lines[1],
+            "6   | 1    | 288                  | 1.00ms | 2.00ms"
+        );
+        assert_eq!(lines[2], "7   | -    | error: missing input | -      | -");
+        assert_eq!(lines[3], "Total: 3.00ms");
+    }
+
+    #[test]
+    fn test_format_summary_table_with_memory() {
+        let results = vec![(
+            6,
+            Ok(DayRun {
+                parse_time: Duration::from_millis(1),
+                parts: vec![PartRun {
+                    part: Part::One,
+                    answer: Ok(Answer::U64(288)),
+                    solve_time: Duration::from_millis(2),
+                    peak_memory: Some(1536),
+                }],
+            }),
+        )];
+
+        let table = format_summary_table(&results);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "Day | Part | Answer | Parse  | Solve  | Memory");
+        assert_eq!(lines[1], "6   | 1    | 288    | 1.00ms | 2.00ms | 1.50 KiB");
+    }
+}
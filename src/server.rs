@@ -0,0 +1,132 @@
+use std::{net::SocketAddr, time::Duration};
+
+use anyhow::Context;
+use axum::{extract::Path, http::StatusCode, routing::post, Json, Router};
+use serde::Serialize;
+
+use crate::{
+    get_solution,
+    runner::run_day_with_input,
+    solution::{Answer, Part},
+};
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum SolveResponse {
+    Answer {
+        day: u32,
+        part: u8,
+        answer: Answer,
+        parse_time_ms: f64,
+        solve_time_ms: f64,
+    },
+    Error {
+        error: String,
+    },
+}
+
+impl SolveResponse {
+    fn error(e: impl Into<anyhow::Error>) -> Self {
+        SolveResponse::Error {
+            error: format!("{:#}", e.into()),
+        }
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+///
+/// Solve a part of a day against the given input, with the status code to respond with
+pub fn solve(day: u32, part: u8, input: &str) -> (StatusCode, SolveResponse) {
+    let solve_part = match Part::try_from(part) {
+        Ok(solve_part) => solve_part,
+        Err(e) => return (StatusCode::BAD_REQUEST, SolveResponse::error(e)),
+    };
+    if get_solution(day).is_none() {
+        return (
+            StatusCode::NOT_FOUND,
+            SolveResponse::error(anyhow::anyhow!("day {day} isn't implemented")),
+        );
+    }
+
+    let day_run = match run_day_with_input(day, input, &[solve_part]) {
+        Ok(day_run) => day_run,
+        Err(e) => return (StatusCode::UNPROCESSABLE_ENTITY, SolveResponse::error(e)),
+    };
+    let part_run = day_run.parts.into_iter().next().expect("ran a single part");
+    match part_run.answer {
+        Ok(answer) => (
+            StatusCode::OK,
+            SolveResponse::Answer {
+                day,
+                part,
+                answer,
+                parse_time_ms: as_millis(day_run.parse_time),
+                solve_time_ms: as_millis(part_run.solve_time),
+            },
+        ),
+        Err(e) => (StatusCode::UNPROCESSABLE_ENTITY, SolveResponse::error(e)),
+    }
+}
+
+async fn solve_handler(
+    Path((day, part)): Path<(u32, u8)>,
+    input: String,
+) -> (StatusCode, Json<SolveResponse>) {
+    // solving can take a while, so it mustn't block the async workers
+    let (status, response) = tokio::task::spawn_blocking(move || solve(day, part, &input))
+        .await
+        .unwrap_or_else(|e| (StatusCode::INTERNAL_SERVER_ERROR, SolveResponse::error(e)));
+    (status, Json(response))
+}
+
+///
+/// `POST /solve/{day}/{part}` with the raw puzzle input as the body
+pub fn router() -> Router {
+    Router::new().route("/solve/{day}/{part}", post(solve_handler))
+}
+
+///
+/// Serve the router until the process is stopped
+pub fn serve(addr: SocketAddr) -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Runtime::new().context("failed to start the async runtime")?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("failed to listen on {addr}"))?;
+        tracing::info!(%addr, "serving");
+        axum::serve(listener, router())
+            .await
+            .context("server failed")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+    #[test]
+    fn test_solve() {
+        let (status, response) = solve(6, 2, INPUT);
+        assert_eq!(status, StatusCode::OK);
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["answer"], 71503);
+        assert_eq!(json["part"], 2);
+        assert!(json["solve_time_ms"].is_f64());
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert_eq!(solve(26, 1, INPUT).0, StatusCode::NOT_FOUND);
+        assert_eq!(solve(6, 3, INPUT).0, StatusCode::BAD_REQUEST);
+
+        let (status, response) = solve(6, 1, "Time: x");
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json["error"].is_string());
+    }
+}
@@ -0,0 +1,415 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::memory::measure_peak;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            _ => anyhow::bail!("invalid part: {value}"),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+///
+/// The answer to a part. Numbers stay numbers when serialized, e.g. in the JSON output.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    U64(u64),
+    I64(i64),
+    Usize(usize),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::U64(x) => write!(f, "{x}"),
+            Answer::I64(x) => write!(f, "{x}"),
+            Answer::Usize(x) => write!(f, "{x}"),
+            Answer::Text(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::U64(value)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::U64(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::I64(value)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::I64(value.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Usize(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+///
+/// A single day's puzzle. The input is parsed once and then shared by both parts.
+///
+pub trait Solution {
+    /// The parsed input, which may borrow slices of the input string instead of copying them
+    type Input<'a>;
+
+    /// The title of the puzzle on adventofcode.com
+    const TITLE: &'static str;
+
+    /// False while `part1` is only a placeholder which fails
+    const PART1_IMPLEMENTED: bool = true;
+
+    /// False while `part2` is only a placeholder which fails
+    const PART2_IMPLEMENTED: bool = true;
+
+    /// Solve both parts on separate threads when both are requested, worth it for the days with
+    /// a slow part
+    const CONCURRENT_PARTS: bool = false;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>>;
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer>;
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer>;
+}
+
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: anyhow::Result<Answer>,
+    pub solve_time: Duration,
+    /// The peak heap usage while solving, only measured with the `alloc-stats` feature
+    pub peak_memory: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub parse_time: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|x| x.solve_time).sum::<Duration>()
+    }
+}
+
+///
+/// A day's input parsed once, ready to solve any of the parts as many times as needed. It borrows
+/// the input, which has to outlive it.
+///
+pub type PreparedSolution<'a> = Box<dyn Fn(Part) -> anyhow::Result<Answer> + Send + Sync + 'a>;
+
+///
+/// Object safe version of `Solution`, so days with different input types can be stored
+/// together in the registry.
+///
+pub trait DynSolution: Send + Sync {
+    /// Parse the input and return a closure solving a part against the parsed input
+    fn prepare<'a>(&self, input: &'a str) -> anyhow::Result<PreparedSolution<'a>>;
+
+    fn concurrent_parts(&self) -> bool;
+
+    fn title(&self) -> &'static str;
+
+    fn part1_implemented(&self) -> bool;
+
+    fn part2_implemented(&self) -> bool;
+
+    fn implemented(&self, part: Part) -> bool {
+        match part {
+            Part::One => self.part1_implemented(),
+            Part::Two => self.part2_implemented(),
+        }
+    }
+
+    /// Parse the input once and solve each of the requested parts, in order.
+    /// A part failing doesn't stop the other parts from running.
+    /// With the `alloc-stats` feature the parts always run one after another, so their memory
+    /// isn't mixed up.
+    fn run(&self, input: &str, parts: &[Part]) -> anyhow::Result<DayRun> {
+        let start = Instant::now();
+        let prepared = tracing::info_span!("parse").in_scope(|| self.prepare(input))?;
+        let parse_time = start.elapsed();
+        tracing::debug!(?parse_time, "parsed input");
+
+        let parts = if self.concurrent_parts() && parts.len() > 1 && !cfg!(feature = "alloc-stats")
+        {
+            let span = tracing::Span::current();
+            parts
+                .par_iter()
+                .map(|&part| span.in_scope(|| run_part(&prepared, part)))
+                .collect()
+        } else {
+            parts
+                .iter()
+                .map(|&part| run_part(&prepared, part))
+                .collect()
+        };
+
+        Ok(DayRun { parse_time, parts })
+    }
+}
+
+fn run_part(prepared: &PreparedSolution<'_>, part: Part) -> PartRun {
+    let _span = tracing::info_span!("solve", %part).entered();
+    let start = Instant::now();
+    let (answer, peak_memory) = measure_peak(|| prepared(part));
+    let solve_time = start.elapsed();
+    tracing::debug!(?solve_time, ?peak_memory, ok = answer.is_ok(), "solved");
+    PartRun {
+        part,
+        answer,
+        solve_time,
+        peak_memory,
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Send + Sync + 'static,
+    for<'a> S::Input<'a>: Send + Sync,
+{
+    fn prepare<'a>(&self, input: &'a str) -> anyhow::Result<PreparedSolution<'a>> {
+        let parsed = S::parse(input)?;
+        Ok(Box::new(move |part| match part {
+            Part::One => S::part1(&parsed),
+            Part::Two => S::part2(&parsed),
+        }))
+    }
+
+    fn concurrent_parts(&self) -> bool {
+        S::CONCURRENT_PARTS
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn part1_implemented(&self) -> bool {
+        S::PART1_IMPLEMENTED
+    }
+
+    fn part2_implemented(&self) -> bool {
+        S::PART2_IMPLEMENTED
+    }
+}
+
+///
+/// The puzzle description of a day on adventofcode.com
+pub fn puzzle_url(day: u32) -> String {
+    format!("https://adventofcode.com/2023/day/{day}")
+}
+
+///
+/// What's known about a registered day without running it
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DayMetadata {
+    pub day: u32,
+    pub title: &'static str,
+    pub part1_implemented: bool,
+    pub part2_implemented: bool,
+}
+
+impl DayMetadata {
+    pub fn new(day: u32, solution: &dyn DynSolution) -> Self {
+        Self {
+            day,
+            title: solution.title(),
+            part1_implemented: solution.part1_implemented(),
+            part2_implemented: solution.part2_implemented(),
+        }
+    }
+
+    pub fn url(&self) -> String {
+        puzzle_url(self.day)
+    }
+
+    pub fn implemented(&self, part: Part) -> bool {
+        match part {
+            Part::One => self.part1_implemented,
+            Part::Two => self.part2_implemented,
+        }
+    }
+
+    ///
+    /// A star for each implemented part, like the stars on the calendar
+    pub fn stars(&self) -> u32 {
+        u32::from(self.part1_implemented) + u32::from(self.part2_implemented)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::get_solution;
+
+    use super::*;
+
+    fn get_answers(day_run: DayRun) -> Vec<Answer> {
+        day_run
+            .parts
+            .into_iter()
+            .map(|x| x.answer.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_run_through_registry() {
+        let solution = get_solution(6).unwrap();
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        assert_eq!(
+            get_answers(solution.run(input, &Part::ALL).unwrap()),
+            vec![Answer::U64(288), Answer::U64(71503)]
+        );
+        assert_eq!(
+            get_answers(solution.run(input, &[Part::Two]).unwrap()),
+            vec![Answer::U64(71503)]
+        );
+    }
+
+    #[test]
+    fn test_failing_part_doesnt_stop_others() {
+        let solution = get_solution(7).unwrap();
+        let day_run = solution.run("32T3K 765\nT55J5 684", &Part::ALL).unwrap();
+        assert!(day_run.parts[0].answer.is_err());
+        assert_eq!(
+            day_run.parts[1].answer.as_ref().unwrap().to_string(),
+            "2133"
+        );
+    }
+
+    #[test]
+    fn test_prepare_solves_repeatedly() {
+        let solution = get_solution(6).unwrap();
+        let prepared = solution
+            .prepare("Time:      7  15   30\nDistance:  9  40  200\n")
+            .unwrap();
+        assert_eq!(prepared(Part::One).unwrap(), Answer::U64(288));
+        assert_eq!(prepared(Part::One).unwrap(), Answer::U64(288));
+        assert_eq!(prepared(Part::Two).unwrap(), Answer::U64(71503));
+    }
+
+    struct Slow;
+
+    impl Solution for Slow {
+        type Input<'a> = u64;
+
+        const TITLE: &'static str = "Slow";
+
+        const CONCURRENT_PARTS: bool = true;
+
+        fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+            Ok(input.parse()?)
+        }
+
+        fn part1(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+            std::thread::sleep(Duration::from_millis(20));
+            Ok((*input).into())
+        }
+
+        fn part2(input: &Self::Input<'_>) -> anyhow::Result<Answer> {
+            Ok((input * 2).into())
+        }
+    }
+
+    #[test]
+    fn test_concurrent_parts_keep_order() {
+        assert!(Slow.concurrent_parts());
+        assert!(!get_solution(6).unwrap().concurrent_parts());
+
+        let day_run = Slow.run("21", &Part::ALL).unwrap();
+        assert_eq!(
+            day_run.parts.iter().map(|x| x.part).collect::<Vec<_>>(),
+            Part::ALL
+        );
+        assert_eq!(get_answers(day_run), vec![Answer::U64(21), Answer::U64(42)]);
+    }
+
+    #[test]
+    fn test_answer_serialization() {
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(serde_json::to_string(&Answer::from(42usize)).unwrap(), "42");
+        assert_eq!(
+            serde_json::to_string(&Answer::from("ABC")).unwrap(),
+            "\"ABC\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Answer>("\"ABC\"").unwrap(),
+            Answer::Text("ABC".to_string())
+        );
+    }
+
+    #[test]
+    fn test_metadata_stars() {
+        let metadata = crate::day_metadata(7).unwrap();
+        assert!(!metadata.implemented(Part::One));
+        assert!(metadata.implemented(Part::Two));
+        assert_eq!(metadata.stars(), 1);
+        assert_eq!(crate::day_metadata(6).unwrap().stars(), 2);
+        assert_eq!(crate::day_metadata(25).unwrap().stars(), 1);
+    }
+
+    #[test]
+    fn test_missing_day() {
+        assert!(get_solution(26).is_none());
+    }
+}
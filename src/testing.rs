@@ -0,0 +1,244 @@
+//! Generators of puzzle inputs for the property tests, following each day's input grammar
+
+use std::{collections::HashSet, ops::Range};
+
+use fastrand::Rng;
+use itertools::Itertools;
+use proptest::{collection, prelude::*, sample};
+
+pub use crate::gen::COLORS;
+
+///
+/// A day 1 line mixing digits, spelled out digits and pieces of them, e.g. `xtwone3eigh`, so
+/// spelled out digits often overlap or are cut short
+pub fn calibration_line() -> impl Strategy<Value = String> {
+    let piece = prop_oneof![
+        sample::select(vec![
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "on", "tw",
+            "thre", "eigh", "ni", "ne", "ight",
+        ])
+        .prop_map(String::from),
+        "[0-9]",
+        "[a-z]{1,3}",
+    ];
+    collection::vec(piece, 0..10).prop_map(|pieces| pieces.concat())
+}
+
+///
+/// A day 2 game, e.g. `Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green`
+pub fn game_line() -> impl Strategy<Value = String> {
+    let revealed = sample::subsequence(COLORS.to_vec(), 1..=COLORS.len())
+        .prop_shuffle()
+        .prop_flat_map(|colors| {
+            let num_colors = colors.len();
+            (Just(colors), collection::vec(1u32..20, num_colors))
+        })
+        .prop_map(|(colors, counts)| {
+            counts
+                .iter()
+                .zip(colors)
+                .map(|(count, color)| format!("{count} {color}"))
+                .join(", ")
+        });
+
+    (1u32..1000, collection::vec(revealed, 1..5))
+        .prop_map(|(id, revealed)| format!("Game {id}: {}", revealed.join("; ")))
+}
+
+///
+/// Day 4 scratch cards along with the number of chosen numbers in each card's winning numbers.
+/// A card never wins more cards than there are after it, like the puzzle promises.
+pub fn scratch_cards() -> impl Strategy<Value = Vec<(String, usize)>> {
+    let card = (
+        collection::hash_set(1u32..50, 5..10),
+        collection::hash_set(50u32..100, 0..5),
+        0usize..5,
+    );
+
+    collection::vec(card, 1..20).prop_map(|cards| {
+        let num_cards = cards.len();
+        cards
+            .into_iter()
+            .enumerate()
+            .map(|(index, (winning, extra, matches))| {
+                let matches = matches.min(num_cards - index - 1);
+                let chosen: HashSet<u32> =
+                    winning.iter().copied().take(matches).chain(extra).collect();
+                let line = format!(
+                    "Card {:>3}: {} | {}",
+                    index + 1,
+                    chosen.iter().map(|x| format!("{x:>2}")).join(" "),
+                    winning.iter().map(|x| format!("{x:>2}")).join(" ")
+                );
+                (line, matches)
+            })
+            .collect()
+    })
+}
+
+///
+/// A rectangular grid of the given characters with a trailing newline, e.g. `.#.\n#..\n`
+pub fn char_grid(alphabet: &'static [char], max_size: usize) -> impl Strategy<Value = String> {
+    (1..=max_size, 1..=max_size)
+        .prop_flat_map(move |(num_rows, num_columns)| {
+            collection::vec(
+                collection::vec(sample::select(alphabet), num_columns),
+                num_rows,
+            )
+        })
+        .prop_map(|rows| {
+            rows.into_iter()
+                .map(|row| row.into_iter().collect::<String>() + "\n")
+                .collect()
+        })
+}
+
+///
+/// A day 8 map where both choices of every node lead along a single path from `AAA` to `ZZZ`,
+/// along with the length of the path
+pub fn network_path(max_len: usize) -> impl Strategy<Value = (String, usize)> {
+    let instructions = collection::vec(sample::select(vec!['L', 'R']), 1..10);
+    // the middle nodes never start with A or end with Z, so they aren't mistaken for the ends
+    let names = collection::hash_set("[B-Y]{3}", 0..max_len);
+
+    (instructions, names).prop_map(|(instructions, names)| {
+        let path: Vec<String> = std::iter::once("AAA".to_string())
+            .chain(names)
+            .chain(std::iter::once("ZZZ".to_string()))
+            .collect();
+        let nodes = path
+            .iter()
+            .zip(
+                path.iter()
+                    .skip(1)
+                    .chain(std::iter::once(&path[path.len() - 1])),
+            )
+            .map(|(node, next)| format!("{node} = ({next}, {next})"))
+            .join("\n");
+
+        let input = format!(
+            "{}\n\n{nodes}\n",
+            instructions.into_iter().collect::<String>()
+        );
+        (input, path.len() - 1)
+    })
+}
+
+/// The seeds of the inputs each differential test checks, so a failure can be reproduced
+pub const DIFFERENTIAL_SEEDS: Range<u64> = 0..300;
+
+///
+/// Day 6 races, often with records just below the best possible distance where rounding the roots
+/// of the quadratic matters. Every race, including the single race of part 2, can be won.
+pub fn seeded_races(seed: u64) -> String {
+    let mut rng = Rng::with_seed(seed);
+    loop {
+        let races: Vec<(u64, u64)> = (0..rng.usize(1..=3))
+            .map(|_| {
+                let time = rng.u64(2..100);
+                let best = time * time / 4;
+                let record = match rng.bool() {
+                    true => rng.u64(best.saturating_sub(3)..best),
+                    false => rng.u64(0..best),
+                };
+                (time, record)
+            })
+            .collect();
+
+        let single_time: u64 = races.iter().map(|(time, _)| time).join("").parse().unwrap();
+        let single_record: u64 = races
+            .iter()
+            .map(|(_, record)| record)
+            .join("")
+            .parse()
+            .unwrap();
+        if single_record < single_time * single_time / 4 {
+            return format!(
+                "Time:{}\nDistance:{}\n",
+                races.iter().map(|(time, _)| format!(" {time:>4}")).join(""),
+                races
+                    .iter()
+                    .map(|(_, record)| format!(" {record:>4}"))
+                    .join("")
+            );
+        }
+    }
+}
+
+///
+/// A day 8 map of up to 4 ghosts. Each ghost walks from its start node into a loop that ends at
+/// its end node and then starts over, the structure the puzzle input has.
+pub fn seeded_ghost_network(seed: u64) -> String {
+    let mut rng = Rng::with_seed(seed);
+    let instructions: String = (0..rng.usize(1..6))
+        .map(|_| if rng.bool() { 'L' } else { 'R' })
+        .collect();
+
+    let mut nodes = Vec::new();
+    for ghost in ('B'..='E').take(rng.usize(1..=4)) {
+        // the network drops edges from a node to itself, so the loop needs at least 2 nodes
+        let loop_len = rng.usize(2..10);
+        // 1A, 1X .. nX, then nZ leading back to 1X, so the end is reached every `loop_len` steps
+        let names: Vec<String> = (1..loop_len)
+            .map(|index| format!("{ghost}{index}X"))
+            .chain(std::iter::once(format!("{ghost}{loop_len}Z")))
+            .collect();
+        nodes.push(format!("{ghost}0A = ({0}, {0})", names[0]));
+        for (name, next) in names.iter().zip(names.iter().cycle().skip(1)) {
+            nodes.push(format!("{name} = ({next}, {next})"));
+        }
+    }
+    rng.shuffle(&mut nodes);
+
+    format!("{instructions}\n\n{}\n", nodes.join("\n"))
+}
+
+///
+/// Day 13 patterns, most mirrored around a random row or column, some with a single smudged cell
+pub fn seeded_patterns(seed: u64) -> String {
+    let mut rng = Rng::with_seed(seed);
+    let random_row = |rng: &mut Rng, num_columns: usize| -> Vec<bool> {
+        (0..num_columns).map(|_| rng.bool()).collect()
+    };
+
+    let patterns: Vec<String> = (0..rng.usize(1..5))
+        .map(|_| {
+            let num_columns = rng.usize(2..8);
+            let above = rng.usize(1..5);
+            let mut rows: Vec<Vec<bool>> = (0..above)
+                .map(|_| random_row(&mut rng, num_columns))
+                .collect();
+            let mirrored: Vec<Vec<bool>> = rows
+                .iter()
+                .rev()
+                .take(rng.usize(0..=above))
+                .cloned()
+                .collect();
+            rows.extend(mirrored);
+            if rows.len() < 2 {
+                rows.push(random_row(&mut rng, num_columns));
+            }
+
+            if rng.bool() {
+                let row = rng.usize(..rows.len());
+                let column = rng.usize(..num_columns);
+                rows[row][column] = !rows[row][column];
+            }
+            if rng.bool() {
+                rows = (0..num_columns)
+                    .map(|column| rows.iter().map(|row| row[column]).collect())
+                    .collect();
+            }
+
+            rows.iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&rock| if rock { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .join("\n")
+        })
+        .collect();
+
+    patterns.join("\n\n") + "\n"
+}
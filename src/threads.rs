@@ -0,0 +1,60 @@
+//! The rayon thread pool which the parallel solvers, the concurrent parts and the all-days mode
+//! share, so they all respect the same limit.
+
+use std::{env, ffi::OsString};
+
+use anyhow::Context;
+
+/// How many threads to run on, for when there's no `--threads` flag
+pub const THREADS_ENV_VAR: &str = "AOC_THREADS";
+
+fn threads_from_env(value: Option<OsString>) -> anyhow::Result<Option<usize>> {
+    let Some(value) = value.filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .with_context(|| format!("{THREADS_ENV_VAR} isn't valid unicode"))?;
+    let threads = value
+        .parse()
+        .with_context(|| format!("invalid {THREADS_ENV_VAR}: {value}"))?;
+    Ok(Some(threads))
+}
+
+///
+/// Size the global rayon pool, before anything runs on it. The `--threads` flag takes precedence
+/// over `AOC_THREADS`, which takes precedence over the config's `threads`. Without any of them,
+/// or with 0, there's a thread per core. Returns the number of threads in the pool.
+///
+pub fn init_thread_pool(flag: Option<usize>, config: Option<usize>) -> anyhow::Result<usize> {
+    let threads = match flag {
+        Some(threads) => Some(threads),
+        None => threads_from_env(env::var_os(THREADS_ENV_VAR))?.or(config),
+    };
+    let Some(threads) = threads else {
+        return Ok(rayon::current_num_threads());
+    };
+
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .context("failed to set the number of threads")?;
+    tracing::debug!(
+        threads = rayon::current_num_threads(),
+        "initialized the thread pool"
+    );
+    Ok(rayon::current_num_threads())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threads_from_env() {
+        assert_eq!(threads_from_env(Some("4".into())).unwrap(), Some(4));
+        assert_eq!(threads_from_env(Some("".into())).unwrap(), None);
+        assert_eq!(threads_from_env(None).unwrap(), None);
+        assert!(threads_from_env(Some("four".into())).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::Context;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Style, Stylize},
+    widgets::{Block, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    day_metadata, registry,
+    runner::{format_duration, read_day_input, run_day_with_input},
+    solution::{DayRun, Part},
+    utils::{get_day_test_input, read_input_file},
+    verify::{verify, ExpectedAnswers, VerifyEntry, VerifyStatus},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Actual,
+    Test,
+}
+
+impl InputKind {
+    fn toggle(self) -> Self {
+        match self {
+            InputKind::Actual => InputKind::Test,
+            InputKind::Test => InputKind::Actual,
+        }
+    }
+
+    fn read(self, day: u32) -> anyhow::Result<String> {
+        match self {
+            InputKind::Actual => read_day_input(day),
+            InputKind::Test => read_input_file(get_day_test_input(&format!("day{day}")))
+                .with_context(|| format!("failed to read test input for day {day}")),
+        }
+    }
+}
+
+///
+/// The combined verification status of a day's parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Check {
+    Unknown,
+    Pass,
+    Fail,
+    Error,
+}
+
+impl Check {
+    fn from_entries<'a>(entries: impl IntoIterator<Item = &'a VerifyEntry>) -> Self {
+        entries.into_iter().fold(Check::Unknown, |check, entry| {
+            match (check, &entry.status) {
+                (Check::Fail, _) | (_, VerifyStatus::Fail { .. }) => Check::Fail,
+                (Check::Error, _) | (_, VerifyStatus::Error(_)) => Check::Error,
+                (_, VerifyStatus::Pass) => Check::Pass,
+            }
+        })
+    }
+
+    fn cell(self) -> (&'static str, Color) {
+        match self {
+            Check::Unknown => ("-", Color::Reset),
+            Check::Pass => ("ok", Color::Green),
+            Check::Fail => ("FAIL", Color::Red),
+            Check::Error => ("ERROR", Color::Yellow),
+        }
+    }
+}
+
+fn answer_cell(day_run: &anyhow::Result<DayRun>, part: Part) -> String {
+    let day_run = match day_run {
+        Ok(day_run) => day_run,
+        Err(e) => return format!("error: {e:#}"),
+    };
+    match day_run.parts.iter().find(|x| x.part == part) {
+        Some(part_run) => match &part_run.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => format!("error: {e:#}"),
+        },
+        None => "-".to_string(),
+    }
+}
+
+fn timing_cells(day_run: &anyhow::Result<DayRun>) -> [String; 2] {
+    match day_run {
+        Ok(day_run) => [
+            format_duration(day_run.parse_time),
+            format_duration(day_run.total_time() - day_run.parse_time),
+        ],
+        Err(_) => ["-".to_string(), "-".to_string()],
+    }
+}
+
+///
+/// A row per implemented day, run against the selected input
+///
+pub struct App {
+    results: Vec<(u32, anyhow::Result<DayRun>)>,
+    expected: Option<ExpectedAnswers>,
+    input: InputKind,
+    table: TableState,
+}
+
+impl App {
+    ///
+    /// Answers are only checked against the actual input, the expected answers are optional
+    pub fn new(expected: Option<ExpectedAnswers>) -> Self {
+        let mut app = Self {
+            results: registry()
+                .into_keys()
+                .map(|day| (day, Err(anyhow::anyhow!("not run yet"))))
+                .collect(),
+            expected,
+            input: InputKind::Actual,
+            table: TableState::default().with_selected(Some(0)),
+        };
+        app.run_all();
+        app
+    }
+
+    fn run_day(day: u32, input: InputKind) -> anyhow::Result<DayRun> {
+        let input = input.read(day)?;
+        run_day_with_input(day, &input, &Part::ALL)
+    }
+
+    fn run_all(&mut self) {
+        for (day, day_run) in &mut self.results {
+            *day_run = Self::run_day(*day, self.input);
+        }
+    }
+
+    fn run_selected(&mut self) {
+        if let Some((day, day_run)) = self
+            .table
+            .selected()
+            .and_then(|index| self.results.get_mut(index))
+        {
+            *day_run = Self::run_day(*day, self.input);
+        }
+    }
+
+    fn checks(&self) -> Vec<VerifyEntry> {
+        match (&self.expected, self.input) {
+            (Some(expected), InputKind::Actual) => verify(expected, &self.results),
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, help_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+        let checks = self.checks();
+        let rows: Vec<Row> = self
+            .results
+            .iter()
+            .map(|(day, day_run)| {
+                let check = Check::from_entries(checks.iter().filter(|x| x.day == *day));
+                let (check, color) = check.cell();
+                let [parse, solve] = timing_cells(day_run);
+                let title = day_metadata(*day).map_or("", |metadata| metadata.title);
+                Row::new(vec![
+                    day.to_string().into(),
+                    title.into(),
+                    answer_cell(day_run, Part::One).into(),
+                    answer_cell(day_run, Part::Two).into(),
+                    parse.into(),
+                    solve.into(),
+                    check.fg(color),
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(32),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(6),
+        ];
+        let input = match self.input {
+            InputKind::Actual => "actual",
+            InputKind::Test => "test",
+        };
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new([
+                    "Day", "Title", "Part 1", "Part 2", "Parse", "Solve", "Check",
+                ])
+                .bold(),
+            )
+            .block(Block::bordered().title(format!(" Advent of Code 2023 ({input} input) ")))
+            .row_highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+
+        let help = "↑/↓ select  r re-run day  a re-run all  t toggle test/actual input  q quit";
+        frame.render_widget(Paragraph::new(help).dim(), help_area);
+    }
+
+    ///
+    /// Draw and handle keys until the user quits
+    pub fn run(mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            // poll so a resize redraws even without key presses
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+                KeyCode::Char('r') | KeyCode::Enter => self.run_selected(),
+                KeyCode::Char('a') => self.run_all(),
+                KeyCode::Char('t') => {
+                    self.input = self.input.toggle();
+                    self.run_all();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+///
+/// Run the dashboard, restoring the terminal afterwards even on errors
+pub fn run_tui(answers: &Path) -> anyhow::Result<()> {
+    let app = App::new(ExpectedAnswers::load_if_exists(answers)?);
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::solution::{Answer, PartRun};
+
+    use super::*;
+
+    fn get_day_run() -> anyhow::Result<DayRun> {
+        Ok(DayRun {
+            parse_time: Duration::from_millis(1),
+            parts: vec![
+                PartRun {
+                    part: Part::One,
+                    answer: Ok(Answer::U64(288)),
+                    solve_time: Duration::from_millis(2),
+                    peak_memory: None,
+                },
+                PartRun {
+                    part: Part::Two,
+                    answer: Err(anyhow::anyhow!("no answer")),
+                    solve_time: Duration::from_millis(3),
+                    peak_memory: None,
+                },
+            ],
+        })
+    }
+
+    #[test]
+    fn test_cells() {
+        let day_run = get_day_run();
+        assert_eq!(answer_cell(&day_run, Part::One), "288");
+        assert_eq!(answer_cell(&day_run, Part::Two), "error: no answer");
+        assert_eq!(timing_cells(&day_run), ["1.00ms", "5.00ms"]);
+        assert_eq!(
+            answer_cell(&Err(anyhow::anyhow!("missing input")), Part::One),
+            "error: missing input"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let expected = ExpectedAnswers::parse("[day6]\npart1 = 288\n[day7]\npart2 = 1").unwrap();
+        let results = vec![(6, get_day_run()), (7, get_day_run())];
+        let entries = verify(&expected, &results);
+        let check = |day: u32| Check::from_entries(entries.iter().filter(|x| x.day == day));
+
+        assert_eq!(check(6), Check::Pass);
+        assert_eq!(check(7), Check::Error);
+        assert_eq!(check(8), Check::Unknown);
+    }
+}
@@ -1,63 +1,123 @@
-use std::{
-    fmt::Debug,
-    fs::File,
-    io::{BufRead, BufReader, Read},
-    path::{Path, PathBuf},
-    str::FromStr,
-};
-
-pub fn read_lines<P: AsRef<Path>>(path: P) -> impl IntoIterator<Item = String> {
-    let file = File::open(path).unwrap();
-    BufReader::new(file)
-        .lines()
-        .map(std::result::Result::unwrap)
+use std::{io::BufRead, str::FromStr};
+
+use anyhow::Context;
+use rayon::prelude::*;
+
+use crate::error::ParseLocation;
+
+#[cfg(feature = "arena")]
+pub mod arena;
+pub mod geom;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod search;
+pub mod simd;
+// reading inputs from disk isn't possible in the browser
+#[cfg(not(target_arch = "wasm32"))]
+mod input;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use input::*;
+pub use interval::merge_ranges;
+
+///
+/// An input which can be built up a line at a time, so it can be parsed without holding the whole
+/// input in memory
+///
+pub trait FromLines: Sized {
+    /// What's kept of the lines seen so far
+    type Builder: Default;
+
+    fn push_line(builder: &mut Self::Builder, line: &str) -> anyhow::Result<()>;
+
+    fn finish(builder: Self::Builder) -> anyhow::Result<Self>;
 }
 
-pub fn parse_input_lines<P, T>(path: P) -> Vec<T>
+impl<T> FromLines for Vec<T>
 where
-    P: AsRef<Path>,
     T: FromStr,
-    T::Err: Debug,
+    T::Err: Into<anyhow::Error>,
 {
-    read_lines(path)
-        .into_iter()
-        .map(|x| x.parse().unwrap())
-        .collect()
+    type Builder = Vec<T>;
+
+    fn push_line(builder: &mut Self::Builder, line: &str) -> anyhow::Result<()> {
+        builder.push(line.parse().map_err(Into::into)?);
+        Ok(())
+    }
+
+    fn finish(builder: Self::Builder) -> anyhow::Result<Self> {
+        Ok(builder)
+    }
 }
 
-pub fn parse_input<P, T>(path: P) -> T
+///
+/// Feed lines into the builder of `T` one by one, failing at the first line which doesn't parse
+pub fn parse_from_lines<'a, T: FromLines>(
+    lines: impl IntoIterator<Item = &'a str>,
+) -> anyhow::Result<T> {
+    let mut builder = T::Builder::default();
+    for (index, line) in lines.into_iter().enumerate() {
+        T::push_line(&mut builder, line).with_context(|| ParseLocation::line(index + 1, line))?;
+    }
+    T::finish(builder)
+}
+
+///
+/// Parse lines from any reader as they're read, like [`parse_input_streaming`] does for files
+pub fn parse_reader_streaming<R, T>(mut reader: R) -> anyhow::Result<T>
 where
-    P: AsRef<Path>,
-    T: FromStr,
-    T::Err: Debug,
+    R: BufRead,
+    T: FromLines,
 {
-    let mut content = String::new();
-    File::open(path)
-        .unwrap()
-        .read_to_string(&mut content)
-        .unwrap();
-    content.parse().unwrap()
-}
+    let mut builder = T::Builder::default();
+    let mut line = String::new();
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        if reader
+            .read_line(&mut line)
+            .with_context(|| format!("failed to read line {}", line_number + 1))?
+            == 0
+        {
+            break;
+        }
+        line_number += 1;
 
-#[cfg(test)]
-pub fn get_day_test_input(day: &str) -> PathBuf {
-    let mut path = PathBuf::from("input");
-    path.push(day);
-    path.push("test.txt");
-    path
+        let line = line.trim_end_matches(['\n', '\r']);
+        T::push_line(&mut builder, line).with_context(|| ParseLocation::line(line_number, line))?;
+    }
+
+    T::finish(builder)
 }
 
-#[cfg(test)]
-pub fn get_day_extra_test_input(day: &str, extra_test: usize) -> PathBuf {
-    let mut path = PathBuf::from("input");
-    path.push(day);
-    path.push(format!("test_{}.txt", extra_test));
-    path
+///
+/// Parse every line of an already loaded input
+pub fn parse_lines<T>(input: &str) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    parse_from_lines(input.lines())
 }
 
-pub fn get_day_input(day: &str) -> PathBuf {
-    let mut path = PathBuf::from("input");
-    path.push(day);
-    path.push("actual.txt");
-    path
+///
+/// Like [`parse_lines`], but parsing the lines on every core, for inputs with a huge number of
+/// lines. A malformed line fails the whole input, though not necessarily the first one.
+// not behind a feature, rayon is a core dependency which also runs the days and their parts
+pub fn par_parse_lines<T>(input: &str) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr + Send,
+    T::Err: Into<anyhow::Error>,
+{
+    let lines: Vec<&str> = input.lines().collect();
+    lines
+        .into_par_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            line.parse()
+                .map_err(Into::into)
+                .with_context(|| ParseLocation::line(index + 1, line))
+        })
+        .collect()
 }
@@ -0,0 +1,64 @@
+use bumpalo::{collections::Vec as BumpVec, Bump};
+
+///
+/// A bump arena for the short-lived allocations of parsing and solving, like the steps of day 15
+/// or the difference levels of day 9. Allocating is a pointer bump, and everything is freed at
+/// once by `reset`, which keeps the memory for the next input.
+///
+#[derive(Default)]
+pub struct ParseContext {
+    bump: Bump,
+}
+
+impl ParseContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn alloc_str(&self, s: &str) -> &str {
+        self.bump.alloc_str(s)
+    }
+
+    ///
+    /// A slice of `len` elements, the element at each index being `f(index)`
+    pub fn alloc_slice_with<T>(&self, len: usize, f: impl FnMut(usize) -> T) -> &mut [T] {
+        self.bump.alloc_slice_fill_with(len, f)
+    }
+
+    pub fn vec<T>(&self) -> BumpVec<'_, T> {
+        BumpVec::new_in(&self.bump)
+    }
+
+    ///
+    /// Free everything allocated so far, keeping the largest chunk around to be reused
+    pub fn reset(&mut self) {
+        self.bump.reset();
+    }
+
+    pub fn allocated_bytes(&self) -> usize {
+        self.bump.allocated_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_reuses_memory() {
+        let mut arena = ParseContext::new();
+        for _ in 0..3 {
+            assert_eq!(arena.alloc_str("rn"), "rn");
+            let diffs = arena.alloc_slice_with(3, |i| i * 2);
+            assert_eq!(diffs, &[0, 2, 4]);
+            let mut levels = arena.vec();
+            levels.push(&*diffs);
+            assert_eq!(levels.len(), 1);
+        }
+
+        let allocated = arena.allocated_bytes();
+        arena.reset();
+        arena.alloc_slice_with(16, |_| 0u64);
+        assert!(arena.allocated_bytes() <= allocated);
+    }
+}
@@ -0,0 +1,117 @@
+///
+/// A location on a grid, `x` grows to the east and `y` grows to the south
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    ///
+    /// The neighbouring point in the direction, or `None` if it would be below zero
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        let (x, y) = match direction {
+            Direction::North => (self.x, self.y.checked_sub(1)?),
+            Direction::South => (self.x, self.y + 1),
+            Direction::East => (self.x + 1, self.y),
+            Direction::West => (self.x.checked_sub(1)?, self.y),
+        };
+        Some(Self { x, y })
+    }
+
+    ///
+    /// Like `step`, but also `None` if the point would leave a grid of `width` by `height`
+    pub fn step_within(self, direction: Direction, width: usize, height: usize) -> Option<Self> {
+        self.step(direction)
+            .filter(|next| next.x < width && next.y < height)
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+    }
+
+    #[test]
+    fn test_step() {
+        let origin = Point::new(0, 0);
+        assert_eq!(origin.step(Direction::North), None);
+        assert_eq!(origin.step(Direction::West), None);
+        assert_eq!(origin.step(Direction::South), Some(Point::new(0, 1)));
+        assert_eq!(origin.step(Direction::East), Some(Point::new(1, 0)));
+
+        assert_eq!(
+            origin.step_within(Direction::East, 2, 1),
+            Some(Point::new(1, 0))
+        );
+        assert_eq!(Point::new(1, 0).step_within(Direction::East, 2, 1), None);
+        assert_eq!(origin.step_within(Direction::South, 2, 1), None);
+    }
+
+    #[test]
+    fn test_manhattan_distance() {
+        assert_eq!(Point::new(1, 6).manhattan_distance(Point::new(5, 11)), 9);
+        assert_eq!(Point::new(5, 11).manhattan_distance(Point::new(1, 6)), 9);
+    }
+}
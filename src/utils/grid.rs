@@ -0,0 +1,270 @@
+use anyhow::Context;
+use itertools::Itertools;
+
+use crate::error::ParseLocation;
+
+///
+/// A rectangular grid stored row after row, indexed by (row, column)
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    num_rows: usize,
+    num_columns: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> anyhow::Result<Self> {
+        let num_rows = rows.len();
+        let num_columns = rows.first().map(Vec::len).unwrap_or(0);
+        let mut cells = Vec::with_capacity(num_rows * num_columns);
+        for (index, row) in rows.into_iter().enumerate() {
+            anyhow::ensure!(
+                row.len() == num_columns,
+                "row {index} has {} columns instead of {num_columns}",
+                row.len()
+            );
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            cells,
+            num_rows,
+            num_columns,
+        })
+    }
+
+    ///
+    /// Parse a grid with a cell per character and a row per line
+    pub fn parse_chars<F>(s: &str, mut parse_cell: F) -> anyhow::Result<Self>
+    where
+        F: FnMut(char) -> anyhow::Result<T>,
+    {
+        let rows: anyhow::Result<Vec<Vec<T>>> = s
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(column, c)| {
+                        parse_cell(c).with_context(|| ParseLocation::cell(row + 1, column + 1, c))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self::from_rows(rows?)
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        row < self.num_rows && column < self.num_columns
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        if !self.contains(row, column) {
+            return None;
+        }
+        self.cells.get(row * self.num_columns + column)
+    }
+
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut T> {
+        if !self.contains(row, column) {
+            return None;
+        }
+        self.cells.get_mut(row * self.num_columns + column)
+    }
+
+    ///
+    /// Swap two cells in place, e.g. to move an item along a row or column. Panics if either cell
+    /// is outside the grid.
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        assert!(
+            self.contains(a.0, a.1) && self.contains(b.0, b.1),
+            "swapping {a:?} and {b:?} outside of a {}x{} grid",
+            self.num_rows,
+            self.num_columns
+        );
+        self.cells
+            .swap(a.0 * self.num_columns + a.1, b.0 * self.num_columns + b.1);
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row >= self.num_rows {
+            return None;
+        }
+        let start = row * self.num_columns;
+        self.cells.get(start..start + self.num_columns)
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + Clone {
+        // chunks can't be empty, and a grid without columns has no cells anyway
+        self.cells.chunks_exact(self.num_columns.max(1))
+    }
+
+    pub fn column(&self, column: usize) -> impl DoubleEndedIterator<Item = &T> + Clone {
+        let cells = if column < self.num_columns {
+            &self.cells[column..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.num_columns.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + Clone> {
+        (0..self.num_columns).map(|column| self.column(column))
+    }
+
+    ///
+    /// Every cell along with its (row, column), row after row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let num_columns = self.num_columns.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index / num_columns, index % num_columns), cell))
+    }
+
+    pub fn position<P>(&self, mut predicate: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    ///
+    /// The (row, column) of the cells north, south, west and east of the given cell which are
+    /// inside the grid
+    pub fn neighbours(&self, row: usize, column: usize) -> impl Iterator<Item = (usize, usize)> {
+        let candidates = [
+            row.checked_sub(1).map(|row| (row, column)),
+            Some((row + 1, column)),
+            column.checked_sub(1).map(|column| (row, column)),
+            Some((row, column + 1)),
+        ];
+        let (num_rows, num_columns) = (self.num_rows, self.num_columns);
+        candidates
+            .into_iter()
+            .flatten()
+            .filter(move |&(row, column)| row < num_rows && column < num_columns)
+    }
+
+    ///
+    /// The (row, column) of the cells around the given cell, diagonals included, which are inside
+    /// the grid, row by row
+    pub fn surrounding(&self, row: usize, column: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (num_rows, num_columns) = (self.num_rows, self.num_columns);
+        let rows = row.saturating_sub(1)..=row + 1;
+        let columns = column.saturating_sub(1)..=column + 1;
+        rows.cartesian_product(columns)
+            .filter(move |&cell| cell != (row, column))
+            .filter(move |&(row, column)| row < num_rows && column < num_columns)
+    }
+
+    /// Swap the rows and columns of the grid
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flatten().cloned().collect();
+        Self {
+            cells,
+            num_rows: self.num_columns,
+            num_columns: self.num_rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_grid() -> Grid<char> {
+        Grid::parse_chars("abc\ndef", Ok).unwrap()
+    }
+
+    #[test]
+    fn test_access() {
+        let grid = get_grid();
+        assert_eq!((grid.num_rows(), grid.num_columns()), (2, 3));
+        assert_eq!(grid.get(1, 0), Some(&'d'));
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_iterators() {
+        let grid = get_grid();
+        let rows: Vec<&[char]> = grid.rows().rev().collect();
+        assert_eq!(rows, vec![&['d', 'e', 'f'][..], &['a', 'b', 'c'][..]]);
+
+        let columns: Vec<String> = grid.columns().map(|x| x.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+        assert_eq!(grid.column(2).rev().collect::<String>(), "fc");
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let grid = get_grid();
+        let transposed = grid.transpose();
+        assert_eq!(transposed, Grid::parse_chars("ad\nbe\ncf", Ok).unwrap());
+        assert_eq!(transposed.transpose(), grid);
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut grid = get_grid();
+        grid.swap((0, 1), (1, 1));
+        assert_eq!(
+            grid,
+            Grid::parse_chars(
+                "aec
+dbf", Ok
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = get_grid();
+        let neighbours: Vec<(usize, usize)> = grid.neighbours(0, 0).collect();
+        assert_eq!(neighbours, vec![(1, 0), (0, 1)]);
+        let neighbours: Vec<(usize, usize)> = grid.neighbours(1, 1).collect();
+        assert_eq!(neighbours, vec![(0, 1), (1, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn test_surrounding() {
+        let grid = get_grid();
+        let surrounding: Vec<(usize, usize)> = grid.surrounding(0, 0).collect();
+        assert_eq!(surrounding, vec![(0, 1), (1, 0), (1, 1)]);
+        let surrounding: Vec<(usize, usize)> = grid.surrounding(1, 1).collect();
+        assert_eq!(surrounding, vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]);
+        assert_eq!(grid.surrounding(5, 5).count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        assert!(Grid::parse_chars("abc\nde", Ok).is_err());
+        let empty: Grid<char> = Grid::parse_chars("", Ok).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.rows().count(), 0);
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use anyhow::Context;
+use flate2::read::GzDecoder;
+
+use super::{parse_lines, parse_reader_streaming, FromLines};
+use crate::error::AocError;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+///
+/// Read a whole input file, transparently decompressing it if it's gzipped. Invalid utf-8 and
+/// corrupt archives are reported as `io::ErrorKind::InvalidData`.
+pub fn read_input_file<P: AsRef<Path>>(path: P) -> Result<String, AocError> {
+    let path = path.as_ref();
+    read_decompressed(path).map_err(|source| AocError::IoError {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn read_decompressed(path: &Path) -> io::Result<String> {
+    let bytes = fs::read(path)?;
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+
+    let mut content = String::new();
+    GzDecoder::new(bytes.as_slice()).read_to_string(&mut content)?;
+    Ok(content)
+}
+
+///
+/// Open an input file for reading a line at a time, transparently decompressing it if it's gzipped
+fn open_input_reader(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    let mut reader = BufReader::new(fs::File::open(path)?);
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        return Ok(Box::new(BufReader::new(GzDecoder::new(reader))));
+    }
+    Ok(Box::new(reader))
+}
+
+///
+/// Parse an input file a line at a time, so only the builder of `T` and the current line are held
+/// in memory instead of the whole file
+pub fn parse_input_streaming<P, T>(path: P) -> anyhow::Result<T>
+where
+    P: AsRef<Path>,
+    T: FromLines,
+{
+    let path = path.as_ref();
+    let reader = open_input_reader(path).map_err(|source| AocError::IoError {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_reader_streaming(reader).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+///
+/// Like `parse_input`, but returns an error with the path instead of panicking
+pub fn try_parse_input<P, T>(path: P) -> anyhow::Result<T>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let path = path.as_ref();
+    let content = read_input_file(path)?;
+    content
+        .parse()
+        .map_err(Into::into)
+        .with_context(|| format!("failed to parse {}", path.display()))
+}
+
+///
+/// Like `parse_input_lines`, but returns an error with the path and line number instead of
+/// panicking
+pub fn try_parse_input_lines<P, T>(path: P) -> anyhow::Result<Vec<T>>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let path = path.as_ref();
+    let content = read_input_file(path)?;
+    parse_lines(&content).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Relocates the inputs, so an installed binary can be run from anywhere
+pub const INPUT_DIR_ENV_VAR: &str = "AOC_INPUT_DIR";
+
+static INPUT_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+///
+/// Look for the inputs in `dir` instead of `input/`. Only the first call has any effect, and
+/// `AOC_INPUT_DIR` takes precedence over it
+pub fn set_input_dir(dir: PathBuf) -> anyhow::Result<()> {
+    INPUT_DIR
+        .set(dir)
+        .map_err(|_| anyhow::anyhow!("the input directory is already set"))
+}
+
+fn input_dir_from_env(value: Option<OsString>) -> Option<PathBuf> {
+    value.filter(|dir| !dir.is_empty()).map(PathBuf::from)
+}
+
+///
+/// The directory holding a directory of inputs per day. That's `AOC_INPUT_DIR` when it's set,
+/// otherwise the directory given to `set_input_dir`, otherwise `input/`.
+pub fn input_dir() -> &'static Path {
+    static FROM_ENV: OnceLock<Option<PathBuf>> = OnceLock::new();
+    FROM_ENV
+        .get_or_init(|| input_dir_from_env(env::var_os(INPUT_DIR_ENV_VAR)))
+        .as_deref()
+        .or(INPUT_DIR.get().map(PathBuf::as_path))
+        .unwrap_or(Path::new(DEFAULT_INPUT_DIR))
+}
+
+///
+/// The path of the example input from the puzzle description
+pub fn get_day_test_input(day: &str) -> PathBuf {
+    let mut path = input_dir().to_path_buf();
+    path.push(day);
+    path.push("test.txt");
+    path
+}
+
+#[cfg(test)]
+pub fn get_day_extra_test_input(day: &str, extra_test: usize) -> PathBuf {
+    let mut path = input_dir().to_path_buf();
+    path.push(day);
+    path.push(format!("test_{}.txt", extra_test));
+    path
+}
+
+///
+/// The path of `actual.txt`, or of `actual.txt.gz` if only the compressed input exists
+pub fn get_day_input(day: &str) -> PathBuf {
+    let mut path = input_dir().to_path_buf();
+    path.push(day);
+    path.push("actual.txt");
+    if !path.exists() {
+        let compressed = path.with_extension("txt.gz");
+        if compressed.exists() {
+            return compressed;
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, io::Write};
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+    use crate::error::ParseLocation;
+
+    #[test]
+    fn test_try_parse_input_errors() {
+        let missing = try_parse_input::<_, u32>("input/missing/actual.txt").unwrap_err();
+        assert!(format!("{missing:#}").contains("input/missing/actual.txt"));
+
+        let path = env::temp_dir().join(format!("aoc-utils-test-{}.txt", std::process::id()));
+        fs::write(&path, "1\ntwo\n3").unwrap();
+        let invalid_line = try_parse_input_lines::<_, u32>(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(format!("{invalid_line:#}").contains("failed to parse line 2: two"));
+    }
+
+    #[test]
+    fn test_input_dir_from_env() {
+        assert_eq!(
+            input_dir_from_env(Some("/srv/aoc/input".into())),
+            Some(PathBuf::from("/srv/aoc/input"))
+        );
+        assert_eq!(input_dir_from_env(Some("".into())), None);
+        assert_eq!(input_dir_from_env(None), None);
+    }
+
+    #[test]
+    fn test_read_gzipped_input() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"1\n2\n3\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let path = env::temp_dir().join(format!("aoc-utils-test-{}.txt.gz", std::process::id()));
+        fs::write(&path, compressed).unwrap();
+        let parsed = try_parse_input_lines::<_, u32>(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(parsed.unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_parse_input_streaming() {
+        let path = env::temp_dir().join(format!("aoc-streaming-test-{}.txt", std::process::id()));
+        fs::write(&path, "1\r\n2\n3").unwrap();
+        let parsed = parse_input_streaming::<_, Vec<u32>>(&path);
+        fs::write(&path, "1\ntwo\n3\n").unwrap();
+        let invalid_line = parse_input_streaming::<_, Vec<u32>>(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(parsed.unwrap(), vec![1, 2, 3]);
+        assert!(format!("{invalid_line:#}").contains("failed to parse line 2: two"));
+        assert_eq!(
+            invalid_line.downcast_ref::<ParseLocation>().map(|l| l.line),
+            Some(2)
+        );
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"4\n5\n").unwrap();
+        let path = path.with_extension("txt.gz");
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+        let parsed = parse_input_streaming::<_, Vec<u32>>(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(parsed.unwrap(), vec![4, 5]);
+
+        assert!(parse_input_streaming::<_, Vec<u32>>("input/missing/actual.txt").is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use std::ops::Range;
+
+/// Sorts the ranges and coalesces any overlapping or adjacent ones in place.
+pub fn merge_ranges(ranges: &mut Vec<Range<u64>>) {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_unstable_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges.drain(..) {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    *ranges = merged;
+}
+
+///
+/// Split the range into the part below `at` and the part from `at` onwards, either of which may be
+/// empty
+pub fn split_range(range: Range<u64>, at: u64) -> (Range<u64>, Range<u64>) {
+    let at = at.clamp(range.start, range.end.max(range.start));
+    (range.start..at, at..range.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_ranges() {
+        let mut ranges = vec![1..3, 2..5, 10..12];
+        merge_ranges(&mut ranges);
+        assert_eq!(ranges, vec![1..5, 10..12]);
+    }
+
+    #[test]
+    fn test_merge_adjacent_ranges() {
+        let mut ranges = vec![5..8, 1..5, 8..8, 9..10];
+        merge_ranges(&mut ranges);
+        assert_eq!(ranges, vec![1..8, 9..10]);
+    }
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(split_range(1..10, 5), (1..5, 5..10));
+        assert_eq!(split_range(1..10, 0), (1..1, 1..10));
+        assert_eq!(split_range(1..10, 20), (1..10, 10..10));
+        assert!(split_range(5..5, 3).0.is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+///
+/// The greatest common divisor, with Euclid's algorithm
+pub fn gcd(a: u64, b: u64) -> u64 {
+    let (mut a, mut b) = (a.max(b), b.min(a));
+
+    while b != 0 {
+        let tmp = b;
+        b = a % b;
+        a = tmp;
+    }
+
+    a
+}
+
+///
+/// The least common multiple, dividing before multiplying so it only overflows if the result does.
+/// Zero if either is zero.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(18, 12), 6);
+        assert_eq!(gcd(7, 13), 1);
+        assert_eq!(gcd(0, 5), 5);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(7, 13), 91);
+        assert_eq!(lcm(0, 5), 0);
+        assert_eq!(
+            [3739, 3761, 3797, 3889].into_iter().fold(1, lcm),
+            207652583562007
+        );
+    }
+}
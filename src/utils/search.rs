@@ -0,0 +1,160 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+///
+/// A state waiting in the queue, ordered so the cheapest one is popped first out of the max-heap
+struct Queued<S> {
+    cost: u64,
+    state: S,
+}
+
+impl<S> PartialEq for Queued<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl<S> Eq for Queued<S> {}
+
+impl<S> PartialOrd for Queued<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Queued<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+///
+/// The lowest total cost from any of the starting states to a state satisfying `is_goal`, with
+/// Dijkstra's algorithm over a binary heap. `successors` gives the states reachable from a state
+/// along with the cost of moving to each of them. `None` if no goal is reachable.
+///
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<u64>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut best: HashMap<S, u64> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for state in starts {
+        best.insert(state.clone(), 0);
+        queue.push(Queued { cost: 0, state });
+    }
+
+    while let Some(Queued { cost, state }) = queue.pop() {
+        if is_goal(&state) {
+            return Some(cost);
+        }
+        // a cheaper way to this state was already expanded
+        if best.get(&state).is_some_and(|&best| best < cost) {
+            continue;
+        }
+
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            match best.entry(next.clone()) {
+                Entry::Occupied(mut entry) if *entry.get() > next_cost => {
+                    entry.insert(next_cost);
+                }
+                Entry::Occupied(_) => continue,
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                }
+            }
+            queue.push(Queued {
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    None
+}
+
+///
+/// The fewest steps from any of the starting states to every state reachable in at most
+/// `max_steps`, with a breadth-first search. `successors` gives the states one step away from a
+/// state. This is also a flood fill, with `max_steps` as `usize::MAX`.
+///
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    max_steps: usize,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut steps: HashMap<S, usize> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for state in starts {
+        if let Entry::Vacant(entry) = steps.entry(state.clone()) {
+            entry.insert(0);
+            queue.push_back((state, 0));
+        }
+    }
+
+    while let Some((state, distance)) = queue.pop_front() {
+        if distance == max_steps {
+            continue;
+        }
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = steps.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dijkstra() {
+        // the direct edge from 0 to 3 is more expensive than going around
+        let edges = [(0, 1, 1), (1, 2, 2), (2, 3, 1), (0, 3, 10), (4, 3, 1)];
+        let successors = |&node: &u32| {
+            edges
+                .iter()
+                .filter(move |&&(from, _, _)| from == node)
+                .map(|&(_, to, cost)| (to, cost))
+        };
+
+        assert_eq!(dijkstra([0], successors, |&node| node == 3), Some(4));
+        assert_eq!(dijkstra([0, 2], successors, |&node| node == 3), Some(1));
+        assert_eq!(dijkstra([3], successors, |&node| node == 3), Some(0));
+        assert_eq!(dijkstra([0], successors, |&node| node == 4), None);
+    }
+
+    #[test]
+    fn test_bfs() {
+        // a line of nodes, where each one leads to the next two
+        let successors = |&node: &u32| [node + 1, node + 2].into_iter().filter(|&x| x < 10);
+
+        let steps = bfs([0], successors, usize::MAX);
+        assert_eq!(steps.len(), 10);
+        assert_eq!(steps[&0], 0);
+        assert_eq!(steps[&1], 1);
+        assert_eq!(steps[&9], 5);
+
+        let steps = bfs([0, 5], successors, 1);
+        let mut reached: Vec<u32> = steps.into_keys().collect();
+        reached.sort_unstable();
+        assert_eq!(reached, vec![0, 1, 2, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,171 @@
+//! Scans over the bytes of a grid row. With the nightly-only `simd` feature a row is compared 32
+//! bytes at a time with `std::simd`, otherwise a byte at a time. Both give the same results.
+
+#[cfg(feature = "simd")]
+pub use vector::*;
+
+#[cfg(not(feature = "simd"))]
+pub use scalar::*;
+
+///
+/// How two rows compare, when all that matters is whether they differ in at most one byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowComparison {
+    Equal,
+    OneMismatch,
+    Different,
+}
+
+/// A symbol is anything but a digit or a `.`, like the parts next to the numbers of day 3
+fn is_symbol(byte: u8) -> bool {
+    !byte.is_ascii_digit() && byte != b'.'
+}
+
+mod scalar {
+    use super::{is_symbol, RowComparison};
+
+    ///
+    /// How many bytes of the row are `byte`
+    pub fn count_matching(row: &[u8], byte: u8) -> usize {
+        row.iter().filter(|x| **x == byte).count()
+    }
+
+    ///
+    /// The index of the first symbol in the row, see `is_symbol`
+    pub fn find_symbol(row: &[u8]) -> Option<usize> {
+        row.iter().position(|x| is_symbol(*x))
+    }
+
+    ///
+    /// Compare two rows, stopping at the second mismatch. Rows of different lengths are always
+    /// `Different`.
+    pub fn compare_rows(a: &[u8], b: &[u8]) -> RowComparison {
+        if a.len() != b.len() {
+            return RowComparison::Different;
+        }
+
+        let mut mismatches = a.iter().zip(b).filter(|(a, b)| a != b);
+        match (mismatches.next(), mismatches.next()) {
+            (None, _) => RowComparison::Equal,
+            (Some(_), None) => RowComparison::OneMismatch,
+            (Some(_), Some(_)) => RowComparison::Different,
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+mod vector {
+    use std::simd::{
+        cmp::{SimdPartialEq, SimdPartialOrd},
+        u8x32,
+    };
+
+    use super::{scalar, RowComparison};
+
+    const LANES: usize = 32;
+
+    pub fn count_matching(row: &[u8], byte: u8) -> usize {
+        let (chunks, rest) = row.as_chunks::<LANES>();
+        let needle = u8x32::splat(byte);
+        let in_chunks: u32 = chunks
+            .iter()
+            .map(|chunk| {
+                u8x32::from_array(*chunk)
+                    .simd_eq(needle)
+                    .to_bitmask()
+                    .count_ones()
+            })
+            .sum();
+        in_chunks as usize + scalar::count_matching(rest, byte)
+    }
+
+    pub fn find_symbol(row: &[u8]) -> Option<usize> {
+        let (chunks, rest) = row.as_chunks::<LANES>();
+        for (index, chunk) in chunks.iter().enumerate() {
+            let chunk = u8x32::from_array(*chunk);
+            let digits = chunk.simd_ge(u8x32::splat(b'0')) & chunk.simd_le(u8x32::splat(b'9'));
+            let symbols = !digits & chunk.simd_ne(u8x32::splat(b'.'));
+            let bitmask = symbols.to_bitmask();
+            if bitmask != 0 {
+                return Some(index * LANES + bitmask.trailing_zeros() as usize);
+            }
+        }
+        scalar::find_symbol(rest).map(|index| chunks.len() * LANES + index)
+    }
+
+    pub fn compare_rows(a: &[u8], b: &[u8]) -> RowComparison {
+        if a.len() != b.len() {
+            return RowComparison::Different;
+        }
+
+        let (a_chunks, a_rest) = a.as_chunks::<LANES>();
+        let (b_chunks, b_rest) = b.as_chunks::<LANES>();
+        let mut mismatches = 0;
+        for (a, b) in a_chunks.iter().zip(b_chunks) {
+            let unequal = u8x32::from_array(*a).simd_ne(u8x32::from_array(*b));
+            mismatches += unequal.to_bitmask().count_ones();
+            if mismatches > 1 {
+                return RowComparison::Different;
+            }
+        }
+
+        match (mismatches, scalar::compare_rows(a_rest, b_rest)) {
+            (0, rest) => rest,
+            (_, RowComparison::Equal) => RowComparison::OneMismatch,
+            _ => RowComparison::Different,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scans_match_scalar() {
+        // long enough for a few whole vectors and a remainder
+        let row: Vec<u8> = (0..100)
+            .map(|i| match i % 7 {
+                0 => b'#',
+                1 | 2 => b'4',
+                _ => b'.',
+            })
+            .collect();
+        assert_eq!(count_matching(&row, b'#'), 15);
+        assert_eq!(
+            count_matching(&row, b'#'),
+            scalar::count_matching(&row, b'#')
+        );
+
+        let no_symbols: Vec<u8> = row
+            .iter()
+            .map(|x| if *x == b'#' { b'.' } else { *x })
+            .collect();
+        assert_eq!(find_symbol(&no_symbols), None);
+        for index in [0, 31, 32, 70, 99] {
+            let mut with_symbol = no_symbols.clone();
+            with_symbol[index] = b'*';
+            assert_eq!(find_symbol(&with_symbol), Some(index));
+        }
+        assert_eq!(find_symbol(&row), scalar::find_symbol(&row));
+    }
+
+    #[test]
+    fn test_compare_rows() {
+        let row = vec![b'.'; 70];
+        assert_eq!(compare_rows(&row, &row), RowComparison::Equal);
+        assert_eq!(compare_rows(&row, &row[1..]), RowComparison::Different);
+
+        for index in [0, 33, 69] {
+            let mut smudged = row.clone();
+            smudged[index] = b'#';
+            assert_eq!(compare_rows(&row, &smudged), RowComparison::OneMismatch);
+            for other in [1, 40, 68] {
+                let mut twice = smudged.clone();
+                twice[other] = b'#';
+                assert_eq!(compare_rows(&row, &twice), RowComparison::Different);
+                assert_eq!(scalar::compare_rows(&row, &twice), RowComparison::Different);
+            }
+        }
+    }
+}
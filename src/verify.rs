@@ -0,0 +1,281 @@
+use std::{collections::BTreeMap, fmt::Display, fs, path::Path};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::solution::{Answer, DayRun, Part};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+///
+/// Answers can be written either as numbers or as strings
+///
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum ExpectedAnswer {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for ExpectedAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectedAnswer::Number(number) => write!(f, "{number}"),
+            ExpectedAnswer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<ExpectedAnswer>,
+    part2: Option<ExpectedAnswer>,
+}
+
+///
+/// The known correct answers, e.g.
+/// ```toml
+/// [day6]
+/// part1 = 288
+/// part2 = 71503
+/// ```
+///
+#[derive(Debug)]
+pub struct ExpectedAnswers {
+    days: BTreeMap<u32, DayAnswers>,
+}
+
+impl ExpectedAnswers {
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let tables: BTreeMap<String, DayAnswers> =
+            toml::from_str(s).context("failed to parse answers")?;
+
+        let mut days = BTreeMap::new();
+        for (name, answers) in tables {
+            let day = name
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .with_context(|| format!("invalid day table: {name}, expected e.g. [day6]"))?;
+            days.insert(day, answers);
+        }
+
+        Ok(Self { days })
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("invalid answers file {}", path.display()))
+    }
+
+    ///
+    /// Like `load`, but a missing file is no answers at all rather than an error
+    pub fn load_if_exists(path: &Path) -> anyhow::Result<Option<Self>> {
+        if path.exists() {
+            Self::load(path).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn days(&self) -> Vec<u32> {
+        self.days.keys().copied().collect()
+    }
+
+    ///
+    /// The parts with a known answer for the day
+    pub fn parts(&self, day: u32) -> Vec<Part> {
+        Part::ALL
+            .into_iter()
+            .filter(|&part| self.get(day, part).is_some())
+            .collect()
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&ExpectedAnswer> {
+        let answers = self.days.get(&day)?;
+        match part {
+            Part::One => answers.part1.as_ref(),
+            Part::Two => answers.part2.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VerifyStatus {
+    Pass,
+    Fail { expected: String, actual: String },
+    Error(String),
+}
+
+#[derive(Debug)]
+pub struct VerifyEntry {
+    pub day: u32,
+    pub part: Part,
+    pub status: VerifyStatus,
+}
+
+impl Display for VerifyEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {} part {}: ", self.day, self.part)?;
+        match &self.status {
+            VerifyStatus::Pass => write!(f, "ok"),
+            VerifyStatus::Fail { expected, actual } => {
+                write!(f, "FAIL, expected {expected} but got {actual}")
+            }
+            VerifyStatus::Error(e) => write!(f, "ERROR, {e}"),
+        }
+    }
+}
+
+///
+/// Answers are compared as text, so e.g. an expected number matches a numeric answer of any type
+pub fn compare(expected: &ExpectedAnswer, actual: &Answer) -> VerifyStatus {
+    let (expected, actual) = (expected.to_string(), actual.to_string());
+    if expected == actual {
+        VerifyStatus::Pass
+    } else {
+        VerifyStatus::Fail { expected, actual }
+    }
+}
+
+///
+/// Compare the results of running the days against the expected answers, with an entry per
+/// expected answer
+pub fn verify(
+    expected: &ExpectedAnswers,
+    results: &[(u32, anyhow::Result<DayRun>)],
+) -> Vec<VerifyEntry> {
+    let mut entries = Vec::new();
+    for day in expected.days() {
+        let day_run = results
+            .iter()
+            .find(|(result_day, _)| *result_day == day)
+            .map(|(_, day_run)| day_run);
+
+        for part in expected.parts(day) {
+            let expected_answer = expected.get(day, part).expect("only parts with answers");
+            let status = match day_run {
+                None => VerifyStatus::Error("wasn't run".to_string()),
+                Some(Err(e)) => VerifyStatus::Error(format!("{e:#}")),
+                Some(Ok(day_run)) => match day_run.parts.iter().find(|x| x.part == part) {
+                    None => VerifyStatus::Error("wasn't run".to_string()),
+                    Some(part_run) => match &part_run.answer {
+                        Err(e) => VerifyStatus::Error(format!("{e:#}")),
+                        Ok(actual) => compare(expected_answer, actual),
+                    },
+                },
+            };
+
+            entries.push(VerifyEntry { day, part, status });
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::solution::PartRun;
+
+    use super::*;
+
+    fn get_day_run(answers: Vec<anyhow::Result<Answer>>) -> DayRun {
+        DayRun {
+            parse_time: Duration::ZERO,
+            parts: Part::ALL
+                .into_iter()
+                .zip(answers)
+                .map(|(part, answer)| PartRun {
+                    part,
+                    answer,
+                    solve_time: Duration::ZERO,
+                    peak_memory: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_expected_answers() {
+        let expected = ExpectedAnswers::parse(
+            "[day6]
+part1 = 288
+part2 = \"71503\"
+
+[day15]
+part1 = 1320
+",
+        )
+        .unwrap();
+
+        assert_eq!(expected.days(), vec![6, 15]);
+        assert_eq!(expected.parts(15), vec![Part::One]);
+        assert_eq!(
+            expected.get(6, Part::Two),
+            Some(&ExpectedAnswer::Text("71503".to_string()))
+        );
+        assert!(ExpectedAnswers::parse("[six]\npart1 = 1").is_err());
+        assert!(ExpectedAnswers::parse("[day6]\npart3 = 1").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(
+            compare(&ExpectedAnswer::Number(288), &Answer::Usize(288)),
+            VerifyStatus::Pass
+        );
+        assert_eq!(
+            compare(&ExpectedAnswer::Text("288".to_string()), &Answer::U64(289)),
+            VerifyStatus::Fail {
+                expected: "288".to_string(),
+                actual: "289".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let expected = ExpectedAnswers::parse(
+            "[day6]\npart1 = 288\npart2 = 1\n[day7]\npart1 = 6440\n[day8]\npart2 = 6",
+        )
+        .unwrap();
+        let results = vec![
+            (
+                6,
+                Ok(get_day_run(vec![
+                    Ok(Answer::U64(288)),
+                    Ok(Answer::U64(71503)),
+                ])),
+            ),
+            (7, Err(anyhow::anyhow!("missing input"))),
+        ];
+
+        let statuses: Vec<(u32, Part, VerifyStatus)> = verify(&expected, &results)
+            .into_iter()
+            .map(|x| (x.day, x.part, x.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (6, Part::One, VerifyStatus::Pass),
+                (
+                    6,
+                    Part::Two,
+                    VerifyStatus::Fail {
+                        expected: "1".to_string(),
+                        actual: "71503".to_string()
+                    }
+                ),
+                (
+                    7,
+                    Part::One,
+                    VerifyStatus::Error("missing input".to_string())
+                ),
+                (8, Part::Two, VerifyStatus::Error("wasn't run".to_string())),
+            ]
+        );
+    }
+}
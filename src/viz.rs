@@ -0,0 +1,165 @@
+use std::{collections::HashSet, fmt::Write};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use anyhow::Context;
+
+use crate::{day10, day11, day16, utils::geom::Point};
+
+pub const VIZ_DIR: &str = "viz";
+
+/// The size in pixels of a single cell in the svg drawings
+const SVG_CELL_SIZE: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum VizFormat {
+    Ascii,
+    Svg,
+}
+
+impl VizFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            VizFormat::Ascii => "txt",
+            VizFormat::Svg => "svg",
+        }
+    }
+}
+
+///
+/// A day's state which can be drawn, with a character per cell of the puzzle's grid
+///
+pub trait Render {
+    fn render_ascii(&self) -> anyhow::Result<String>;
+
+    ///
+    /// By default the ascii drawing with a filled square per character other than `.`
+    fn render_svg(&self) -> anyhow::Result<String> {
+        Ok(ascii_to_svg(&self.render_ascii()?))
+    }
+
+    fn render(&self, format: VizFormat) -> anyhow::Result<String> {
+        match format {
+            VizFormat::Ascii => self.render_ascii(),
+            VizFormat::Svg => self.render_svg(),
+        }
+    }
+}
+
+///
+/// Draw a `width` by `height` grid with the given points as `#` and everything else as `.`
+pub fn draw_points(points: &HashSet<Point>, width: usize, height: usize) -> String {
+    let mut drawing = String::with_capacity((width + 1) * height);
+    for y in 0..height {
+        for x in 0..width {
+            if points.contains(&Point { x, y }) {
+                drawing.push('#');
+            } else {
+                drawing.push('.');
+            }
+        }
+        drawing.push('\n');
+    }
+
+    drawing
+}
+
+pub fn ascii_to_svg(ascii: &str) -> String {
+    let num_rows = ascii.lines().count();
+    let num_columns = ascii.lines().map(|x| x.chars().count()).max().unwrap_or(0);
+    let (width, height) = (num_columns * SVG_CELL_SIZE, num_rows * SVG_CELL_SIZE);
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )
+    .expect("writing to a string can't fail");
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    for (y, line) in ascii.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let fill = match c {
+                '.' | ' ' => continue,
+                '#' => "black",
+                _ => "gray",
+            };
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{SVG_CELL_SIZE}" height="{SVG_CELL_SIZE}" fill="{fill}"/>"#,
+                x * SVG_CELL_SIZE,
+                y * SVG_CELL_SIZE
+            )
+            .expect("writing to a string can't fail");
+        }
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+///
+/// Draw the parsed input for the days that support it
+///
+pub fn render(day: u32, input: &str, format: VizFormat) -> Option<anyhow::Result<String>> {
+    let drawing = match day {
+        10 => input
+            .parse::<day10::Grid>()
+            .and_then(|grid| grid.render(format)),
+        11 => input
+            .parse::<day11::Image>()
+            .and_then(|image| image.render(format)),
+        16 => input
+            .parse::<day16::Contraption>()
+            .and_then(|contraption| contraption.render(format)),
+        _ => return None,
+    };
+
+    Some(drawing)
+}
+
+///
+/// Draw a day against the given input into `dir/day<N>.<extension>`, returning the path
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_viz(day: u32, input: &str, format: VizFormat, dir: &Path) -> anyhow::Result<PathBuf> {
+    let drawing = render(day, input, format)
+        .with_context(|| format!("day {day} doesn't have a visualization"))??;
+
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let path = dir.join(format!("day{day}.{}", format.extension()));
+    fs::write(&path, drawing).with_context(|| format!("failed to write {}", path.display()))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_points() {
+        let points = HashSet::from([Point::new(0, 0), Point::new(2, 1)]);
+        assert_eq!(draw_points(&points, 3, 2), "#..\n..#\n");
+    }
+
+    #[test]
+    fn test_ascii_to_svg() {
+        let svg = ascii_to_svg("#.\n.S\n");
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16""#)
+        );
+        assert!(svg.contains(r#"<rect x="0" y="0" width="8" height="8" fill="black"/>"#));
+        assert!(svg.contains(r#"<rect x="8" y="8" width="8" height="8" fill="gray"/>"#));
+        assert_eq!(svg.matches("<rect").count(), 3);
+    }
+
+    #[test]
+    fn test_render() {
+        let drawing = render(11, "#..\n...\n..#\n", VizFormat::Ascii).unwrap();
+        assert_eq!(drawing.unwrap(), "#...\n....\n....\n...#\n");
+        assert!(render(6, "", VizFormat::Ascii).is_none());
+    }
+}
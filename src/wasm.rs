@@ -0,0 +1,32 @@
+use wasm_bindgen::prelude::*;
+
+use crate::solution::Part;
+
+fn try_solve(day: u32, part: u32, input: &str) -> anyhow::Result<String> {
+    let part = u8::try_from(part)
+        .map_err(|_| anyhow::anyhow!("invalid part: {part}"))
+        .and_then(Part::try_from)?;
+    Ok(crate::solve(day, part, input)?)
+}
+
+///
+/// Solve a part of a day for the browser playground. Errors are returned as the answer, prefixed
+/// with `error: `, so they can be shown as is
+#[wasm_bindgen]
+pub fn solve(day: u32, part: u32, input: &str) -> String {
+    try_solve(day, part, input).unwrap_or_else(|e| format!("error: {e:#}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        assert_eq!(solve(6, 1, input), "288");
+        assert_eq!(solve(6, 2, input), "71503");
+        assert_eq!(solve(6, 3, input), "error: invalid part: 3");
+        assert_eq!(solve(26, 1, input), "error: day 26 isn't implemented");
+    }
+}